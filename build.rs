@@ -0,0 +1,40 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+fn main() {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let git_hash = git_hash().unwrap_or_else(|| "unknown".into());
+    let rustc_version = rustc_version().unwrap_or_else(|| "unknown".into());
+
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={timestamp}");
+    println!("cargo:rustc-env=BUILD_GIT_HASH={git_hash}");
+    println!("cargo:rustc-env=BUILD_RUSTC_VERSION={rustc_version}");
+}
+
+fn git_hash() -> Option<String> {
+    let head = fs::read_to_string(".git/HEAD").ok()?;
+    let hash = match head.trim().strip_prefix("ref: ") {
+        Some(reference) => fs::read_to_string(Path::new(".git").join(reference))
+            .ok()
+            .or_else(|| packed_ref(reference))?,
+        None => head,
+    };
+
+    Some(hash.trim().chars().take(7).collect())
+}
+
+fn packed_ref(reference: &str) -> Option<String> {
+    fs::read_to_string(".git/packed-refs").ok()?.lines().find_map(|line| {
+        let (hash, name) = line.split_once(' ')?;
+        (name == reference).then(|| hash.into())
+    })
+}
+
+fn rustc_version() -> Option<String> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+
+    Some(String::from_utf8(output.stdout).ok()?.trim().into())
+}
@@ -0,0 +1,46 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+/// implements `ConvertArgument` for a newtype by converting the wrapped type
+#[proc_macro_derive(ConvertArgument)]
+pub fn derive_convert_argument(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let inner = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => return newtype_error(&input),
+        },
+        _ => return newtype_error(&input),
+    };
+
+    quote! {
+        #[::async_trait::async_trait]
+        impl crate::utilities::convert_argument::ConvertArgument for #name {
+            async fn convert<'a>(
+                ctx: &crate::utilities::command_context::CommandContext,
+                arguments: &'a str,
+            ) -> ::std::result::Result<
+                (Self, &'a str),
+                crate::utilities::convert_argument::ConversionError,
+            > {
+                let (value, arguments) =
+                    <#inner as crate::utilities::convert_argument::ConvertArgument>::convert(
+                        ctx, arguments,
+                    )
+                    .await?;
+
+                Ok((Self(value), arguments))
+            }
+        }
+    }
+    .into()
+}
+
+fn newtype_error(input: &DeriveInput) -> TokenStream {
+    syn::Error::new_spanned(&input.ident, "ConvertArgument can only be derived for newtypes")
+        .to_compile_error()
+        .into()
+}
@@ -1,15 +1,26 @@
+pub mod a1111;
+pub mod bing;
 pub mod cobalt;
 pub mod craiyon;
+pub mod cratesio;
 pub mod different_dimension_me;
+pub mod github;
 pub mod google;
+pub mod google_tts;
+pub mod ipinfo;
 pub mod kiwifarms;
 pub mod makersuite;
 pub mod mathjs;
 pub mod microlink;
 pub mod moveit;
+pub mod open_graph;
 pub mod openai;
 pub mod poligon;
+pub mod spotify;
 pub mod stablehorde;
 pub mod translate;
 pub mod urbandictionary;
 pub mod urbansharing;
+pub mod wikipedia;
+pub mod wolfram;
+pub mod youtube;
@@ -0,0 +1,127 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+#[derive(Serialize)]
+pub struct Txt2ImgRequest<'a> {
+    pub prompt: &'a str,
+    pub negative_prompt: &'a str,
+    pub steps: u32,
+    pub cfg_scale: f32,
+    /// -1 picks a random seed
+    pub seed: i64,
+    pub width: u32,
+    pub height: u32,
+    pub sampler_name: &'a str,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    images: Vec<String>,
+    info: String,
+}
+
+#[derive(Deserialize)]
+pub struct GenerationInfo {
+    pub seed: i64,
+    pub sampler_name: String,
+    pub steps: u32,
+    pub cfg_scale: f32,
+}
+
+pub struct A1111Response {
+    /// PNG images
+    pub images: Vec<Vec<u8>>,
+    pub info: GenerationInfo,
+}
+
+pub async fn generate(
+    http_client: reqwest::Client,
+    base_url: &str,
+    txt2img_request: &Txt2ImgRequest<'_>,
+) -> Result<A1111Response, CommandError> {
+    let response = http_client
+        .post(format!("{}/sdapi/v1/txt2img", base_url.trim_end_matches('/')))
+        .json(txt2img_request)
+        .send()
+        .await?
+        .server_error()?
+        .error_for_status()?
+        .json::<Response>()
+        .await?;
+
+    let images = response
+        .images
+        .into_iter()
+        .map(|image| STANDARD.decode(image))
+        .collect::<Result<_, _>>()
+        .map_err(|_| "the server returned an invalid image.")?;
+
+    // `info` is JSON serialized into a string
+    let info = serde_json::from_str(&response.info)
+        .map_err(|_| "the server returned invalid generation info.")?;
+
+    Ok(A1111Response { images, info })
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageFormat};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::*;
+
+    const REQUEST: Txt2ImgRequest = Txt2ImgRequest {
+        prompt: "a cat",
+        negative_prompt: "",
+        steps: 1,
+        cfg_scale: 7.0,
+        seed: -1,
+        width: 1,
+        height: 1,
+        sampler_name: "Euler a",
+    };
+
+    #[tokio::test]
+    async fn test_generate() {
+        let mut image = Vec::new();
+        DynamicImage::new_rgb8(1, 1)
+            .write_to(&mut Cursor::new(&mut image), ImageFormat::Png)
+            .unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sdapi/v1/txt2img"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "images": [STANDARD.encode(&image)],
+                "info": r#"{"seed": 42, "sampler_name": "Euler a", "steps": 1, "cfg_scale": 7.0}"#,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = generate(reqwest::Client::new(), &server.uri(), &REQUEST).await.unwrap();
+
+        assert_eq!(response.images, [image]);
+        assert_eq!(response.info.seed, 42);
+    }
+
+    #[tokio::test]
+    async fn test_generate_server_error() {
+        let server = MockServer::start().await;
+        // only HTML error pages are reported as server errors
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(502).insert_header("content-type", "text/html"))
+            .mount(&server)
+            .await;
+
+        let result = generate(reqwest::Client::new(), &server.uri(), &REQUEST).await;
+        assert!(matches!(result, Err(CommandError::Server(_))));
+    }
+}
@@ -0,0 +1,42 @@
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+#[derive(Deserialize)]
+struct Response {
+    value: Vec<Image>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Image {
+    pub content_url: String,
+}
+
+/// searches Bing Images with the strict safe search setting
+pub async fn image_search(
+    http_client: reqwest::Client,
+    api_key: &str,
+    query: &str,
+    count: usize,
+) -> Result<Vec<Image>, CommandError> {
+    let response = http_client
+        .get(
+            Url::parse_with_params(
+                "https://api.bing.microsoft.com/v7.0/images/search",
+                [("q", query), ("count", &count.to_string()), ("safeSearch", "Strict")],
+            )
+            .unwrap(),
+        )
+        .header("Ocp-Apim-Subscription-Key", api_key)
+        .send()
+        .await?
+        .server_error()?
+        .error_for_status()?
+        .json::<Response>()
+        .await?;
+
+    Ok(response.value)
+}
@@ -1,12 +1,14 @@
 use std::time::{Duration, Instant};
 
+use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
 use crate::commands::CommandError;
-use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::api_utils::{DetectServerError, ServerError};
 
 const DRAW_VERSION: &str = "35s5hfwn9n78gb06";
 const SEARCH_VERSION: &str = "hpv3obayw36clkqp";
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
 #[derive(Serialize)]
 struct Payload<'a> {
@@ -31,6 +33,12 @@ struct Response {
     next_prompt: String,
 }
 
+pub enum CraiyonError {
+    Timeout(Duration),
+    Server(ServerError),
+    Network(reqwest::Error),
+}
+
 pub struct GenerationResult {
     pub images: Vec<String>,
     pub next_prompt: String,
@@ -42,17 +50,26 @@ pub async fn draw(
     model: Model,
     negative_prompt: &str,
     prompt: &str,
-) -> Result<GenerationResult, CommandError> {
+    timeout: Duration,
+) -> Result<GenerationResult, CraiyonError> {
     let start = Instant::now();
-    let response = http_client
-        .post("https://api.craiyon.com/v3")
-        .json(&Payload { model, prompt, negative_prompt, version: DRAW_VERSION })
-        .send()
-        .await?
-        .server_error()?
-        .error_for_status()?
-        .json::<Response>()
-        .await?;
+    let response = tokio::time::timeout(
+        timeout,
+        http_client
+            .post("https://api.craiyon.com/v3")
+            .json(&Payload { model, prompt, negative_prompt, version: DRAW_VERSION })
+            .send(),
+    )
+    .await
+    .map_err(|_| CraiyonError::Timeout(timeout))?
+    .map_err(CraiyonError::Network)?
+    .server_error()
+    .map_err(CraiyonError::Server)?
+    .error_for_status()
+    .map_err(CraiyonError::Network)?
+    .json::<Response>()
+    .await
+    .map_err(CraiyonError::Network)?;
 
     let duration = start.elapsed();
 
@@ -68,13 +85,18 @@ pub struct SearchResult {
     pub prompt: String,
 }
 
+/// also used by /health to check the service without generating anything
+pub fn search_request(http_client: &reqwest::Client, text: &str) -> RequestBuilder {
+    http_client
+        .post("https://search.craiyon.com/search")
+        .form(&[("text", text), ("version", SEARCH_VERSION)])
+}
+
 pub async fn search(
     http_client: reqwest::Client,
     text: &str,
 ) -> Result<Vec<SearchResult>, CommandError> {
-    let results = http_client
-        .post("https://search.craiyon.com/search")
-        .form(&[("text", text), ("version", SEARCH_VERSION)])
+    let results = search_request(&http_client, text)
         .send()
         .await?
         .server_error()?
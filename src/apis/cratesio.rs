@@ -0,0 +1,77 @@
+use reqwest::header::USER_AGENT;
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::{DetectServerError, BOT_USER_AGENT};
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(rename = "crate")]
+    krate: Crate,
+    versions: Vec<Version>,
+}
+
+#[derive(Deserialize)]
+struct Crate {
+    name: String,
+    description: Option<String>,
+    downloads: u64,
+    recent_downloads: Option<u64>,
+    max_version: String,
+    max_stable_version: Option<String>,
+    repository: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    num: String,
+    license: Option<String>,
+}
+
+pub struct CrateInfo {
+    pub name: String,
+    /// the latest stable version, or the latest version if there are no stable
+    /// ones
+    pub version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+    pub recent_downloads: Option<u64>,
+    pub license: Option<String>,
+    pub repository: Option<String>,
+}
+
+pub async fn crate_info(
+    http_client: reqwest::Client,
+    name: &str,
+) -> Result<CrateInfo, CommandError> {
+    let response = http_client
+        .get(format!("https://crates.io/api/v1/crates/{name}"))
+        // https://crates.io/data-access#api
+        .header(
+            USER_AGENT,
+            format!("{BOT_USER_AGENT} (https://github.com/DuckyBlender/craiyon-bot-gemini-flash)"),
+        )
+        .send()
+        .await?
+        .server_error()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err("crate not found.".into());
+    }
+
+    let Response { krate, versions } = response.error_for_status()?.json().await?;
+    let version = krate.max_stable_version.unwrap_or(krate.max_version);
+    let license =
+        versions.into_iter().find(|other| other.num == version).and_then(|version| version.license);
+
+    Ok(CrateInfo {
+        name: krate.name,
+        version,
+        description: krate.description,
+        downloads: krate.downloads,
+        recent_downloads: krate.recent_downloads,
+        license,
+        repository: krate.repository,
+    })
+}
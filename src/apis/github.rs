@@ -0,0 +1,48 @@
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::{DetectServerError, BOT_USER_AGENT};
+
+#[derive(Deserialize)]
+pub struct Repository {
+    pub full_name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub stargazers_count: u64,
+    pub forks_count: u64,
+    pub open_issues_count: u64,
+    pub language: Option<String>,
+    #[serde(with = "time::serde::iso8601::option")]
+    pub pushed_at: Option<OffsetDateTime>,
+    pub license: Option<License>,
+}
+
+#[derive(Deserialize)]
+pub struct License {
+    pub name: String,
+}
+
+pub async fn repository(
+    http_client: reqwest::Client,
+    owner: &str,
+    repo: &str,
+) -> Result<Repository, CommandError> {
+    let response = http_client
+        .get(format!("https://api.github.com/repos/{owner}/{repo}"))
+        .header(USER_AGENT, BOT_USER_AGENT)
+        .header(ACCEPT, "application/vnd.github+json")
+        .send()
+        .await?
+        .server_error()?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => Err("repository not found.".into()),
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+            Err("GitHub API rate limit exceeded, try again later.".into())
+        }
+        _ => Ok(response.error_for_status()?.json().await?),
+    }
+}
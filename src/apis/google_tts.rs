@@ -0,0 +1,83 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Request<'a> {
+    input: Input<'a>,
+    voice: Voice<'a>,
+    audio_config: AudioConfig,
+}
+
+#[derive(Serialize)]
+struct Input<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Voice<'a> {
+    language_code: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AudioConfig {
+    audio_encoding: &'static str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Response {
+    audio_content: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetails,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetails {
+    message: String,
+}
+
+/// returns OGG Opus audio spoken by the default standard voice for
+/// `language_code`
+pub async fn synthesize(
+    http_client: reqwest::Client,
+    api_key: &str,
+    text: &str,
+    language_code: &str,
+) -> Result<Vec<u8>, CommandError> {
+    let response = http_client
+        .post(
+            Url::parse_with_params(
+                "https://texttospeech.googleapis.com/v1/text:synthesize",
+                [("key", api_key)],
+            )
+            .unwrap(),
+        )
+        .json(&Request {
+            input: Input { text },
+            voice: Voice { language_code },
+            audio_config: AudioConfig { audio_encoding: "OGG_OPUS" },
+        })
+        .send()
+        .await?
+        .server_error()?;
+
+    if response.status().is_client_error() {
+        let error = response.json::<ErrorResponse>().await?;
+        return Err(format!("text-to-speech failed: {}", error.error.message).into());
+    }
+
+    let response = response.error_for_status()?.json::<Response>().await?;
+
+    Ok(STANDARD.decode(response.audio_content).map_err(|_| "the server returned invalid audio.")?)
+}
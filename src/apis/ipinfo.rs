@@ -0,0 +1,30 @@
+use std::env;
+use std::net::IpAddr;
+
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+#[derive(Deserialize)]
+pub struct IpInfo {
+    pub ip: String,
+    #[serde(default)]
+    pub bogon: bool,
+    pub city: Option<String>,
+    pub region: Option<String>,
+    pub country: Option<String>,
+    pub org: Option<String>,
+    pub timezone: Option<String>,
+}
+
+/// `IPINFO_TOKEN` is optional, requests without it are limited more strictly
+pub async fn lookup(http_client: reqwest::Client, ip: IpAddr) -> Result<IpInfo, CommandError> {
+    let mut request = http_client.get(format!("https://ipinfo.io/{ip}/json"));
+
+    if let Some(token) = env::var("IPINFO_TOKEN").ok().filter(|token| !token.is_empty()) {
+        request = request.bearer_auth(token);
+    }
+
+    Ok(request.send().await?.server_error()?.error_for_status()?.json().await?)
+}
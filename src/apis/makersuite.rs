@@ -1,5 +1,6 @@
 use std::borrow::Cow;
-use std::{env, fmt};
+use std::fmt;
+use std::sync::LazyLock;
 
 use futures_util::StreamExt;
 use reqwest::StatusCode;
@@ -8,6 +9,25 @@ use tokio::sync::mpsc;
 use url::Url;
 
 use crate::commands::CommandError;
+use crate::utilities::api_key_pool::ApiKeyPool;
+
+pub const GEMINI_FLASH: &str = "gemini-1.5-flash-latest";
+
+/// set with `GEMINI_API_KEYS` (comma-separated) or `MAKERSUITE_API_KEY`, `None`
+/// if neither is set
+pub static API_KEYS: LazyLock<Option<ApiKeyPool>> =
+    LazyLock::new(|| ApiKeyPool::from_env("GEMINI_API_KEYS", "MAKERSUITE_API_KEY"));
+
+fn api_key() -> &'static str {
+    API_KEYS.as_ref().expect("GEMINI_API_KEYS or MAKERSUITE_API_KEY has to be set").next()
+}
+
+const SAFETY_SETTINGS: &[SafetySetting] = &[
+    SafetySetting { category: "HARM_CATEGORY_HATE_SPEECH", threshold: "BLOCK_NONE" },
+    SafetySetting { category: "HARM_CATEGORY_SEXUALLY_EXPLICIT", threshold: "BLOCK_NONE" },
+    SafetySetting { category: "HARM_CATEGORY_DANGEROUS_CONTENT", threshold: "BLOCK_NONE" },
+    SafetySetting { category: "HARM_CATEGORY_HARASSMENT", threshold: "BLOCK_NONE" },
+];
 
 pub enum GenerationError {
     NetworkError(reqwest::Error),
@@ -18,6 +38,8 @@ pub enum GenerationError {
 #[serde(rename_all = "camelCase")]
 struct GenerateContentRequest<'a> {
     contents: &'a [Content<'a>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content<'a>>,
     safety_settings: &'static [SafetySetting],
     generation_config: GenerationConfig,
 }
@@ -49,8 +71,10 @@ pub struct SafetySetting {
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct GenerationConfig {
-    max_output_tokens: u16,
+pub struct GenerationConfig {
+    pub max_output_tokens: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
 }
 
 #[derive(Deserialize)]
@@ -143,32 +167,19 @@ pub async fn stream_generate_content(
     tx: mpsc::UnboundedSender<Result<GenerateContentResponse, GenerationError>>,
     model: &str,
     parts: &[Part],
-    max_output_tokens: u16,
+    generation_config: GenerationConfig,
 ) {
     let url = format!(
         "https://generativelanguage.googleapis.com/v1beta/models/{model}:streamGenerateContent"
     );
 
     let response = http_client
-        .post(
-            Url::parse_with_params(&url, [("key", env::var("MAKERSUITE_API_KEY").unwrap())])
-                .unwrap(),
-        )
+        .post(Url::parse_with_params(&url, [("key", api_key())]).unwrap())
         .json(&GenerateContentRequest {
             contents: &[Content { parts }],
-            safety_settings: &[
-                SafetySetting { category: "HARM_CATEGORY_HATE_SPEECH", threshold: "BLOCK_NONE" },
-                SafetySetting {
-                    category: "HARM_CATEGORY_SEXUALLY_EXPLICIT",
-                    threshold: "BLOCK_NONE",
-                },
-                SafetySetting {
-                    category: "HARM_CATEGORY_DANGEROUS_CONTENT",
-                    threshold: "BLOCK_NONE",
-                },
-                SafetySetting { category: "HARM_CATEGORY_HARASSMENT", threshold: "BLOCK_NONE" },
-            ],
-            generation_config: GenerationConfig { max_output_tokens },
+            system_instruction: None,
+            safety_settings: SAFETY_SETTINGS,
+            generation_config,
         })
         .send()
         .await;
@@ -226,12 +237,69 @@ pub async fn stream_generate_content(
     tx.send(Ok(serde_json::from_str(&String::from_utf8_lossy(&buffer)).unwrap())).unwrap();
 }
 
+/// generates a complete response at once, guided by the system instruction
+pub async fn generate_content(
+    http_client: reqwest::Client,
+    model: &str,
+    system_instruction: &str,
+    parts: &[Part],
+    max_output_tokens: u16,
+) -> Result<GenerateContentResponse, GenerationError> {
+    let url =
+        format!("https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent");
+
+    let response = http_client
+        .post(Url::parse_with_params(&url, [("key", api_key())]).unwrap())
+        .json(&GenerateContentRequest {
+            contents: &[Content { parts }],
+            system_instruction: Some(Content { parts: &[Part::Text(system_instruction.into())] }),
+            safety_settings: SAFETY_SETTINGS,
+            generation_config: GenerationConfig { max_output_tokens, temperature: None },
+        })
+        .send()
+        .await
+        .map_err(GenerationError::NetworkError)?;
+
+    if response.status() == StatusCode::OK {
+        response.json().await.map_err(GenerationError::NetworkError)
+    } else {
+        Err(response
+            .json::<ErrorResponse>()
+            .await
+            .map_or_else(GenerationError::NetworkError, |response| {
+                GenerationError::GoogleError(response.error)
+            }))
+    }
+}
+
+impl GenerateContentResponse {
+    /// text of the first candidate, if it wasn't blocked
+    pub fn into_text(self) -> Option<String> {
+        let text = self
+            .candidates
+            .into_iter()
+            .next()?
+            .content?
+            .parts
+            .into_iter()
+            .filter_map(|part| match part {
+                PartResponse::Text(text) => Some(text),
+                PartResponse::InlineData => None,
+            })
+            .collect::<String>();
+
+        (!text.trim().is_empty()).then_some(text)
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct GenerateTextRequest<'a> {
     prompt: TextPrompt<'a>,
     safety_settings: &'a [SafetySetting],
     max_output_tokens: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -256,6 +324,7 @@ pub async fn generate_text(
     http_client: reqwest::Client,
     prompt: &str,
     max_output_tokens: u16,
+    temperature: Option<f64>,
 ) -> Result<Result<GenerateTextResponse, Error>, CommandError> {
     let response = http_client
         .post(
@@ -264,13 +333,14 @@ pub async fn generate_text(
                     "https://generativelanguage.googleapis.com",
                     "/v1beta/models/text-bison-001:generateText"
                 ),
-                [("key", env::var("MAKERSUITE_API_KEY").unwrap())],
+                [("key", api_key())],
             )
             .unwrap(),
         )
         .json(&GenerateTextRequest {
             prompt: TextPrompt { text: prompt },
             max_output_tokens,
+            temperature,
             safety_settings: &[
                 SafetySetting { category: "HARM_CATEGORY_DEROGATORY", threshold: "BLOCK_NONE" },
                 SafetySetting { category: "HARM_CATEGORY_TOXICITY", threshold: "BLOCK_NONE" },
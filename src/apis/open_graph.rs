@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use reqwest::{Response, Url};
+use scraper::{Html, Selector};
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::net_utils;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_REDIRECTS: usize = 5;
+pub const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+pub const NON_PUBLIC_URL: &str = "the URL doesn't resolve to a public IP address.";
+
+#[derive(Default)]
+pub struct OpenGraph {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<Url>,
+    pub site_name: Option<String>,
+}
+
+/// sends a GET request, following redirects manually as the shared clients
+/// don't follow them. the client should be `public_http_client`, so that
+/// every hop connects to a public address
+pub async fn get(http_client: &reqwest::Client, mut url: Url) -> Result<Response, CommandError> {
+    for _ in 0..=MAX_REDIRECTS {
+        if !net_utils::is_public_host(&url) {
+            return Err(NON_PUBLIC_URL.into());
+        }
+
+        let response = match http_client.get(url.clone()).timeout(TIMEOUT).send().await {
+            Ok(response) => response,
+            Err(err) if net_utils::is_non_public_address(&err) => return Err(NON_PUBLIC_URL.into()),
+            Err(err) => Err(err)?,
+        };
+
+        if !response.status().is_redirection() {
+            return Ok(response.server_error()?.error_for_status()?);
+        }
+
+        let Some(location) = response.headers().get(LOCATION).and_then(|value| value.to_str().ok())
+        else {
+            return Err("the website redirected to nowhere.".into());
+        };
+
+        url = url.join(location).map_err(|_| "the website redirected to an invalid URL.")?;
+    }
+
+    Err(format!("the website redirected more than {MAX_REDIRECTS} times.").into())
+}
+
+/// reads at most `MAX_BODY_SIZE` bytes of the response, discarding the rest
+pub async fn read_limited(response: Response) -> Result<Vec<u8>, CommandError> {
+    let mut body = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(bytes) = stream.next().await {
+        body.extend_from_slice(&bytes?);
+
+        if body.len() >= MAX_BODY_SIZE {
+            body.truncate(MAX_BODY_SIZE);
+            break;
+        }
+    }
+
+    Ok(body)
+}
+
+pub async fn fetch(http_client: &reqwest::Client, url: Url) -> Result<OpenGraph, CommandError> {
+    let response = get(http_client, url).await?;
+    let url = response.url().clone();
+
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+
+    if !content_type.starts_with("text/html") && !content_type.starts_with("application/xhtml") {
+        return Err(if content_type.is_empty() {
+            "the URL doesn't point to a webpage.".into()
+        } else {
+            format!("the URL doesn't point to a webpage ({content_type}).").into()
+        });
+    }
+
+    let body = read_limited(response).await?;
+
+    Ok(parse(&String::from_utf8_lossy(&body), &url))
+}
+
+fn parse(html: &str, url: &Url) -> OpenGraph {
+    let document = Html::parse_document(html);
+    let mut open_graph = OpenGraph::default();
+
+    for element in document.select(&Selector::parse("meta[content]").unwrap()) {
+        let element = element.value();
+        let Some(property) = element.attr("property").or_else(|| element.attr("name")) else {
+            continue;
+        };
+        let content = element.attr("content").unwrap().trim();
+
+        if content.is_empty() {
+            continue;
+        }
+
+        let field = match property {
+            "og:title" => &mut open_graph.title,
+            "og:description" => &mut open_graph.description,
+            "og:site_name" => &mut open_graph.site_name,
+            "og:image" => {
+                open_graph.image = open_graph.image.or_else(|| url.join(content).ok());
+                continue;
+            }
+            _ => continue,
+        };
+
+        field.get_or_insert_with(|| content.into());
+    }
+
+    if open_graph.title.is_none() {
+        open_graph.title = document
+            .select(&Selector::parse("title").unwrap())
+            .next()
+            .map(|title| title.text().collect::<String>().trim().to_owned())
+            .filter(|title| !title.is_empty());
+    }
+
+    open_graph
+}
@@ -1,3 +1,4 @@
+use reqwest::multipart::{Form, Part};
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 
@@ -6,9 +7,11 @@ use crate::utilities::api_utils::DetectServerError;
 
 #[derive(Serialize)]
 struct Request<'a> {
-    model: &'static str,
+    model: &'a str,
     messages: &'a [Message<'a>],
     max_tokens: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
 }
 
 #[derive(Serialize)]
@@ -48,13 +51,15 @@ pub async fn chat_completion(
     http_client: reqwest::Client,
     base_url: &str,
     api_key: &str,
-    model: &'static str,
+    model: &str,
     messages: &[Message<'_>],
+    max_tokens: u16,
+    temperature: Option<f64>,
 ) -> Result<Result<ChatCompletion, Error>, CommandError> {
     let response = http_client
         .post(format!("{base_url}/chat/completions"))
         .bearer_auth(api_key)
-        .json(&Request { model, messages, max_tokens: 256 })
+        .json(&Request { model, messages, max_tokens, temperature })
         .send()
         .await?
         .server_error()?;
@@ -67,3 +72,51 @@ pub async fn chat_completion(
         Ok(Err(response.error))
     }
 }
+
+#[derive(Deserialize)]
+pub struct Transcription {
+    pub text: String,
+    /// only returned with the `verbose_json` response format
+    pub segments: Option<Vec<Segment>>,
+}
+
+#[derive(Deserialize)]
+pub struct Segment {
+    /// seconds from the start of the audio
+    pub start: f64,
+    pub text: String,
+}
+
+pub async fn transcription(
+    http_client: reqwest::Client,
+    base_url: &str,
+    api_key: &str,
+    audio: Vec<u8>,
+    language: Option<&str>,
+    segments: bool,
+) -> Result<Result<Transcription, Error>, CommandError> {
+    let mut form = Form::new()
+        .part("file", Part::bytes(audio).file_name("voice.ogg").mime_str("audio/ogg").unwrap())
+        .text("model", "whisper-1")
+        .text("response_format", if segments { "verbose_json" } else { "json" });
+
+    if let Some(language) = language {
+        form = form.text("language", language.to_owned());
+    }
+
+    let response = http_client
+        .post(format!("{base_url}/audio/transcriptions"))
+        .bearer_auth(api_key)
+        .multipart(form)
+        .send()
+        .await?
+        .server_error()?;
+
+    if response.status() == StatusCode::OK {
+        let response = response.json::<Transcription>().await?;
+        Ok(Ok(response))
+    } else {
+        let response = response.json::<ErrorResponse>().await?;
+        Ok(Err(response.error))
+    }
+}
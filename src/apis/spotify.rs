@@ -0,0 +1,157 @@
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+/// refresh tokens a bit before they actually expire
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+pub struct AccessToken {
+    pub token: String,
+    expires_at: Instant,
+}
+
+impl AccessToken {
+    pub fn is_valid(&self) -> bool {
+        Instant::now() < self.expires_at
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Artist {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ExternalUrls {
+    pub spotify: String,
+}
+
+#[derive(Deserialize)]
+pub struct TrackAlbum {
+    pub name: String,
+    pub release_date: String,
+}
+
+#[derive(Deserialize)]
+pub struct Track {
+    pub name: String,
+    pub artists: Vec<Artist>,
+    pub album: TrackAlbum,
+    pub duration_ms: u64,
+    pub popularity: u32,
+    pub external_urls: ExternalUrls,
+}
+
+#[derive(Deserialize)]
+pub struct Album {
+    pub name: String,
+    pub artists: Vec<Artist>,
+    pub release_date: String,
+    pub total_tracks: u32,
+    pub popularity: Option<u32>,
+    pub external_urls: ExternalUrls,
+}
+
+#[derive(Deserialize)]
+pub struct Owner {
+    pub display_name: Option<String>,
+    pub id: String,
+}
+
+#[derive(Deserialize)]
+pub struct Total {
+    pub total: u64,
+}
+
+#[derive(Deserialize)]
+pub struct Playlist {
+    pub name: String,
+    pub owner: Owner,
+    pub tracks: Total,
+    pub followers: Total,
+    pub external_urls: ExternalUrls,
+}
+
+/// requests a token using the client credentials flow
+pub async fn access_token(
+    http_client: reqwest::Client,
+    client_id: &str,
+    client_secret: &str,
+) -> Result<AccessToken, CommandError> {
+    let response: TokenResponse = http_client
+        .post("https://accounts.spotify.com/api/token")
+        .basic_auth(client_id, Some(client_secret))
+        .form(&[("grant_type", "client_credentials")])
+        .send()
+        .await?
+        .server_error()?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(AccessToken {
+        token: response.access_token,
+        expires_at: Instant::now()
+            + Duration::from_secs(response.expires_in).saturating_sub(EXPIRY_MARGIN),
+    })
+}
+
+async fn get<T: DeserializeOwned>(
+    http_client: reqwest::Client,
+    access_token: &str,
+    path: &str,
+) -> Result<T, CommandError> {
+    let response = http_client
+        .get(format!("https://api.spotify.com/v1/{path}"))
+        .bearer_auth(access_token)
+        .send()
+        .await?
+        .server_error()?;
+
+    // invalid IDs are reported as bad requests
+    if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST) {
+        return Err("not found on Spotify.".into());
+    }
+
+    Ok(response.error_for_status()?.json().await?)
+}
+
+pub async fn track(
+    http_client: reqwest::Client,
+    access_token: &str,
+    id: &str,
+) -> Result<Track, CommandError> {
+    get(http_client, access_token, &format!("tracks/{id}")).await
+}
+
+pub async fn album(
+    http_client: reqwest::Client,
+    access_token: &str,
+    id: &str,
+) -> Result<Album, CommandError> {
+    get(http_client, access_token, &format!("albums/{id}")).await
+}
+
+pub async fn playlist(
+    http_client: reqwest::Client,
+    access_token: &str,
+    id: &str,
+) -> Result<Playlist, CommandError> {
+    get(
+        http_client,
+        access_token,
+        &format!("playlists/{id}?fields=name,owner(display_name,id),tracks(total),followers(total),external_urls"),
+    )
+    .await
+}
@@ -1,20 +1,119 @@
+use std::collections::HashMap;
 use std::env;
-use std::time::Duration;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
-use reqwest::{RequestBuilder, StatusCode, Url};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
 
 use crate::commands::CommandError;
-use crate::utilities::api_utils::DetectServerError;
+use crate::utilities::api_utils::{DetectServerError, ServerError};
 
-trait StableHordeClientAgent {
-    fn client_agent_header(self) -> Self;
+pub enum HordeError {
+    Http(reqwest::Error),
+    Server(StatusCode),
+    Api { code: u16, message: String },
+    RateLimited { retry_after: u32 },
+    Maintenance,
+    InvalidJson(serde_json::Error),
 }
 
-impl StableHordeClientAgent for RequestBuilder {
-    fn client_agent_header(self) -> Self {
-        self.header("Client-Agent", env::var("STABLEHORDE_CLIENT").unwrap())
+impl From<reqwest::Error> for HordeError {
+    fn from(value: reqwest::Error) -> Self {
+        Self::Http(value)
+    }
+}
+
+impl From<ServerError> for HordeError {
+    fn from(value: ServerError) -> Self {
+        Self::Server(value.0)
+    }
+}
+
+pub type HordeResult<T> = Result<T, HordeError>;
+
+const DEFAULT_BASE_URL: &str = "https://stablehorde.net/api/v2";
+
+/// older generations aren't shared, in case every request waiting for them gave
+/// up without cancelling
+const SHARED_GENERATION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// generations by their parameters, kept until they're finished so that
+/// identical requests share them
+static IN_FLIGHT: LazyLock<Mutex<HashMap<String, InFlight>>> = LazyLock::new(Mutex::default);
+
+enum InFlight {
+    /// waiting requests receive the request ID or `None` if the submission
+    /// failed
+    Submitting(broadcast::Sender<Option<String>>),
+    Submitted {
+        request_id: String,
+        /// requests waiting for the generation, it's only cancelled once all of
+        /// them gave up on it
+        waiters: usize,
+        submitted_at: Instant,
+    },
+}
+
+/// removes the in-flight entry if the submitting request gets cancelled, which
+/// closes the channel for everyone waiting on it
+struct InFlightGuard(String);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = IN_FLIGHT.lock().unwrap();
+        if matches!(in_flight.get(&self.0), Some(InFlight::Submitting(_))) {
+            in_flight.remove(&self.0);
+        }
+    }
+}
+
+/// stops sharing a generation, returns whether no other request is waiting for
+/// it anymore
+fn release(request_id: &str, cancelled: bool) -> bool {
+    let mut in_flight = IN_FLIGHT.lock().unwrap();
+    let Some((key, waiters)) = in_flight.iter_mut().find_map(|(key, entry)| match entry {
+        InFlight::Submitted { request_id: id, waiters, .. } if id == request_id => {
+            Some((key.clone(), waiters))
+        }
+        _ => None,
+    }) else {
+        return true;
+    };
+
+    if cancelled && *waiters > 1 {
+        *waiters -= 1;
+        return false;
+    }
+
+    in_flight.remove(&key);
+    true
+}
+
+/// the API instance and the credentials sent to it
+#[derive(Clone)]
+pub struct HordeConfig {
+    pub base_url: String,
+    pub token: String,
+    pub client_agent: String,
+}
+
+impl HordeConfig {
+    /// `STABLEHORDE_BASE_URL` points the client at another instance of the API
+    pub fn from_env() -> Self {
+        Self {
+            base_url: env::var("STABLEHORDE_BASE_URL")
+                .ok()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| DEFAULT_BASE_URL.into()),
+            token: env::var("STABLEHORDE_TOKEN").unwrap_or_default(),
+            client_agent: env::var("STABLEHORDE_CLIENT").unwrap_or_default(),
+        }
     }
 }
 
@@ -26,6 +125,10 @@ struct GenerationInput<'a> {
     nsfw: bool,
     r2: bool,
     shared: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_processing: Option<&'static str>,
 }
 
 #[derive(Serialize)]
@@ -36,6 +139,44 @@ struct Params {
     sampler_name: &'static str,
     steps: u32,
     karras: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    control_type: Option<ControlType>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ControlType {
+    Canny,
+    Depth,
+    Openpose,
+    Scribble,
+}
+
+impl ControlType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "canny" => Some(Self::Canny),
+            "depth" => Some(Self::Depth),
+            "openpose" => Some(Self::Openpose),
+            "scribble" => Some(Self::Scribble),
+            _ => None,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Canny => "canny",
+            Self::Depth => "depth",
+            Self::Openpose => "openpose",
+            Self::Scribble => "scribble",
+        }
+    }
+}
+
+/// a source image guiding the composition of the generated images
+pub struct ControlNet<'a> {
+    pub image: &'a [u8],
+    pub control_type: ControlType,
 }
 
 #[derive(Deserialize)]
@@ -73,14 +214,95 @@ pub struct GeneratedImage {
     pub worker_name: String,
 }
 
+/// submits a generation, identical requests made while another one is being
+/// submitted share its request ID
 pub async fn generate(
     http_client: reqwest::Client,
+    config: &HordeConfig,
     prompt: &str,
     model: &'static str,
     size: (u32, u32),
-) -> Result<Result<String, String>, CommandError> {
+    nsfw: bool,
+    control_net: Option<ControlNet<'_>>,
+) -> HordeResult<String> {
+    // control images aren't part of the key, so these are never shared
+    if control_net.is_some() {
+        return submit(http_client, config, prompt, model, size, nsfw, control_net).await;
+    }
+
+    let key = format!("{model}\n{}x{}\n{nsfw}\n{prompt}", size.0, size.1);
+    let receiver = {
+        let mut in_flight = IN_FLIGHT.lock().unwrap();
+        in_flight.retain(|_, entry| match entry {
+            InFlight::Submitting(_) => true,
+            InFlight::Submitted { submitted_at, .. } => {
+                submitted_at.elapsed() < SHARED_GENERATION_TTL
+            }
+        });
+
+        match in_flight.get_mut(&key) {
+            Some(InFlight::Submitting(sender)) => Some(sender.subscribe()),
+            Some(InFlight::Submitted { request_id, waiters, .. }) => {
+                *waiters += 1;
+                return Ok(request_id.clone());
+            }
+            None => {
+                in_flight.insert(key.clone(), InFlight::Submitting(broadcast::channel(1).0));
+                None
+            }
+        }
+    };
+
+    if let Some(mut receiver) = receiver {
+        if let Ok(Some(request_id)) = receiver.recv().await {
+            return Ok(request_id);
+        }
+
+        // the shared submission failed or was cancelled, submit separately to
+        // report the error to this request as well
+        return submit(http_client, config, prompt, model, size, nsfw, None).await;
+    }
+
+    let guard = InFlightGuard(key);
+    let result = submit(http_client, config, prompt, model, size, nsfw, None).await;
+    let sender = {
+        let mut in_flight = IN_FLIGHT.lock().unwrap();
+        let sender = match in_flight.remove(&guard.0) {
+            Some(InFlight::Submitting(sender)) => Some(sender),
+            _ => None,
+        };
+        // everyone subscribed in the meantime gets the request ID below
+        if let (Some(sender), Ok(request_id)) = (&sender, &result) {
+            in_flight.insert(
+                guard.0.clone(),
+                InFlight::Submitted {
+                    request_id: request_id.clone(),
+                    waiters: 1 + sender.receiver_count(),
+                    submitted_at: Instant::now(),
+                },
+            );
+        }
+        sender
+    };
+    if let Some(sender) = sender {
+        sender.send(result.as_ref().ok().cloned()).ok();
+    }
+    drop(guard);
+
+    result
+}
+
+async fn submit(
+    http_client: reqwest::Client,
+    config: &HordeConfig,
+    prompt: &str,
+    model: &'static str,
+    size: (u32, u32),
+    nsfw: bool,
+    control_net: Option<ControlNet<'_>>,
+) -> HordeResult<String> {
     let response = http_client
-        .post("https://stablehorde.net/api/v2/generate/async")
+        .post(format!("{}/generate/async", config.base_url))
         .json(&GenerationInput {
             models: vec![model],
             prompt,
@@ -91,38 +313,57 @@ pub async fn generate(
                 sampler_name: "k_euler",
                 steps: 24,
                 karras: true,
+                control_type: control_net.as_ref().map(|control_net| control_net.control_type),
             },
-            nsfw: true,
+            nsfw,
             r2: true,
             shared: true,
+            source_image: control_net
+                .as_ref()
+                .map(|control_net| STANDARD.encode(control_net.image)),
+            source_processing: control_net.is_some().then_some("img2img"),
         })
-        .header("apikey", env::var("STABLEHORDE_TOKEN").unwrap())
-        .client_agent_header()
+        .header("apikey", &config.token)
+        .header("Client-Agent", &config.client_agent)
         .send()
         .await?
         .server_error()?;
 
-    match response.status() {
-        StatusCode::ACCEPTED => Ok(Ok(response.json::<RequestId>().await?.id)),
-        status => {
-            let error = response
+    let status = response.status();
+    match status {
+        StatusCode::ACCEPTED => {
+            let body = response.text().await?;
+            Ok(serde_json::from_str::<RequestId>(&body).map_err(HordeError::InvalidJson)?.id)
+        }
+        StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()?.parse().ok())
+                .unwrap_or(60);
+            Err(HordeError::RateLimited { retry_after })
+        }
+        StatusCode::SERVICE_UNAVAILABLE => Err(HordeError::Maintenance),
+        _ => {
+            let message = response
                 .json::<RequestError>()
                 .await
-                .map_or_else(|_| "zjebalo sie".into(), |e| e.message);
-            Ok(Err(format!("{}: {error}", status.as_u16())))
+                .map_or_else(|_| "unknown error".into(), |e| e.message);
+            Err(HordeError::Api { code: status.as_u16(), message })
         }
     }
 }
 
 async fn generation_info<O: DeserializeOwned>(
     http_client: reqwest::Client,
+    config: &HordeConfig,
     action: &str,
     request_id: &str,
 ) -> Result<Result<O, String>, CommandError> {
-    let url = Url::parse(&format!("https://stablehorde.net/api/v2/generate/{action}/{request_id}"))
-        .unwrap();
+    let url = Url::parse(&format!("{}/generate/{action}/{request_id}", config.base_url)).unwrap();
     let response = loop {
-        match http_client.get(url.clone()).client_agent_header().send().await {
+        match http_client.get(url.clone()).header("Client-Agent", &config.client_agent).send().await
+        {
             Err(err) if err.is_request() => {
                 log::warn!("{err}");
                 tokio::time::sleep(Duration::from_secs(1)).await;
@@ -137,7 +378,7 @@ async fn generation_info<O: DeserializeOwned>(
             let error = response
                 .json::<RequestError>()
                 .await
-                .map_or_else(|_| "zjebalo sie".into(), |e| e.message);
+                .map_or_else(|_| "unknown error".into(), |e| e.message);
             Ok(Err(format!("{}: {error}", status.as_u16())))
         }
     }
@@ -145,29 +386,43 @@ async fn generation_info<O: DeserializeOwned>(
 
 pub async fn check(
     http_client: reqwest::Client,
+    config: &HordeConfig,
     request_id: &str,
 ) -> Result<Result<Status, String>, CommandError> {
-    generation_info::<Status>(http_client, "check", request_id).await
+    let status = generation_info::<Status>(http_client, config, "check", request_id).await?;
+
+    // finished generations can't be shared anymore
+    if let Ok(status) = &status {
+        if status.done || status.faulted {
+            release(request_id, false);
+        }
+    }
+
+    Ok(status)
 }
 
 pub async fn results(
     http_client: reqwest::Client,
+    config: &HordeConfig,
     request_id: &str,
 ) -> Result<Result<Vec<GeneratedImage>, String>, CommandError> {
-    match generation_info::<Generations>(http_client, "status", request_id).await? {
+    match generation_info::<Generations>(http_client, config, "status", request_id).await? {
         Ok(status) => Ok(Ok(status.generations)),
         Err(err) => Ok(Err(err)),
     }
 }
 
+/// cancels the generation once no other request is waiting for it
 pub async fn cancel_generation(
     http_client: reqwest::Client,
+    config: &HordeConfig,
     request_id: &str,
 ) -> reqwest::Result<()> {
-    http_client
-        .delete(format!("https://stablehorde.net/api/v2/generate/status/{request_id}"))
-        .send()
-        .await?;
+    if !release(request_id, true) {
+        return Ok(());
+    }
+
+    http_client.delete(format!("{}/generate/status/{request_id}", config.base_url)).send().await?;
 
     Ok(())
 }
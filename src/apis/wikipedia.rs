@@ -0,0 +1,84 @@
+use reqwest::header::USER_AGENT;
+use reqwest::{StatusCode, Url};
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::{DetectServerError, BOT_USER_AGENT};
+
+#[derive(Clone, Deserialize)]
+pub struct Summary {
+    pub title: String,
+    #[serde(rename = "type")]
+    kind: String,
+    pub description: Option<String>,
+    pub extract: String,
+    pub content_urls: ContentUrls,
+}
+
+impl Summary {
+    pub fn is_disambiguation(&self) -> bool {
+        self.kind == "disambiguation" || self.title.contains("(disambiguation)")
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct ContentUrls {
+    pub desktop: PageUrls,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct PageUrls {
+    pub page: String,
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    pages: Vec<SearchResult>,
+}
+
+#[derive(Deserialize)]
+pub struct SearchResult {
+    pub id: u64,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+fn request(http_client: &reqwest::Client, url: Url) -> reqwest::RequestBuilder {
+    // https://meta.wikimedia.org/wiki/User-Agent_policy
+    http_client.get(url).header(
+        USER_AGENT,
+        format!("{BOT_USER_AGENT} (https://github.com/DuckyBlender/craiyon-bot-gemini-flash)"),
+    )
+}
+
+pub async fn summary(http_client: reqwest::Client, title: &str) -> Result<Summary, CommandError> {
+    let mut url = Url::parse("https://en.wikipedia.org/api/rest_v1/page/summary").unwrap();
+    url.path_segments_mut().unwrap().push(&title.replace(' ', "_"));
+    // return the target of redirects instead of a 302
+    url.query_pairs_mut().append_pair("redirect", "false");
+
+    let response = request(&http_client, url).send().await?.server_error()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err("article not found.".into());
+    }
+
+    Ok(response.error_for_status()?.json().await?)
+}
+
+pub async fn search(
+    http_client: reqwest::Client,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchResult>, CommandError> {
+    let url = Url::parse_with_params(
+        "https://en.wikipedia.org/w/rest.php/v1/search/page",
+        [("q", query), ("limit", &limit.to_string())],
+    )
+    .unwrap();
+
+    let response: SearchResponse =
+        request(&http_client, url).send().await?.server_error()?.error_for_status()?.json().await?;
+
+    Ok(response.pages)
+}
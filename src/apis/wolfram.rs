@@ -0,0 +1,31 @@
+use reqwest::{StatusCode, Url};
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+/// asks the Short Answers API, `None` means Wolfram Alpha couldn't answer the
+/// query
+pub async fn short_answer(
+    http_client: reqwest::Client,
+    app_id: &str,
+    query: &str,
+) -> Result<Option<String>, CommandError> {
+    let response = http_client
+        .get(
+            Url::parse_with_params(
+                "https://api.wolframalpha.com/v1/result",
+                [("appid", app_id), ("i", query)],
+            )
+            .unwrap(),
+        )
+        .send()
+        .await?
+        .server_error()?;
+
+    // documented as 501, but 404 is returned for some inputs as well
+    if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::NOT_IMPLEMENTED) {
+        return Ok(None);
+    }
+
+    Ok(Some(response.error_for_status()?.text().await?))
+}
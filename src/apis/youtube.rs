@@ -0,0 +1,84 @@
+use reqwest::Url;
+use serde::Deserialize;
+
+use crate::commands::CommandError;
+use crate::utilities::api_utils::DetectServerError;
+
+#[derive(Deserialize)]
+struct Response {
+    items: Vec<Video>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Video {
+    pub id: String,
+    pub snippet: Snippet,
+    pub content_details: ContentDetails,
+    pub statistics: Statistics,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snippet {
+    pub title: String,
+    pub channel_title: String,
+    pub description: String,
+}
+
+#[derive(Deserialize)]
+pub struct ContentDetails {
+    /// ISO 8601 duration, e.g. `PT1H2M3S`
+    pub duration: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    pub view_count: Option<String>,
+    /// missing if the uploader hid the likes
+    pub like_count: Option<String>,
+}
+
+impl ContentDetails {
+    /// the duration in seconds, `None` if it couldn't be parsed
+    pub fn seconds(&self) -> Option<u64> {
+        let duration = self.duration.strip_prefix('P')?;
+        let (days, time) = duration.split_once('T').unwrap_or((duration, ""));
+        let days = if days.is_empty() { 0 } else { days.strip_suffix('D')?.parse::<u64>().ok()? };
+
+        let mut seconds = days * 86_400;
+        let mut rest = time;
+        for (unit, multiplier) in [('H', 3600), ('M', 60), ('S', 1)] {
+            if let Some((value, remaining)) = rest.split_once(unit) {
+                seconds += value.parse::<u64>().ok()? * multiplier;
+                rest = remaining;
+            }
+        }
+
+        rest.is_empty().then_some(seconds)
+    }
+}
+
+pub async fn video(
+    http_client: reqwest::Client,
+    api_key: &str,
+    id: &str,
+) -> Result<Option<Video>, CommandError> {
+    let response: Response = http_client
+        .get(
+            Url::parse_with_params(
+                "https://www.googleapis.com/youtube/v3/videos",
+                [("part", "snippet,contentDetails,statistics"), ("id", id), ("key", api_key)],
+            )
+            .unwrap(),
+        )
+        .send()
+        .await?
+        .server_error()?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response.items.into_iter().next())
+}
@@ -4,25 +4,30 @@ use std::sync::Arc;
 
 use async_signal::{Signal, Signals};
 use futures_util::StreamExt;
-use tdlib::enums::{
-    AuthorizationState, BotCommands, ConnectionState, MessageSender, OptionValue, Update,
-};
+use tdlib::enums::{AuthorizationState, ConnectionState, MessageSender, OptionValue, Update};
 use tdlib::functions;
 use tdlib::types::{
-    BotCommand, OptionValueBoolean, OptionValueInteger, OptionValueString,
-    UpdateAuthorizationState, UpdateChatMember, UpdateChatPermissions, UpdateChatTitle,
-    UpdateConnectionState, UpdateMessageSendFailed, UpdateMessageSendSucceeded, UpdateNewChat,
-    UpdateNewInlineQuery, UpdateNewMessage, UpdateOption, UpdateUser,
+    OptionValueBoolean, OptionValueInteger, OptionValueString, UpdateAuthorizationState,
+    UpdateChatMember, UpdateChatPermissions, UpdateChatTitle, UpdateConnectionState,
+    UpdateMessageSendFailed, UpdateMessageSendSucceeded, UpdateNewCallbackQuery, UpdateNewChat,
+    UpdateNewChosenInlineResult, UpdateNewInlineQuery, UpdateNewMessage, UpdateOption, UpdateUser,
 };
 use tokio::signal;
 use tokio::task::JoinHandle;
 
-use crate::commands::{calculate_inline, dice_reply, CommandTrait};
+use crate::commands::{
+    calculate_inline, dice_reply, register_commands, remind, stablehorde_inline, CommandTrait,
+    Privilege,
+};
 use crate::utilities::bot_state::{BotState, BotStatus};
 use crate::utilities::cache::CompactUser;
 use crate::utilities::command_manager::{CommandInstance, CommandManager};
 use crate::utilities::message_filters::MessageDestination;
-use crate::utilities::{command_dispatcher, markov_chain_manager, message_filters, telegram_utils};
+use crate::utilities::parsed_command::COMMAND_PREFIX;
+use crate::utilities::{
+    callback_dispatcher, command_dispatcher, health_server, markov_chain_manager, message_filters,
+    telegram_utils, watchdog,
+};
 
 pub type TdError = tdlib::types::Error;
 pub type TdResult<T> = Result<T, TdError>;
@@ -30,7 +35,7 @@ pub type TdResult<T> = Result<T, TdError>;
 pub struct Bot {
     pub client_id: i32,
     my_id: Option<i64>,
-    command_manager: CommandManager,
+    pub command_manager: Arc<CommandManager>,
     state: Arc<BotState>,
     tasks: Vec<JoinHandle<()>>,
 }
@@ -40,13 +45,14 @@ impl Bot {
         Self {
             client_id: tdlib::create_client(),
             my_id: None,
-            command_manager: CommandManager::new(),
+            command_manager: Arc::new(CommandManager::new()),
             state: Arc::new(BotState::new()),
             tasks: Vec::new(),
         }
     }
 
     pub fn run(&mut self) {
+        log::info!("command prefix: {:?}", *COMMAND_PREFIX);
         *self.state.status.lock().unwrap() = BotStatus::Running;
         let client_id = self.client_id;
         self.run_task(async move {
@@ -74,9 +80,19 @@ impl Bot {
             *state.status.lock().unwrap() = BotStatus::WaitingToClose;
         });
 
+        tokio::spawn(remind::send_reminders(self.state.clone(), client_id));
+        tokio::spawn(watchdog::watch(self.state.clone()));
+
+        if let Some(address) =
+            env::var("HEALTH_CHECK_ADDRESS").ok().filter(|address| !address.is_empty())
+        {
+            tokio::spawn(health_server::serve(self.state.clone(), address));
+        }
+
         let mut last_task_count = 0;
         loop {
             if let Some((update, _)) = tdlib::receive() {
+                self.state.record_update();
                 self.on_update(update);
             }
             self.tasks.retain(|t| !t.is_finished());
@@ -102,6 +118,10 @@ impl Bot {
             log::error!("failed to save bot config: {err}");
         }
 
+        if let Err(err) = self.state.database.lock().unwrap().save() {
+            log::error!("failed to save database: {err}");
+        }
+
         if let Err(err) = markov_chain_manager::save(&self.state.markov_chain.lock().unwrap()) {
             log::error!("failed to save Markov chain: {err}");
         }
@@ -132,6 +152,8 @@ impl Bot {
             Update::Option(update) => self.on_option(update),
             Update::ConnectionState(update) => self.on_connection_state(&update),
             Update::NewInlineQuery(update) => self.on_new_inline_query(update),
+            Update::NewChosenInlineResult(update) => self.on_new_chosen_inline_result(update),
+            Update::NewCallbackQuery(update) => self.on_new_callback_query(update),
             Update::ChatMember(update) => self.on_chat_member(update),
             _ => (),
         }
@@ -200,10 +222,10 @@ impl Bot {
 
     fn on_ready(&mut self) {
         let client_id = self.client_id;
-        let commands = self.command_manager.public_command_list();
+        let command_manager = self.command_manager.clone();
         self.run_task(async move {
             functions::get_me(client_id).await.unwrap();
-            Self::sync_commands(commands, client_id).await.unwrap();
+            register_commands::register_commands(&command_manager, client_id).await.unwrap();
         });
     }
 
@@ -217,8 +239,20 @@ impl Bot {
                         command, arguments, context,
                     ));
                 }
+                MessageDestination::CommandSuggestion { suggestion, context } => {
+                    self.run_task(async move {
+                        let text = format!("did you mean {}{suggestion}?", *COMMAND_PREFIX);
+                        if let Err(err) = context.reply(text).await {
+                            log::error!(
+                                "failed to suggest a command: {}: {}",
+                                err.code,
+                                err.message
+                            );
+                        }
+                    });
+                }
                 MessageDestination::Dice { message } => {
-                    self.run_task(dice_reply::execute(message, self.client_id));
+                    self.run_task(dice_reply::execute(message, self.state.clone(), self.client_id));
                 }
                 MessageDestination::MarkovChain { text } => {
                     markov_chain_manager::train(&mut self.state.markov_chain.lock().unwrap(), text);
@@ -228,6 +262,11 @@ impl Bot {
     }
 
     fn on_new_inline_query(&mut self, update: UpdateNewInlineQuery) {
+        if update.query.starts_with(stablehorde_inline::PREFIX) {
+            self.run_task(stablehorde_inline::execute(update, self.state.clone(), self.client_id));
+            return;
+        }
+
         self.run_task(calculate_inline::execute(
             update,
             self.state.http_client.clone(),
@@ -235,6 +274,19 @@ impl Bot {
         ));
     }
 
+    fn on_new_chosen_inline_result(&mut self, update: UpdateNewChosenInlineResult) {
+        self.run_task(stablehorde_inline::chosen(update, self.state.clone(), self.client_id));
+    }
+
+    fn on_new_callback_query(&mut self, update: UpdateNewCallbackQuery) {
+        self.run_task(callback_dispatcher::dispatch_callback(
+            update,
+            self.state.clone(),
+            self.command_manager.clone(),
+            self.client_id,
+        ));
+    }
+
     fn on_chat_member(&self, update: UpdateChatMember) {
         if let MessageSender::User(user) = &update.new_chat_member.member_id {
             if self.my_id.is_some_and(|my_id| user.user_id == my_id) {
@@ -305,26 +357,16 @@ impl Bot {
     }
 
     pub fn add_command(&mut self, command: impl CommandTrait + Send + Sync + 'static) {
-        self.command_manager.add_command(Box::new(command));
+        // commands are only added before the bot starts sharing the manager with
+        // callbacks
+        Arc::get_mut(&mut self.command_manager).unwrap().add_command(Box::new(command));
     }
 
     pub fn get_command(&self, name: &str) -> Option<Arc<CommandInstance>> {
         self.command_manager.get_command(name)
     }
 
-    pub async fn sync_commands(commands: Vec<BotCommand>, client_id: i32) -> TdResult<()> {
-        let BotCommands::BotCommands(bot_commands) =
-            functions::get_commands(None, String::new(), client_id).await?;
-
-        if commands == bot_commands.commands {
-            log::debug!("commands already synced");
-            return Ok(());
-        }
-
-        let commands_len = commands.len();
-        functions::set_commands(None, String::new(), commands, client_id).await?;
-        log::info!("synced {commands_len} commands");
-
-        Ok(())
+    pub fn suggest_command(&self, name: &str, privileges: &[Privilege]) -> Option<String> {
+        self.command_manager.suggest_command(name, privileges)
     }
 }
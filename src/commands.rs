@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use reqwest::StatusCode;
 use tdlib::types::FormattedText;
@@ -7,49 +9,135 @@ use crate::bot::TdError;
 use crate::utilities::api_utils::ServerError;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::ConversionError;
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::{self, PromptRejection};
 
+pub mod a1111;
+pub mod ai_settings;
 pub mod autocomplete;
 pub mod badtranslate;
+pub mod blacklist;
 pub mod calculate_inline;
 pub mod charinfo;
+pub mod chat_commands;
 pub mod cobalt_download;
+pub mod compare_models;
 pub mod config;
+pub mod countdown;
 pub mod craiyon;
+pub mod craiyon_compare;
 pub mod craiyon_search;
+pub mod cratesio;
+pub mod csv_table;
+pub mod dashboard;
 pub mod delete;
 pub mod dice_reply;
 pub mod different_dimension_me;
+pub mod dns;
+pub mod enhance_prompt;
+pub mod feedback;
+pub mod github;
 pub mod groq;
+pub mod health;
+pub mod history;
+pub mod http_check;
+pub mod image_search;
+pub mod ipinfo;
+pub mod json_pretty;
 pub mod kebab;
 pub mod kiwifarms;
+pub mod leaderboard;
+pub mod maintenance;
+pub mod make_sticker;
 pub mod makersuite;
 pub mod markov_chain;
 pub mod mevo;
 pub mod moveit_joke;
+pub mod normalize;
+pub mod ocr;
+pub mod palette;
 pub mod ping;
+pub mod profile;
 pub mod radio_poligon;
+pub mod regex_test;
+pub mod register_commands;
+pub mod remind;
 pub mod screenshot;
 pub mod sex;
+pub mod spotify;
 pub mod stablehorde;
+pub mod stablehorde_inline;
 pub mod start;
 pub mod startit_joke;
+pub mod sticker_info;
+pub mod styles;
+pub mod suggest_negative;
+pub mod textstats;
+pub mod timezone;
+pub mod transcribe;
 pub mod translate;
 pub mod trollslate;
+pub mod tts;
 pub mod urbandictionary;
+pub mod url_preview;
+pub mod version;
+pub mod wikipedia;
+pub mod wolfram;
+pub mod youtube;
 
 pub type CommandResult = Result<(), CommandError>;
 
+#[allow(clippy::unreadable_literal)]
+pub const OWNER_ID: i64 = 807128293;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Privilege {
+    User,
+    GroupAdmin,
+    Owner,
+}
+
 #[async_trait]
 pub trait CommandTrait {
+    /// the first name is the main one, the rest are aliases
     fn command_names(&self) -> &[&str];
 
+    fn name(&self) -> &str {
+        self.command_names()[0]
+    }
+
+    fn aliases(&self) -> &[&str] {
+        &self.command_names()[1..]
+    }
+
     fn description(&self) -> Option<&'static str> {
         None
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 30)
+        rate_limit_from_env(self.name(), 3, 30)
+    }
+
+    /// the reply sent when a user hits the rate limit
+    fn cooldown_message(&self, remaining: Duration) -> String {
+        format!(
+            "you're rate limited, try again in {}.",
+            text_utils::format_duration(remaining.as_secs())
+        )
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::User
+    }
+
+    /// hidden commands still work but aren't listed or suggested to users
+    fn hidden(&self) -> bool {
+        false
+    }
+
+    /// how long the dispatcher lets the command run before cancelling it
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(120)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult;
@@ -63,6 +151,13 @@ pub enum CommandError {
     Telegram(TdError),
     Server(StatusCode),
     Reqwest(reqwest::Error),
+    Timeout { operation: &'static str, after: Duration },
+}
+
+impl CommandError {
+    pub fn timeout_message(operation: &str, after: Duration) -> String {
+        format!("the {operation} took too long (>{}s) and was cancelled.", after.as_secs())
+    }
 }
 
 impl From<String> for CommandError {
@@ -77,6 +172,12 @@ impl From<&str> for CommandError {
     }
 }
 
+impl From<PromptRejection> for CommandError {
+    fn from(value: PromptRejection) -> Self {
+        Self::Custom(value.message)
+    }
+}
+
 impl From<ConversionError> for CommandError {
     fn from(value: ConversionError) -> Self {
         Self::ArgumentConversion(value)
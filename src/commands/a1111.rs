@@ -0,0 +1,145 @@
+use std::io::BufWriter;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use image::ImageFormat;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::a1111::{self, Txt2ImgRequest};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply, StylePresetArg};
+use crate::utilities::database::ImageGeneration;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+use crate::utilities::{image_utils, text_utils};
+
+const SIZE: (u32, u32) = (512, 512);
+const NEGATIVE_PROMPT: &str = "lowres, blurry, bad anatomy, watermark, text";
+
+/// generates images using a self-hosted AUTOMATIC1111 web UI
+pub struct A1111 {
+    base_url: String,
+}
+
+impl A1111 {
+    pub const fn new(base_url: String) -> Self {
+        Self { base_url }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for A1111 {
+    fn command_names(&self) -> &[&str] {
+        &["a1111", "local_sd"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("generate images using a local Stable Diffusion instance")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 300)
+    }
+
+    /// a local instance can take minutes to generate
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (StylePresetArg(style), StringGreedyOrReply(prompt)) =
+            ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if let Some(issue) = text_utils::check_prompt(&prompt, &PROMPT_POLICY) {
+            log::info!("prompt rejected: {issue:?}");
+            Err(issue)?;
+        }
+
+        ctx.send_typing().await?;
+
+        let (styled_prompt, negative_prompt) = style.map_or_else(
+            || (prompt.clone(), NEGATIVE_PROMPT.into()),
+            |style| (style.apply(&prompt), style.apply_negative(NEGATIVE_PROMPT)),
+        );
+
+        let start_time = Instant::now();
+        let response = a1111::generate(
+            ctx.bot_state.http_client.clone(),
+            &self.base_url,
+            &Txt2ImgRequest {
+                prompt: &styled_prompt,
+                negative_prompt: &negative_prompt,
+                steps: 24,
+                cfg_scale: 7.0,
+                seed: -1,
+                width: SIZE.0,
+                height: SIZE.1,
+                sampler_name: "Euler a",
+            },
+        )
+        .await?;
+        let time_taken = start_time.elapsed();
+
+        let images = response
+            .images
+            .into_iter()
+            .flat_map(|image| image::load_from_memory_with_format(&image, ImageFormat::Png))
+            .collect::<Vec<_>>();
+
+        if images.is_empty() {
+            Err("the server didn't return any images.")?;
+        }
+
+        let image = image_utils::collage(images, SIZE, 8);
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let info = response.info;
+        let caption = message_entities::formatted_text(vec![
+            "generated ".text(),
+            prompt.clone().truncate_with_ellipsis(256).bold_owned(),
+            format!(" in {}", text_utils::format_duration(time_taken.as_secs())).text_owned(),
+            format!(
+                "\nseed: {}, sampler: {}, steps: {}, CFG scale: {}",
+                info.seed, info.sampler_name, info.steps, info.cfg_scale
+            )
+            .text_owned(),
+        ]);
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: image.width().try_into().unwrap(),
+                    height: image.height().try_into().unwrap(),
+                    caption: Some(caption),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                None,
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        ctx.bot_state.database.lock().unwrap().image_generations.push(ImageGeneration {
+            command: self.name().into(),
+            user_id: ctx.user.id,
+            chat_id: ctx.message.chat_id,
+            date: ctx.message.date,
+            prompt,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use async_trait::async_trait;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::database::AiSettings;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+
+const MAX_TEMPERATURE: f64 = 2.0;
+const MAX_TOKENS: u32 = 2048;
+const SETTINGS: &[&str] = &["model", "temperature", "max_tokens"];
+
+pub struct AiSet;
+
+#[async_trait]
+impl CommandTrait for AiSet {
+    fn command_names(&self) -> &[&str] {
+        &["ai_settings", "aiset"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("set your defaults for the AI commands")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let Ok((mut setting, rest)) = String::convert(ctx, &arguments).await else {
+            let settings = stored_settings(ctx);
+            let value = |value: Option<String>| value.unwrap_or_else(|| "default".into());
+
+            ctx.reply_formatted_text(message_entities::formatted_text(vec![
+                "your AI settings:".bold(),
+                "\nmodel: ".text(),
+                value(settings.model).code_owned(),
+                "\ntemperature: ".text(),
+                value(settings.temperature.map(|value| value.to_string())).code_owned(),
+                "\nmax tokens: ".text(),
+                value(settings.max_tokens.map(|value| value.to_string())).code_owned(),
+                "\n\nchange them with ".text(),
+                "/aiset model|temperature|max_tokens <value>".code(),
+                " or ".text(),
+                "/aiset reset".code(),
+                ". they can also be overridden for a single message, e.g. ".text(),
+                "/gemini --temperature 0.2 <prompt>".code(),
+            ]))
+            .await?;
+
+            return Ok(());
+        };
+
+        setting.make_ascii_lowercase();
+
+        if setting == "reset" {
+            ctx.bot_state.database.lock().unwrap().ai_settings.remove(&ctx.user.id);
+            ctx.reply("your AI settings were reset.".into()).await?;
+            return Ok(());
+        }
+
+        let value = String::convert(ctx, rest).await?.0;
+        let mut settings = stored_settings(ctx);
+        apply(&mut settings, &setting, &value)?;
+        ctx.bot_state.database.lock().unwrap().ai_settings.insert(ctx.user.id, settings);
+
+        ctx.reply(format!("{setting} set to {value}.")).await?;
+
+        Ok(())
+    }
+}
+
+/// the user's stored settings followed by `--setting value` overrides at the
+/// start of the arguments. other words starting with `--` are left in the
+/// prompt
+pub async fn resolve<'a>(
+    ctx: &CommandContext,
+    mut arguments: &'a str,
+) -> Result<(AiSettings, &'a str), CommandError> {
+    let mut settings = stored_settings(ctx);
+
+    while let Some(rest) = arguments.trim_start().strip_prefix("--") {
+        let Ok((mut setting, rest)) = String::convert(ctx, rest).await else {
+            break;
+        };
+        setting.make_ascii_lowercase();
+        if !SETTINGS.contains(&setting.as_str()) {
+            break;
+        }
+
+        let (value, rest) = String::convert(ctx, rest).await?;
+        apply(&mut settings, &setting, &value)?;
+        arguments = rest;
+    }
+
+    Ok((settings, arguments))
+}
+
+fn stored_settings(ctx: &CommandContext) -> AiSettings {
+    ctx.bot_state
+        .database
+        .lock()
+        .unwrap()
+        .ai_settings
+        .get(&ctx.user.id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+fn apply(settings: &mut AiSettings, setting: &str, value: &str) -> CommandResult {
+    match setting {
+        "model" => {
+            if !value.chars().all(|char| char.is_ascii_alphanumeric() || "-._".contains(char)) {
+                Err("invalid model name.")?;
+            }
+            settings.model = Some(value.into());
+        }
+        "temperature" => {
+            let temperature = value
+                .parse::<f64>()
+                .ok()
+                .filter(|temperature| (0.0..=MAX_TEMPERATURE).contains(temperature))
+                .ok_or_else(|| {
+                    format!("temperature has to be a number from 0 to {MAX_TEMPERATURE}.")
+                })?;
+            settings.temperature = Some(temperature);
+        }
+        "max_tokens" => {
+            let max_tokens = value
+                .parse::<u32>()
+                .ok()
+                .filter(|max_tokens| (1..=MAX_TOKENS).contains(max_tokens))
+                .ok_or_else(|| format!("max_tokens has to be a number from 1 to {MAX_TOKENS}."))?;
+            settings.max_tokens = Some(max_tokens);
+        }
+        _ => Err("unknown setting. available settings: model, temperature, max_tokens.")?,
+    }
+
+    Ok(())
+}
+
+impl AiSettings {
+    /// `max_tokens` is capped at [`MAX_TOKENS`], which always fits
+    pub fn max_tokens_or(&self, default: u16) -> u16 {
+        self.max_tokens.map_or(default, |max_tokens| max_tokens.try_into().unwrap())
+    }
+
+    /// the model if its name starts with one of the prefixes, since the
+    /// settings are shared between commands using different providers
+    pub fn model_or<'a>(&'a self, prefixes: &[&str], default: &'a str) -> &'a str {
+        self.model
+            .as_deref()
+            .filter(|model| prefixes.iter().any(|prefix| model.starts_with(prefix)))
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utilities::test_fixtures;
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let ctx = test_fixtures::command_context();
+
+        let (settings, rest) =
+            resolve(&ctx, " --Temperature 0.5 --max_tokens 100 hi").await.unwrap();
+        assert_eq!(settings.temperature, Some(0.5));
+        assert_eq!(settings.max_tokens, Some(100));
+        assert_eq!(rest, "hi");
+
+        let (_, rest) = resolve(&ctx, "--model gemini-pro --help me write").await.unwrap();
+        assert_eq!(rest, "--help me write");
+
+        let (_, rest) = resolve(&ctx, "-- a poem").await.unwrap();
+        assert_eq!(rest, "-- a poem");
+
+        assert!(resolve(&ctx, "--temperature 3 hi").await.is_err());
+    }
+}
@@ -7,7 +7,7 @@ use super::{CommandResult, CommandTrait};
 use crate::apis::google;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 
 pub struct Autocomplete;
 
@@ -22,7 +22,7 @@ impl CommandTrait for Autocomplete {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(10, 30)
+        rate_limit_from_env(self.name(), 10, 30)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
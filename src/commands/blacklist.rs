@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use craiyon_bot_derive::ConvertArgument;
+
+use super::{CommandError, CommandResult, CommandTrait, Privilege, OWNER_ID};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::paginator::Paginator;
+
+const USERS_PER_PAGE: usize = 25;
+
+#[derive(ConvertArgument)]
+struct UserId(i64);
+
+pub struct Blacklist;
+
+#[async_trait]
+impl CommandTrait for Blacklist {
+    fn command_names(&self) -> &[&str] {
+        &["blacklist"]
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (mut action, rest) = String::convert(ctx, &arguments).await?;
+        action.make_ascii_lowercase();
+
+        match action.as_str() {
+            "add" => {
+                let UserId(user_id) = ConvertArgument::convert(ctx, rest).await?.0;
+                if user_id == OWNER_ID {
+                    return Err("the owner can't be blacklisted.".into());
+                }
+
+                let changed = ctx.bot_state.config.lock().unwrap().blacklist.insert(user_id);
+                if changed {
+                    ctx.reply(format!("{user_id} is now blacklisted.")).await?;
+                } else {
+                    ctx.reply(format!("{user_id} was already blacklisted.")).await?;
+                }
+            }
+            "remove" => {
+                let UserId(user_id) = ConvertArgument::convert(ctx, rest).await?.0;
+                let changed = ctx.bot_state.config.lock().unwrap().blacklist.remove(&user_id);
+                if changed {
+                    ctx.reply(format!("{user_id} is no longer blacklisted.")).await?;
+                } else {
+                    ctx.reply(format!("{user_id} wasn't blacklisted.")).await?;
+                }
+            }
+            "list" => {
+                let mut user_ids = ctx
+                    .bot_state
+                    .config
+                    .lock()
+                    .unwrap()
+                    .blacklist
+                    .iter()
+                    .copied()
+                    .collect::<Vec<_>>();
+                user_ids.sort_unstable();
+
+                if user_ids.is_empty() {
+                    ctx.reply("the blacklist is empty.".into()).await?;
+                    return Ok(());
+                }
+
+                let lines = {
+                    let cache = ctx.bot_state.cache.lock().unwrap();
+                    user_ids
+                        .iter()
+                        .map(|&user_id| {
+                            cache.get_user(user_id).map_or_else(
+                                || user_id.to_string(),
+                                |user| format!("{user_id} ({user})"),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                Paginator::from_lines("blacklisted users:", &lines, USERS_PER_PAGE)
+                    .send_first_page(ctx)
+                    .await?;
+            }
+            _ => Err(CommandError::Custom("available actions: add, remove, list.".into()))?,
+        }
+
+        Ok(())
+    }
+}
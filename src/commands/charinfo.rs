@@ -1,9 +1,11 @@
 use async_trait::async_trait;
+use tdlib::types::FormattedText;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::{CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
-use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::message_entities::{self, Entity, ToEntity, ToEntityOwned};
 
 pub struct CharInfo;
 
@@ -18,36 +20,68 @@ impl CommandTrait for CharInfo {
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let StringGreedyOrReply(chars) = ConvertArgument::convert(ctx, &arguments).await?.0;
-        let mut chars = chars.chars();
-
-        let mut entities = chars
-            .by_ref()
-            .take(10)
-            .flat_map(|char| {
-                if char.is_ascii_whitespace() {
-                    vec!["\n".text()]
-                } else {
-                    let value = char.into();
-                    vec![
-                        "\n".text(),
-                        char.to_string().code_owned(),
-                        " ".text(),
-                        format!("U+{value:04X}").code_owned(),
-                        " – ".text(),
-                        charname::get_name(value).code(),
-                    ]
-                }
-            })
-            .skip(1)
-            .collect::<Vec<_>>();
-
-        if chars.next().is_some() {
-            entities.push("…".text());
-        }
-
-        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+        let StringGreedyOrReply(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        ctx.reply_formatted_text(charinfo(&text)).await?;
 
         Ok(())
     }
 }
+
+/// names every character of the first 10 grapheme clusters
+pub fn charinfo(text: &str) -> FormattedText {
+    let mut graphemes = text.grapheme_indices(true).map(|(_, grapheme)| grapheme);
+
+    let mut entities = graphemes
+        .by_ref()
+        .take(10)
+        .flat_map(|grapheme| {
+            if grapheme.chars().all(|char| char.is_ascii_whitespace()) {
+                return vec!["\n".text()];
+            }
+
+            let mut chars = grapheme.chars();
+            if let (Some(char), None) = (chars.next(), chars.next()) {
+                return char_entities(char, false);
+            }
+
+            // show the whole cluster first, then each of its codepoints
+            let mut entities = vec!["\n".text(), grapheme.to_owned().code_owned()];
+            entities.extend(grapheme.chars().flat_map(|char| char_entities(char, true)));
+            entities
+        })
+        .skip(1)
+        .collect::<Vec<_>>();
+
+    if graphemes.next().is_some() {
+        entities.push("…".text());
+    }
+
+    message_entities::formatted_text(entities)
+}
+
+fn char_entities(char: char, indent: bool) -> Vec<Entity<'static>> {
+    let value = char.into();
+    // combining marks are displayed on a dotted circle like in Unicode charts
+    let display =
+        if is_combining_mark(char) { format!("\u{25CC}{char}") } else { char.to_string() };
+
+    vec![
+        if indent { "\n    ".text() } else { "\n".text() },
+        display.code_owned(),
+        " ".text(),
+        format!("U+{value:04X}").code_owned(),
+        " – ".text(),
+        charname::get_name(value).code(),
+    ]
+}
+
+const fn is_combining_mark(char: char) -> bool {
+    matches!(
+        char,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
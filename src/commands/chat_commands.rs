@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait, Privilege};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConversionError, ConvertArgument};
+
+pub struct ChatCommands {
+    command_names: &'static [&'static str],
+    description: &'static str,
+    enable: bool,
+}
+
+impl ChatCommands {
+    pub const fn disable() -> Self {
+        Self {
+            command_names: &["disable"],
+            description: "disable a command in this chat",
+            enable: false,
+        }
+    }
+
+    pub const fn enable() -> Self {
+        Self {
+            command_names: &["enable"],
+            description: "enable a command in this chat",
+            enable: true,
+        }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ChatCommands {
+    fn command_names(&self) -> &[&str] {
+        self.command_names
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some(self.description)
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::GroupAdmin
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let name = String::convert(ctx, &arguments).await?.0;
+        let name = name.trim_start_matches('/').to_ascii_lowercase();
+
+        // aliases are stored under the main name
+        let Some(command) = ctx.command_manager.get_command(&name) else {
+            return Err(ConversionError::BadArgument("unknown command.").into());
+        };
+        let name = command.command.name().to_owned();
+
+        if ["enable", "disable"].contains(&name.as_str()) {
+            Err(ConversionError::BadArgument("this command can't be disabled."))?;
+        }
+
+        let changed = {
+            let mut config = ctx.bot_state.config.lock().unwrap();
+            let disabled_commands =
+                config.disabled_commands.entry(ctx.message.chat_id).or_default();
+            let changed = if self.enable {
+                disabled_commands.remove(&name)
+            } else {
+                disabled_commands.insert(name.clone())
+            };
+
+            if disabled_commands.is_empty() {
+                config.disabled_commands.remove(&ctx.message.chat_id);
+            }
+
+            changed
+        };
+
+        let text = match (self.enable, changed) {
+            (true, true) => format!("/{name} is now enabled in this chat."),
+            (true, false) => format!("/{name} wasn't disabled in this chat."),
+            (false, true) => format!("/{name} is now disabled in this chat."),
+            (false, false) => format!("/{name} was already disabled in this chat."),
+        };
+        ctx.reply(text).await?;
+
+        Ok(())
+    }
+}
@@ -1,13 +1,10 @@
 use std::borrow::Cow;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use rand::seq::IteratorRandom;
-use tdlib::enums::{InputFile, InputMessageContent, InputMessageReplyTo, Messages};
-use tdlib::functions;
-use tdlib::types::{
-    InputFileLocal, InputMessageAudio, InputMessageDocument, InputMessageReplyToMessage,
-    InputMessageVideo,
-};
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessageAudio, InputMessageDocument, InputMessageVideo};
 
 use super::{CommandError, CommandResult, CommandTrait};
 use crate::apis::cobalt::{self, Error};
@@ -53,6 +50,11 @@ impl CommandTrait for CobaltDownload {
         Some(self.description)
     }
 
+    /// large videos can take a long time to download and upload
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(60 * 60)
+    }
+
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(media_url) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
@@ -225,29 +227,5 @@ async fn send_files(ctx: &CommandContext, files: &[NetworkFile]) -> CommandResul
         })
         .collect::<Vec<_>>();
 
-    let Messages::Messages(messages) = functions::send_message_album(
-        ctx.message.chat_id,
-        ctx.message.message_thread_id,
-        Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
-            message_id: ctx.message.id,
-            ..Default::default()
-        })),
-        None,
-        messages,
-        ctx.client_id,
-    )
-    .await?;
-
-    for result in ctx
-        .bot_state
-        .message_queue
-        .wait_for_messages(
-            &messages.messages.into_iter().flatten().map(|message| message.id).collect::<Vec<_>>(),
-        )
-        .await
-    {
-        result?;
-    }
-
-    Ok(())
+    ctx.reply_album(messages).await
 }
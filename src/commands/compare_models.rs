@@ -0,0 +1,200 @@
+use std::io::BufWriter;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::OptionFuture;
+use image::ImageFormat;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::stablehorde::{GeneratedCollage, StableHorde};
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::database::ImageGeneration;
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+use crate::utilities::{image_utils, text_utils};
+
+static MODELS: [StableHorde; 4] = [
+    StableHorde::stable_diffusion(),
+    StableHorde::stable_diffusion_2(),
+    StableHorde::waifu_diffusion(),
+    StableHorde::furry_diffusion(),
+];
+const GENERATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+pub struct CompareModels;
+
+#[async_trait]
+impl CommandTrait for CompareModels {
+    fn command_names(&self) -> &[&str] {
+        &["compare", "compare_models"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("compare Stable Horde models using the same prompt")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 1, 120)
+    }
+
+    fn timeout(&self) -> Duration {
+        GENERATION_TIMEOUT + Duration::from_secs(60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (models, rest) = String::convert(ctx, &arguments).await?;
+        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, rest).await?.0;
+        let models = parse_models(&models)?;
+
+        if let Some(issue) = text_utils::check_prompt(&prompt, &PROMPT_POLICY) {
+            log::info!("prompt rejected: {issue:?}");
+            Err(issue)?;
+        }
+
+        let escaped_prompt = prompt.clone().truncate_with_ellipsis(256);
+        let status_msg = ctx
+            .bot_state
+            .message_queue
+            .wait_for_message(
+                ctx.reply(format!("comparing models for {escaped_prompt}…")).await?.id,
+            )
+            .await?;
+
+        let (first, second, third) = tokio::join!(
+            generate(ctx, models[0], &prompt),
+            generate(ctx, models[1], &prompt),
+            OptionFuture::from(models.get(2).map(|model| generate(ctx, model, &prompt))),
+        );
+        let results = [Some(first), Some(second), third]
+            .into_iter()
+            .flatten()
+            .zip(&models)
+            .collect::<Vec<_>>();
+
+        ctx.delete_message(status_msg.id).await.ok();
+
+        let mut entities = vec!["compared ".text(), escaped_prompt.bold(), ":".text()];
+        let mut images = Vec::with_capacity(results.len());
+
+        for (result, model) in results {
+            entities.extend(["\n".text(), model.model().code()]);
+            match result {
+                Ok(collage) => {
+                    entities.push(
+                        format!(
+                            " – {} by {}",
+                            text_utils::format_duration(collage.time_taken.as_secs()),
+                            collage.workers.join(", ").truncate_with_ellipsis(64)
+                        )
+                        .text_owned(),
+                    );
+                    images.push(collage.image);
+                }
+                Err(err) => entities.push(format!(" – failed ({err})").text_owned()),
+            }
+        }
+
+        if images.is_empty() {
+            return Err(CommandError::CustomFormattedText(message_entities::formatted_text(
+                entities,
+            )));
+        }
+
+        let image = image_utils::side_by_side(images, 8);
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: image.width().try_into().unwrap(),
+                    height: image.height().try_into().unwrap(),
+                    caption: Some(message_entities::formatted_text(entities)),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                Some(
+                    KeyboardBuilder::new()
+                        .url_button("generated thanks to Stable Horde", "https://stablehorde.net/")
+                        .build(),
+                ),
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        ctx.bot_state.database.lock().unwrap().image_generations.push(ImageGeneration {
+            command: self.name().into(),
+            user_id: ctx.user.id,
+            chat_id: ctx.message.chat_id,
+            date: ctx.message.date,
+            prompt,
+        });
+
+        Ok(())
+    }
+}
+
+fn parse_models(models: &str) -> Result<Vec<&'static StableHorde>, CommandError> {
+    let mut parsed: Vec<&'static StableHorde> = Vec::new();
+
+    for name in models.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let Some(model) = MODELS.iter().find(|model| {
+            model.command_names().iter().any(|command| command.eq_ignore_ascii_case(name))
+        }) else {
+            return Err(format!(
+                "unknown model: {name}. available models: {}.",
+                MODELS.iter().map(|model| model.aliases()[0]).collect::<Vec<_>>().join(", ")
+            )
+            .into());
+        };
+
+        if !parsed.iter().any(|other| std::ptr::eq(*other, model)) {
+            parsed.push(model);
+        }
+    }
+
+    if !(2..=3).contains(&parsed.len()) {
+        return Err(
+            "provide 2 or 3 different models separated by commas, for example: /compare sd,wd a cat."
+                .into(),
+        );
+    }
+
+    Ok(parsed)
+}
+
+async fn generate(
+    ctx: &CommandContext,
+    model: &StableHorde,
+    prompt: &str,
+) -> Result<GeneratedCollage, String> {
+    match tokio::time::timeout(
+        GENERATION_TIMEOUT,
+        Box::pin(model.generate_collage(ctx, prompt.to_owned())),
+    )
+    .await
+    {
+        Ok(Ok(collage)) => Ok(collage),
+        Ok(Err(CommandError::Custom(text))) => Err(text.trim_end_matches('.').to_owned()),
+        Ok(Err(CommandError::Server(status_code))) => Err(format!("server error {status_code}")),
+        Ok(Err(err)) => {
+            log::error!("generation using {} failed: {err:?}", model.model());
+            Err("unknown error".into())
+        }
+        Err(_) => Err("timed out".into()),
+    }
+}
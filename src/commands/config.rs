@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use tdlib::enums::{ChatMemberStatus, ChatType};
+use tdlib::enums::ChatMemberStatus;
 
 use super::{CommandError, CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
@@ -7,7 +7,8 @@ use crate::utilities::convert_argument::ConvertArgument;
 use crate::utilities::message_entities::{self, Entity, ToEntity};
 
 const MARKOV_CHAIN_LEARNING: &str = "markov_chain_learning";
-const SETTINGS: [&str; 1] = [MARKOV_CHAIN_LEARNING];
+const NSFW: &str = "nsfw";
+const SETTINGS: [&str; 2] = [MARKOV_CHAIN_LEARNING, NSFW];
 
 pub struct Config;
 
@@ -64,6 +65,30 @@ impl CommandTrait for Config {
                     ctx.reply("Markov chain learning was already disabled.".into()).await?;
                 }
             };
+        } else if setting == NSFW {
+            chat_group_guard(ctx)?;
+            chat_admin_guard(ctx).await?;
+
+            let value = bool::convert(ctx, rest).await?.0;
+            let changed = {
+                let mut config = ctx.bot_state.config.lock().unwrap();
+                if value {
+                    config.nsfw.insert(ctx.message.chat_id)
+                } else {
+                    config.nsfw.remove(&ctx.message.chat_id)
+                }
+            };
+
+            ctx.reply(
+                match (value, changed) {
+                    (true, true) => "NSFW generations are now allowed in this chat.",
+                    (true, false) => "NSFW generations were already allowed.",
+                    (false, true) => "NSFW generations are now disabled in this chat.",
+                    (false, false) => "NSFW generations were already disabled.",
+                }
+                .into(),
+            )
+            .await?;
         } else {
             let mut entities = vec!["unknown setting name. available settings include:\n".text()];
             entities.extend(setting_names());
@@ -76,16 +101,15 @@ impl CommandTrait for Config {
 }
 
 fn chat_group_guard(ctx: &CommandContext) -> CommandResult {
-    let (ChatType::BasicGroup(_) | ChatType::Supergroup(_)) = ctx.chat.r#type else {
+    if !ctx.is_group_chat() {
         return Err("this setting can be only set in groups.".into());
-    };
+    }
 
     Ok(())
 }
 
 async fn chat_admin_guard(ctx: &CommandContext) -> CommandResult {
-    let status =
-        ctx.bot_state.get_member_status(ctx.message.chat_id, ctx.user.id, ctx.client_id).await?;
+    let status = ctx.get_chat_member(ctx.user.id).await?.status;
 
     if !match status {
         ChatMemberStatus::Creator(_) => true,
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::time::Instant;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, DurationArg};
+use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::text_utils;
+
+const MAX_DURATION: Duration = Duration::from_secs(10 * 60);
+const UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+/// Telegram starts rejecting edits of a single message at around 20 per minute
+const MAX_EDITS_PER_MINUTE: usize = 20;
+
+pub struct Countdown;
+
+#[async_trait]
+impl CommandTrait for Countdown {
+    fn command_names(&self) -> &[&str] {
+        &["countdown", "timer"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("start a countdown timer")
+    }
+
+    fn timeout(&self) -> Duration {
+        MAX_DURATION + Duration::from_secs(60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let DurationArg(duration) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if duration.is_zero() {
+            return Err("the countdown has to be at least 1 second long.".into());
+        }
+
+        if duration > MAX_DURATION {
+            return Err(format!(
+                "the countdown can be at most {} long.",
+                text_utils::format_duration(MAX_DURATION.as_secs())
+            )
+            .into());
+        }
+
+        let start = Instant::now();
+        let end = start + duration;
+        let mut message = ctx
+            .bot_state
+            .message_queue
+            .wait_for_message(ctx.reply(format_remaining(duration)).await?.id)
+            .await?;
+        let mut edit_limiter = RateLimiter::new(MAX_EDITS_PER_MINUTE, 60);
+
+        loop {
+            tokio::time::sleep_until(end.min(Instant::now() + UPDATE_INTERVAL)).await;
+
+            let remaining = end.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            let elapsed = start.elapsed().as_secs().try_into().unwrap();
+            if edit_limiter.update_rate_limit(ctx.message.id, elapsed).is_some() {
+                continue;
+            }
+
+            message = Box::pin(ctx.edit_or_resend(message.id, format_remaining(remaining))).await?;
+        }
+
+        Box::pin(ctx.edit_or_resend(message.id, "⏰ time's up\\!".into())).await?;
+
+        // edits don't send notifications, so ping the user in groups
+        if !ctx.is_private_chat() {
+            ctx.reply(format!("{}, time's up!", ctx.user)).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_millis().div_ceil(1000).try_into().unwrap();
+    format!("⏳ {}", text_utils::format_duration(seconds))
+}
@@ -1,19 +1,31 @@
 use std::io::BufWriter;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use image::ImageFormat;
+use image::{DynamicImage, ImageFormat};
 use tdlib::enums::{InputFile, InputMessageContent};
-use tdlib::types::{InputFileLocal, InputMessagePhoto};
-use tempfile::NamedTempFile;
+use tdlib::functions;
+use tdlib::types::{
+    FormattedText, InputFileLocal, InputMessagePhoto, InputMessageText, LinkPreviewOptions,
+};
+use tokio::task::JoinHandle;
 
-use super::{CommandResult, CommandTrait};
-use crate::apis::craiyon::{self, Model};
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::craiyon::{self, CraiyonError, Model};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::database::ImageGeneration;
+use crate::utilities::keyboard::KeyboardBuilder;
 use crate::utilities::message_entities::{ToEntity, ToEntityOwned};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 use crate::utilities::text_utils::TruncateWithEllipsis;
-use crate::utilities::{image_utils, message_entities, telegram_utils, text_utils};
+use crate::utilities::{image_utils, message_entities, text_utils, vote_handler};
+
+const SPINNER: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+const ANIMATION_INTERVAL: Duration = Duration::from_secs(3);
+/// stays under Telegram's edit rate limit for a single message
+const MAX_ANIMATION_EDITS: usize = 15;
 
 pub struct Generate;
 
@@ -92,57 +104,59 @@ impl CommandTrait for Craiyon {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(2, 30)
+        rate_limit_from_env(self.name(), 2, 30)
+    }
+
+    /// leaves room for sending the images after a generation close to the
+    /// API timeout
+    fn timeout(&self) -> Duration {
+        craiyon::DEFAULT_TIMEOUT + Duration::from_secs(60)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
         let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
 
-        if let Some(issue) = text_utils::check_prompt(&prompt) {
+        if let Some(issue) = text_utils::check_prompt(&prompt, &PROMPT_POLICY) {
             log::info!("prompt rejected: {issue:?}");
             Err(issue)?;
         }
 
         let truncated_prompt = prompt.clone().truncate_with_ellipsis(256);
 
+        let status_text = format!("drawing {truncated_prompt}…");
         let status_msg = ctx
             .bot_state
             .message_queue
-            .wait_for_message(ctx.reply(format!("drawing {truncated_prompt}…")).await?.id)
+            .wait_for_message(ctx.reply(status_text.clone()).await?.id)
             .await?;
 
-        let result =
-            craiyon::draw(ctx.bot_state.http_client.clone(), self.model, "", &prompt).await?;
-
-        let tasks = result
-            .images
-            .clone()
-            .into_iter()
-            .map(|url| {
-                let http_client = ctx.bot_state.http_client.clone();
-                tokio::spawn(async move {
-                    let response = http_client.get(url).send().await;
-                    match response {
-                        Ok(response) => response.bytes().await,
-                        Err(err) => Err(err),
-                    }
-                })
-            })
-            .collect::<Vec<_>>();
-
-        let mut images = Vec::with_capacity(tasks.len());
-        for task in tasks {
-            images.push(task.await.unwrap()?);
-        }
+        let animation = LoadingAnimationTask::spawn(ctx, status_msg.id, status_text);
+        let result = craiyon::draw(
+            ctx.bot_state.http_client.clone(),
+            self.model,
+            "",
+            &prompt,
+            craiyon::DEFAULT_TIMEOUT,
+        )
+        .await;
+        drop(animation);
 
-        let images = images
-            .into_iter()
-            .flat_map(|image| image::load_from_memory_with_format(&image, ImageFormat::WebP))
-            .collect::<Vec<_>>();
+        let result = match result {
+            Ok(result) => result,
+            Err(CraiyonError::Timeout(timeout)) => {
+                log::warn!("Craiyon generation timed out after {}s", timeout.as_secs());
+                ctx.delete_message(status_msg.id).await.ok();
+                return Err(CommandError::Timeout { operation: "generation", after: timeout });
+            }
+            Err(CraiyonError::Server(err)) => Err(err)?,
+            Err(CraiyonError::Network(err)) => Err(err)?,
+        };
 
+        let images = download_images(&ctx.bot_state.http_client, &result.images).await?;
         let image = image_utils::collage(images, (256, 256), 8);
-        let mut temp_file = NamedTempFile::new().unwrap();
-        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+        let image_hash = vote_handler::image_hash(&image);
+        let mut temp_file = ctx.bot_state.temp_files.acquire().await;
+        image.write_to(&mut BufWriter::new(&mut *temp_file), ImageFormat::Png).unwrap();
 
         let download_urls = result
             .images
@@ -155,7 +169,7 @@ impl CommandTrait for Craiyon {
             "drawn ".text(),
             truncated_prompt.bold(),
             " in ".text(),
-            text_utils::format_duration(result.duration.as_secs()).text_owned(),
+            text_utils::format_duration_string(result.duration.as_secs()).text_owned(),
             ".\ndownload: ".text(),
         ];
 
@@ -179,14 +193,119 @@ impl CommandTrait for Craiyon {
                     self_destruct_type: None,
                     has_spoiler: false,
                 }),
-                Some(telegram_utils::donate_markup("🖍️ Craiyon", "https://craiyon.com/donate")),
+                Some(
+                    vote_handler::with_vote_buttons(
+                        KeyboardBuilder::new(),
+                        &ctx.bot_state,
+                        &image_hash,
+                        ctx.user.id,
+                    )
+                    .row()
+                    .url_button("donate to 🖍️ Craiyon", "https://craiyon.com/donate")
+                    .build(),
+                ),
             )
             .await?;
 
         ctx.bot_state.message_queue.wait_for_message(message.id).await?;
         ctx.delete_message(status_msg.id).await.ok();
-        temp_file.close().unwrap();
+        drop(temp_file);
+
+        ctx.bot_state.database.lock().unwrap().image_generations.push(ImageGeneration {
+            command: self.command_names[0].into(),
+            user_id: ctx.user.id,
+            chat_id: ctx.message.chat_id,
+            date: ctx.message.date,
+            prompt,
+        });
 
         Ok(())
     }
 }
+
+/// downloads the generated images in parallel, skipping ones that fail to
+/// decode
+pub async fn download_images(
+    http_client: &reqwest::Client,
+    urls: &[String],
+) -> reqwest::Result<Vec<DynamicImage>> {
+    let tasks = urls
+        .iter()
+        .cloned()
+        .map(|url| {
+            let http_client = http_client.clone();
+            tokio::spawn(async move {
+                let response = http_client.get(url).send().await;
+                match response {
+                    Ok(response) => response.bytes().await,
+                    Err(err) => Err(err),
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut images = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        images.push(task.await.unwrap()?);
+    }
+
+    Ok(images
+        .into_iter()
+        .flat_map(|image| image::load_from_memory_with_format(&image, ImageFormat::WebP))
+        .collect())
+}
+
+/// animates the status message, because Craiyon doesn't report the generation
+/// progress
+pub struct LoadingAnimationTask(JoinHandle<()>);
+
+impl LoadingAnimationTask {
+    pub fn spawn(ctx: &CommandContext, message_id: i64, text: String) -> Self {
+        let chat_id = ctx.message.chat_id;
+        let client_id = ctx.client_id;
+        let bot_state = ctx.bot_state.clone();
+        let start = Instant::now();
+
+        Self(tokio::spawn(async move {
+            for frame in SPINNER.iter().cycle().take(MAX_ANIMATION_EDITS) {
+                tokio::time::sleep(ANIMATION_INTERVAL).await;
+
+                let text = format!(
+                    "{frame} {text} {}",
+                    text_utils::format_duration(start.elapsed().as_secs())
+                );
+                let content = InputMessageContent::InputMessageText(InputMessageText {
+                    text: FormattedText { text, ..Default::default() },
+                    link_preview_options: Some(LinkPreviewOptions {
+                        is_disabled: true,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                });
+
+                if bot_state
+                    .telegram_rate_limiter
+                    .run(chat_id, || {
+                        functions::edit_message_text(
+                            chat_id,
+                            message_id,
+                            None,
+                            content.clone(),
+                            client_id,
+                        )
+                    })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }))
+    }
+}
+
+impl Drop for LoadingAnimationTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::BufWriter;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use image::{DynamicImage, ImageFormat};
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::craiyon::{download_images, LoadingAnimationTask};
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::craiyon::{self, CraiyonError, Model};
+use crate::utilities::api_utils::ServerError;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::database::ImageGeneration;
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+use crate::utilities::{image_utils, text_utils};
+
+pub struct CraiyonCompare;
+
+#[async_trait]
+impl CommandTrait for CraiyonCompare {
+    fn command_names(&self) -> &[&str] {
+        &["craiyon_compare", "cc"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("compare the Craiyon art, drawing and photo styles")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 1, 180)
+    }
+
+    fn timeout(&self) -> Duration {
+        craiyon::DEFAULT_TIMEOUT + Duration::from_secs(60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if let Some(issue) = text_utils::check_prompt(&prompt, &PROMPT_POLICY) {
+            log::info!("prompt rejected: {issue:?}");
+            Err(issue)?;
+        }
+
+        let truncated_prompt = prompt.clone().truncate_with_ellipsis(256);
+
+        let status_text = format!("comparing Craiyon styles for {truncated_prompt}…");
+        let status_msg = ctx
+            .bot_state
+            .message_queue
+            .wait_for_message(ctx.reply(status_text.clone()).await?.id)
+            .await?;
+
+        let animation = LoadingAnimationTask::spawn(ctx, status_msg.id, status_text);
+        let (art, drawing, photo) = tokio::join!(
+            generate(ctx, Model::Art, &prompt),
+            generate(ctx, Model::Drawing, &prompt),
+            generate(ctx, Model::Photo, &prompt),
+        );
+        drop(animation);
+        ctx.delete_message(status_msg.id).await.ok();
+
+        let mut entities = vec!["compared ".text(), truncated_prompt.bold(), ":".text()];
+        let mut images = Vec::with_capacity(3);
+
+        for (result, name) in [(art, "art"), (drawing, "drawing"), (photo, "photo")] {
+            entities.extend(["\n".text(), name.code()]);
+            match result {
+                Ok((image, duration)) => {
+                    entities.push(
+                        format!(" – {}", text_utils::format_duration(duration.as_secs()))
+                            .text_owned(),
+                    );
+                    images.push(image_utils::add_label(&image, name));
+                }
+                Err(err) => entities.push(format!(" – failed ({err})").text_owned()),
+            }
+        }
+
+        if images.is_empty() {
+            return Err(CommandError::CustomFormattedText(message_entities::formatted_text(
+                entities,
+            )));
+        }
+
+        let image = image_utils::side_by_side(images, 8);
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: image.width().try_into().unwrap(),
+                    height: image.height().try_into().unwrap(),
+                    caption: Some(message_entities::formatted_text(entities)),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                Some(
+                    KeyboardBuilder::new()
+                        .url_button("donate to 🖍️ Craiyon", "https://craiyon.com/donate")
+                        .build(),
+                ),
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        ctx.bot_state.database.lock().unwrap().image_generations.push(ImageGeneration {
+            command: self.name().into(),
+            user_id: ctx.user.id,
+            chat_id: ctx.message.chat_id,
+            date: ctx.message.date,
+            prompt,
+        });
+
+        Ok(())
+    }
+}
+
+/// generates a collage with the model, the error is a short reason for the
+/// caption
+async fn generate(
+    ctx: &CommandContext,
+    model: Model,
+    prompt: &str,
+) -> Result<(DynamicImage, Duration), String> {
+    let http_client = &ctx.bot_state.http_client;
+
+    let result =
+        match craiyon::draw(http_client.clone(), model, "", prompt, craiyon::DEFAULT_TIMEOUT).await
+        {
+            Ok(result) => result,
+            Err(CraiyonError::Timeout(_)) => return Err("timed out".into()),
+            Err(CraiyonError::Server(ServerError(status_code))) => {
+                return Err(format!("server error {status_code}"));
+            }
+            Err(CraiyonError::Network(err)) => {
+                log::error!("Craiyon generation failed: {err}");
+                return Err("network error".into());
+            }
+        };
+
+    let images = download_images(http_client, &result.images).await.map_err(|err| {
+        log::error!("downloading Craiyon images failed: {err}");
+        "download failed".to_owned()
+    })?;
+
+    Ok((image_utils::collage(images, (256, 256), 8), result.duration))
+}
@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use tdlib::enums::InputMessageContent;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::cratesio;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+pub struct CratesIo;
+
+#[async_trait]
+impl CommandTrait for CratesIo {
+    fn command_names(&self) -> &[&str] {
+        &["crate", "cratesio"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show information about a Rust crate")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(name) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let name = name.trim();
+
+        if name.is_empty()
+            || !name.chars().all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+        {
+            return Err("invalid crate name.".into());
+        }
+
+        let info = cratesio::crate_info(ctx.bot_state.http_client.clone(), name).await?;
+
+        let mut entities = vec![format!("📦 {} {}", info.name, info.version).bold_owned()];
+
+        if let Some(description) = info.description {
+            entities.extend([
+                "\n".text(),
+                description.trim().to_owned().truncate_with_ellipsis(512).text_owned(),
+            ]);
+        }
+
+        entities.push(format!("\n\ndownloads: {}", info.downloads).text_owned());
+
+        if let Some(recent_downloads) = info.recent_downloads {
+            entities.push(format!(" ({recent_downloads} recently)").text_owned());
+        }
+
+        if let Some(license) = info.license {
+            entities.push(format!("\nlicense: {license}").text_owned());
+        }
+
+        if let Some(repository) = info.repository {
+            entities.extend(["\nrepository: ".text(), repository.text_owned()]);
+        }
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(
+                KeyboardBuilder::new()
+                    .url_button(
+                        "view on crates.io",
+                        format!("https://crates.io/crates/{}", info.name),
+                    )
+                    .build(),
+            ),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
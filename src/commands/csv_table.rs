@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use csv::{ReaderBuilder, StringRecord};
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::telegram_utils::MAX_MESSAGE_LENGTH;
+use crate::utilities::text_utils::EscapeMarkdown;
+
+const MAX_COLUMNS: usize = 8;
+const MAX_ROWS: usize = 15;
+const MAX_CELL_LENGTH: usize = 32;
+/// Telegram's message length limit, with room left for the note about hidden
+/// columns and rows
+const MAX_TABLE_LENGTH: usize = MAX_MESSAGE_LENGTH - 64;
+
+pub struct CsvTable;
+
+#[async_trait]
+impl CommandTrait for CsvTable {
+    fn command_names(&self) -> &[&str] {
+        &["csv", "csv_table"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("format CSV as a table")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(csv) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let mut reader = ReaderBuilder::new().has_headers(true).from_reader(csv.as_bytes());
+        let header = reader.headers().map_err(|err| format!("invalid CSV: {err}."))?.clone();
+        let records = reader
+            .records()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| format!("invalid CSV: {err}."))?;
+
+        let mut lines = vec![table_row(&header)];
+        lines.push(
+            std::iter::once("\\|")
+                .chain(header.iter().take(MAX_COLUMNS).map(|_| " \\-\\-\\- \\|"))
+                .collect(),
+        );
+
+        let mut length = lines.iter().map(|line| line.chars().count() + 1).sum::<usize>();
+        let mut shown_rows = 0;
+        for record in records.iter().take(MAX_ROWS) {
+            let row = table_row(record);
+            length += row.chars().count() + 1;
+            if length > MAX_TABLE_LENGTH {
+                break;
+            }
+            lines.push(row);
+            shown_rows += 1;
+        }
+
+        let mut notes = Vec::new();
+        if header.len() > MAX_COLUMNS {
+            notes.push(format!("{MAX_COLUMNS} of {} columns", header.len()));
+        }
+        if records.len() > shown_rows {
+            notes.push(format!("{shown_rows} of {} rows", records.len()));
+        }
+        if !notes.is_empty() {
+            lines.push(format!("\n_showing {}_", notes.join(", ").escape_markdown()));
+        }
+
+        ctx.reply_markdown(lines.join("\n")).await?;
+
+        Ok(())
+    }
+}
+
+fn table_row(record: &StringRecord) -> String {
+    let mut row = String::from("\\|");
+    for field in record.iter().take(MAX_COLUMNS) {
+        row.push(' ');
+        // rows have to stay on a single line
+        let mut field = field.replace(['\r', '\n'], " ");
+        if field.chars().count() > MAX_CELL_LENGTH {
+            field = field.chars().take(MAX_CELL_LENGTH - 1).chain(['…']).collect();
+        }
+        row.push_str(&field.escape_markdown());
+        row.push_str(" \\|");
+    }
+    row
+}
@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use counter::Counter;
+use tdlib::enums::{InputMessageContent, ReplyMarkup};
+use tdlib::types::{FormattedText, InputMessageText, LinkPreviewOptions};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait, Privilege, OWNER_ID};
+use crate::utilities::bot_state::BotState;
+use crate::utilities::callback_dispatcher::CallbackContext;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::database::InvocationResult;
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, Entity, ToEntity, ToEntityOwned};
+use crate::utilities::text_utils;
+
+const TOP_COMMANDS: usize = 5;
+
+pub struct Dashboard;
+
+#[async_trait]
+impl CommandTrait for Dashboard {
+    fn command_names(&self) -> &[&str] {
+        &["dashboard"]
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: dashboard_text(&ctx.bot_state),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(refresh_markup()),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub async fn callback(ctx: &CallbackContext, arguments: &str) -> CommandResult {
+    if ctx.query.sender_user_id != OWNER_ID {
+        ctx.answer("this button can only be used by the bot owner.", false).await?;
+        return Ok(());
+    }
+
+    if arguments == "refresh" {
+        ctx.edit_message(dashboard_text(&ctx.bot_state), Some(refresh_markup())).await?;
+    }
+
+    ctx.answer("", false).await?;
+
+    Ok(())
+}
+
+fn refresh_markup() -> ReplyMarkup {
+    KeyboardBuilder::new().callback_button("🔄 refresh", "dashboard:refresh").build()
+}
+
+fn dashboard_text(bot_state: &BotState) -> FormattedText {
+    let now = OffsetDateTime::now_utc();
+    let today =
+        i32::try_from(now.unix_timestamp() - now.unix_timestamp().rem_euclid(86_400)).unwrap();
+
+    let (users, users_today, commands_today, rate_limited_today, top_commands, images_today) = {
+        let database = bot_state.database.lock().unwrap();
+        let invocations_today =
+            database.command_invocations.iter().filter(|invocation| invocation.date >= today);
+        let executed_today = invocations_today
+            .clone()
+            .filter(|invocation| invocation.result != InvocationResult::RateLimited);
+
+        (
+            database
+                .command_invocations
+                .iter()
+                .map(|invocation| invocation.user_id)
+                .collect::<HashSet<_>>()
+                .len(),
+            invocations_today
+                .clone()
+                .map(|invocation| invocation.user_id)
+                .collect::<HashSet<_>>()
+                .len(),
+            executed_today.clone().count(),
+            invocations_today
+                .filter(|invocation| invocation.result == InvocationResult::RateLimited)
+                .count(),
+            executed_today.map(|invocation| invocation.command.clone()).collect::<Counter<_>>(),
+            database.image_generations.iter().filter(|generation| generation.date >= today).count(),
+        )
+    };
+
+    let mut entities = vec![
+        "📊 dashboard".bold(),
+        "\nuptime: ".text(),
+        text_utils::format_duration_string(bot_state.start_time.elapsed().as_secs()).code_owned(),
+        "\nusers: ".text(),
+        number(users_today),
+        " today, ".text(),
+        number(users),
+        " total".text(),
+        "\ncommands today: ".text(),
+        number(commands_today),
+        "\nimages generated today: ".text(),
+        number(images_today),
+        "\nrate limit rejections today: ".text(),
+        number(rate_limited_today),
+    ];
+
+    if !top_commands.is_empty() {
+        entities.push("\n\ntop commands today:".bold());
+        entities.extend(top_commands.most_common().into_iter().take(TOP_COMMANDS).flat_map(
+            |(command, count)| {
+                ["\n".text(), format!("/{command}").code_owned(), " – ".text(), number(count)]
+            },
+        ));
+    }
+
+    entities.extend([
+        "\n\nupdated at ".text(),
+        now.format(format_description!("[hour]:[minute]:[second] UTC")).unwrap().italic_owned(),
+    ]);
+
+    message_entities::formatted_text(entities)
+}
+
+fn number(n: usize) -> Entity<'static> {
+    text_utils::format_number(u64::try_from(n).unwrap(), *text_utils::NUMBER_LOCALE).code_owned()
+}
@@ -2,12 +2,9 @@ use async_trait::async_trait;
 use tdlib::enums::MessageReplyTo;
 use tdlib::types::MessageReplyToMessage;
 
-use super::{CommandResult, CommandTrait};
+use super::{CommandResult, CommandTrait, Privilege};
 use crate::utilities::command_context::CommandContext;
 
-#[allow(clippy::unreadable_literal)]
-const OWNER_ID: i64 = 807128293;
-
 pub struct Delete;
 
 #[async_trait]
@@ -16,18 +13,23 @@ impl CommandTrait for Delete {
         &["delete", "del"]
     }
 
-    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
-        if ctx.user.id != OWNER_ID {
-            return Ok(());
-        }
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
 
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let Some(&MessageReplyTo::Message(MessageReplyToMessage { message_id, .. })) =
             ctx.message.reply_to.as_ref()
         else {
             return Ok(());
         };
 
-        ctx.delete_message(message_id).await.ok();
+        // the command itself is removed too so that nothing is left behind
+        ctx.delete_messages(&[message_id, ctx.message.id]).await?;
 
         Ok(())
     }
@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use std::time::Duration;
 
 use rand::Rng;
@@ -8,7 +9,9 @@ use tdlib::types::{
     MessageDice,
 };
 
-pub async fn execute(message: Message, client_id: i32) {
+use crate::utilities::bot_state::BotState;
+
+pub async fn execute(message: Message, bot_state: Arc<BotState>, client_id: i32) {
     let MessageContent::MessageDice(dice) = message.content else {
         return;
     };
@@ -23,27 +26,30 @@ pub async fn execute(message: Message, client_id: i32) {
 
     tokio::time::sleep(Duration::from_secs(3)).await;
 
-    functions::send_message(
-        message.chat_id,
-        message.message_thread_id,
-        Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
-            message_id: message.id,
-            ..Default::default()
-        })),
-        None,
-        None,
-        InputMessageContent::InputMessageText(InputMessageText {
-            text: FormattedText { text: text.into(), ..Default::default() },
-            link_preview_options: Some(LinkPreviewOptions {
-                is_disabled: true,
-                ..Default::default()
-            }),
-            ..Default::default()
-        }),
-        client_id,
-    )
-    .await
-    .unwrap();
+    let content = InputMessageContent::InputMessageText(InputMessageText {
+        text: FormattedText { text: text.into(), ..Default::default() },
+        link_preview_options: Some(LinkPreviewOptions { is_disabled: true, ..Default::default() }),
+        ..Default::default()
+    });
+
+    bot_state
+        .telegram_rate_limiter
+        .run(message.chat_id, || {
+            functions::send_message(
+                message.chat_id,
+                message.message_thread_id,
+                Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
+                    message_id: message.id,
+                    ..Default::default()
+                })),
+                None,
+                None,
+                content.clone(),
+                client_id,
+            )
+        })
+        .await
+        .unwrap();
 }
 
 fn dice_success(dice: &MessageDice) -> bool {
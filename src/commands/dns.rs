@@ -0,0 +1,109 @@
+use std::fmt::Write;
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use trust_dns_resolver::error::{ResolveError, ResolveErrorKind};
+use trust_dns_resolver::proto::op::ResponseCode;
+use trust_dns_resolver::proto::rr::RecordType;
+use trust_dns_resolver::TokioAsyncResolver;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+const RECORD_TYPES: [RecordType; 7] = [
+    RecordType::A,
+    RecordType::AAAA,
+    RecordType::MX,
+    RecordType::TXT,
+    RecordType::CNAME,
+    RecordType::NS,
+    RecordType::PTR,
+];
+
+pub struct Dns;
+
+#[async_trait]
+impl CommandTrait for Dns {
+    fn command_names(&self) -> &[&str] {
+        &["dns", "nslookup"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("look up DNS records of a domain")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (domain, rest) = String::convert(ctx, &arguments).await?;
+        let record_type = match rest.trim_start().strip_prefix("--type ") {
+            Some(rest) => {
+                let (name, _) = String::convert(ctx, rest).await?;
+                RECORD_TYPES
+                    .into_iter()
+                    .find(|record_type| record_type.to_string().eq_ignore_ascii_case(&name))
+                    .ok_or(
+                        "unsupported record type, use one of A, AAAA, MX, TXT, CNAME, NS, PTR.",
+                    )?
+            }
+            None => RecordType::A,
+        };
+
+        let resolver = TokioAsyncResolver::tokio_from_system_conf().map_err(|err| {
+            log::error!("failed to create a DNS resolver: {err}");
+            "DNS lookups are unavailable."
+        })?;
+
+        let records = match (record_type, domain.parse::<IpAddr>()) {
+            (RecordType::PTR, Ok(ip)) => resolver
+                .reverse_lookup(ip)
+                .await
+                .map(|lookup| lookup.into_iter().map(|name| name.to_string()).collect::<Vec<_>>()),
+            _ => resolver.lookup(domain.as_str(), record_type).await.map(|lookup| {
+                lookup.record_iter().filter_map(|record| Some(record.data()?.to_string())).collect()
+            }),
+        }
+        .map_err(|err| resolve_error(&err))?;
+
+        if records.is_empty() {
+            return Err("no records found.".into());
+        }
+
+        let mut text = String::new();
+        for record in &records {
+            writeln!(text, "{record}").unwrap();
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            format!("{record_type} records for ").text_owned(),
+            domain.bold(),
+            "\n".text(),
+            text.trim_end().code(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn resolve_error(err: &ResolveError) -> CommandError {
+    match err.kind() {
+        ResolveErrorKind::NoRecordsFound { response_code: ResponseCode::NXDomain, .. } => {
+            "domain not found.".into()
+        }
+        ResolveErrorKind::NoRecordsFound { response_code: ResponseCode::ServFail, .. } => {
+            "DNS server error.".into()
+        }
+        ResolveErrorKind::NoRecordsFound { .. } => "no records found.".into(),
+        ResolveErrorKind::Timeout => "query timed out.".into(),
+        _ => {
+            log::warn!("DNS lookup failed: {err}");
+            "DNS lookup failed.".into()
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::makersuite::{self, Part};
+use crate::utilities::command_context::{CommandContext, TypingGuard};
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+const SYSTEM_INSTRUCTION: &str = concat!(
+    "Expand this into a rich, detailed Stable Diffusion image generation prompt in 50–150 words. ",
+    "Return only the prompt text."
+);
+
+pub struct EnhancePrompt;
+
+#[async_trait]
+impl CommandTrait for EnhancePrompt {
+    fn command_names(&self) -> &[&str] {
+        &["enhance", "ep"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("expand a prompt for image generation using Gemini Flash")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 2, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let _typing = TypingGuard(ctx.send_typing_loop());
+
+        let response = makersuite::generate_content(
+            ctx.bot_state.http_client.clone(),
+            makersuite::GEMINI_FLASH,
+            SYSTEM_INSTRUCTION,
+            &[Part::Text(prompt.clone())],
+            512,
+        )
+        .await?;
+
+        let Some(enhanced_prompt) = response.into_text() else {
+            return Err("the prompt couldn't be enhanced, it was probably blocked.".into());
+        };
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            "original: ".bold(),
+            prompt.truncate_with_ellipsis(128).text_owned(),
+            "\n\nenhanced: ".bold(),
+            enhanced_prompt.trim().code(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::env;
+use std::sync::LazyLock;
+
+use async_trait::async_trait;
+use tdlib::enums::InputMessageContent;
+use tdlib::functions;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedy};
+use crate::utilities::database::FeedbackEntry;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+const MIN_LENGTH: usize = 20;
+
+/// the chat feedback is forwarded to, `None` if unset or invalid
+static ADMIN_CHAT_ID: LazyLock<Option<i64>> = LazyLock::new(|| {
+    let admin_chat_id = env::var("ADMIN_CHAT_ID").ok()?;
+    let parsed = admin_chat_id.parse().ok();
+    if parsed.is_none() {
+        log::warn!("ignoring invalid ADMIN_CHAT_ID: {admin_chat_id:?}");
+    }
+    parsed
+});
+
+pub struct Feedback;
+
+#[async_trait]
+impl CommandTrait for Feedback {
+    fn command_names(&self) -> &[&str] {
+        &["feedback", "report"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("send feedback to the bot owner")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 1, 3600)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedy(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if text.chars().count() < MIN_LENGTH {
+            return Err(format!("feedback has to be at least {MIN_LENGTH} characters long.").into());
+        }
+
+        let id = {
+            let mut database = ctx.bot_state.database.lock().unwrap();
+            let id = database.feedback.len() + 1;
+            database.feedback.push(FeedbackEntry {
+                id,
+                user_id: ctx.user.id,
+                chat_id: ctx.message.chat_id,
+                date: ctx.message.date,
+                text: text.clone(),
+            });
+            id
+        };
+
+        if let Some(admin_chat_id) = *ADMIN_CHAT_ID {
+            let user_url = format!("tg://user?id={}", ctx.user.id);
+            let text = message_entities::formatted_text(vec![
+                format!("feedback #{id}").bold_owned(),
+                "\nfrom: ".text(),
+                ctx.user.to_string().text_url_owned(user_url),
+                " (".text(),
+                ctx.user.id.to_string().code_owned(),
+                ")\nchat: ".text(),
+                ctx.chat.to_string().text_owned(),
+                " (".text(),
+                ctx.message.chat_id.to_string().code_owned(),
+                ")\n\n".text(),
+                text.text_owned(),
+            ]);
+
+            let content = InputMessageContent::InputMessageText(InputMessageText {
+                text,
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+
+            ctx.bot_state
+                .telegram_rate_limiter
+                .run(admin_chat_id, || {
+                    functions::send_message(
+                        admin_chat_id,
+                        0,
+                        None,
+                        None,
+                        None,
+                        content.clone(),
+                        ctx.client_id,
+                    )
+                })
+                .await?;
+        } else {
+            log::warn!("ADMIN_CHAT_ID is not set or invalid, feedback #{id} was only saved");
+        }
+
+        ctx.reply(format!("thanks, your feedback was received (ID: {id}).")).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use tdlib::enums::InputMessageContent;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+use time::macros::format_description;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::github;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+pub struct GitHub;
+
+#[async_trait]
+impl CommandTrait for GitHub {
+    fn command_names(&self) -> &[&str] {
+        &["github", "gh"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show information about a GitHub repository")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(argument) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let (owner, repo) =
+            parse_repository(&argument).ok_or("use owner/repo or a GitHub repository URL.")?;
+
+        let repository = github::repository(ctx.bot_state.http_client.clone(), owner, repo).await?;
+
+        let mut entities = vec![repository.full_name.bold()];
+
+        if let Some(description) = repository.description.filter(|text| !text.is_empty()) {
+            entities.extend(["\n".text(), description.truncate_with_ellipsis(512).text_owned()]);
+        }
+
+        entities.push(
+            format!(
+                "\n\n⭐ {} · 🍴 {} · 🐛 {} open issues",
+                repository.stargazers_count, repository.forks_count, repository.open_issues_count
+            )
+            .text_owned(),
+        );
+
+        if let Some(language) = repository.language {
+            entities.push(format!("\n{} {language}", language_emoji(&language)).text_owned());
+        }
+
+        if let Some(pushed_at) = repository.pushed_at {
+            entities.extend([
+                "\nlast push: ".text(),
+                pushed_at.format(format_description!("[year]-[month]-[day]")).unwrap().text_owned(),
+            ]);
+        }
+
+        if let Some(license) = repository.license {
+            entities.push(format!("\nlicense: {}", license.name).text_owned());
+        }
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(KeyboardBuilder::new().url_button("open on GitHub", repository.html_url).build()),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// accepts `owner/repo` or a `github.com` URL
+fn parse_repository(argument: &str) -> Option<(&str, &str)> {
+    let path = argument.trim();
+    let path =
+        path.strip_prefix("https://").or_else(|| path.strip_prefix("http://")).unwrap_or(path);
+    let path = path.strip_prefix("www.").unwrap_or(path);
+    let path = path.strip_prefix("github.com/").unwrap_or(path);
+
+    let mut segments = path.split('/');
+    let owner = segments.next().filter(|owner| !owner.is_empty())?;
+    let repo = segments.next()?.trim_end_matches(".git");
+
+    let valid = |name: &str| {
+        !name.is_empty()
+            && name.chars().all(|char| char.is_ascii_alphanumeric() || "-_.".contains(char))
+    };
+
+    (valid(owner) && valid(repo)).then_some((owner, repo))
+}
+
+/// approximates GitHub's language colors
+fn language_emoji(language: &str) -> &'static str {
+    match language {
+        "Rust" | "Java" | "Swift" | "HTML" => "🟠",
+        "Python" | "TypeScript" | "Go" | "C++" | "Dockerfile" | "Lua" => "🔵",
+        "JavaScript" | "Shell" => "🟡",
+        "Ruby" | "Scala" => "🔴",
+        "C#" | "Vue" | "Kotlin" => "🟣",
+        "C" | "Haskell" => "⚫",
+        "CSS" | "Elixir" => "🟤",
+        "PHP" | "Nix" => "⚪",
+        _ => "🟢",
+    }
+}
@@ -1,15 +1,18 @@
 use std::env;
-use std::fmt::Write;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use tdlib::types::FormattedText;
-use tdlib::{enums, functions};
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{ai_settings, CommandError, CommandResult, CommandTrait};
 use crate::apis::openai::{self, Message};
-use crate::utilities::command_context::CommandContext;
-use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::command_context::{CommandContext, TypingGuard};
+use crate::utilities::convert_argument::{ConvertArgument, FormattedStringGreedyOrReply};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils;
+
+const MODEL: &str = "llama3-70b-8192";
+/// model families hosted by Groq
+const GROQ_MODEL_PREFIXES: &[&str] = &["llama", "mixtral", "gemma"];
 
 pub struct Llama;
 
@@ -24,36 +27,49 @@ impl CommandTrait for Llama {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 60)
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    /// long responses can take a while to generate
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let StringGreedyOrReply(prompt) = StringGreedyOrReply::convert(ctx, &arguments).await?.0;
+        let (settings, arguments) = ai_settings::resolve(ctx, &arguments).await?;
+        let FormattedStringGreedyOrReply(prompt) =
+            FormattedStringGreedyOrReply::convert(ctx, arguments).await?.0;
+        let model = settings.model_or(GROQ_MODEL_PREFIXES, MODEL);
 
-        ctx.send_typing().await?;
+        let _typing = TypingGuard(ctx.send_typing_loop());
 
+        let start = Instant::now();
         let response = openai::chat_completion(
             ctx.bot_state.http_client.clone(),
             "https://api.groq.com/openai/v1",
             &env::var("GROQ_API_KEY").unwrap(),
-            "llama3-70b-8192",
+            model,
             &[Message { role: "user", content: &prompt }],
+            settings.max_tokens_or(256),
+            settings.temperature,
         )
         .await?
         .map_err(|err| CommandError::Custom(format!("error {}: {}", err.code, err.message)))?;
 
+        let latency = start.elapsed();
         let choice = response.choices.into_iter().next().unwrap();
-        let mut text = choice.message.content;
+        let finish_reason =
+            if choice.finish_reason == "stop" { String::new() } else { choice.finish_reason };
 
-        if choice.finish_reason != "stop" {
-            write!(text, " [{}]", choice.finish_reason).unwrap();
-        }
-
-        let enums::FormattedText::FormattedText(formatted_text) =
-            functions::parse_markdown(FormattedText { text, ..Default::default() }, ctx.client_id)
-                .await?;
-
-        ctx.reply_formatted_text(formatted_text).await?;
+        ctx.reply_split_markdown(
+            &choice.message.content,
+            text_utils::format_footer(&[
+                ("model", model.into()),
+                ("latency", format!("{:.1}s", latency.as_secs_f32())),
+                ("finish reason", finish_reason),
+            ]),
+        )
+        .await?;
 
         Ok(())
     }
@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use reqwest::{RequestBuilder, Url};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait, Privilege};
+use crate::apis::{craiyon, makersuite};
+use crate::utilities::command_context::CommandContext;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct Health;
+
+#[async_trait]
+impl CommandTrait for Health {
+    fn command_names(&self) -> &[&str] {
+        &["health"]
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let http_client = &ctx.bot_state.http_client;
+        let mut services = vec![
+            ("Craiyon", craiyon::search_request(http_client, "cat")),
+            (
+                "Stable Horde",
+                http_client.get(format!("{}/status/heartbeat", ctx.bot_state.stablehorde.base_url)),
+            ),
+        ];
+
+        // services without an API key aren't used by the bot, so there's nothing to
+        // check
+        if let Some(api_keys) = makersuite::API_KEYS.as_ref() {
+            services.push((
+                "Google AI",
+                http_client.get(
+                    Url::parse_with_params(
+                        "https://generativelanguage.googleapis.com/v1beta/models",
+                        [("key", api_keys.next())],
+                    )
+                    .unwrap(),
+                ),
+            ));
+        }
+
+        let results =
+            join_all(services.into_iter().map(|(name, request)| probe(name, request))).await;
+
+        let checked_at = OffsetDateTime::now_utc()
+            .format(format_description!("[hour]:[minute]:[second] UTC"))
+            .unwrap();
+
+        ctx.reply(format!("{}\n\nchecked at {checked_at}", results.join("\n"))).await?;
+
+        Ok(())
+    }
+}
+
+async fn probe(name: &str, request: RequestBuilder) -> String {
+    let start = Instant::now();
+
+    match tokio::time::timeout(PROBE_TIMEOUT, request.send()).await {
+        Ok(Ok(response)) => {
+            let latency = start.elapsed().as_millis();
+            let status = response.status();
+            if status.is_success() {
+                format!("✅ {name} {latency}ms")
+            } else {
+                format!("⚠️ {name} {latency}ms ({status})")
+            }
+        }
+        Ok(Err(err)) => format!("❌ {name} ({})", err.without_url()),
+        Err(_) => format!("❌ {name} (timed out after {}s)", PROBE_TIMEOUT.as_secs()),
+    }
+}
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use tdlib::enums::{self, InputMessageContent};
+use tdlib::functions;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+use time::OffsetDateTime;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::utilities::bot_state::BotState;
+use crate::utilities::callback_dispatcher::CallbackContext;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::command_dispatcher;
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::{self, TruncateWithEllipsis};
+
+const MAX_PROMPTS: usize = 10;
+const BUTTONS_PER_ROW: usize = 5;
+
+pub struct History;
+
+#[async_trait]
+impl CommandTrait for History {
+    fn command_names(&self) -> &[&str] {
+        &["history", "prompts"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show your recent image prompts")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let prompts = recent_prompts(&ctx.bot_state, ctx.user.id);
+
+        if prompts.is_empty() {
+            Err("you haven't generated any images yet.")?;
+        }
+
+        let mut entities = vec!["your recent prompts:".bold()];
+        for (i, (_, command, prompt)) in prompts.iter().enumerate() {
+            entities.extend([
+                format!("\n{}. ", i + 1).text_owned(),
+                format!("/{command}").code_owned(),
+                " ".text(),
+                prompt.clone().truncate_with_ellipsis(128).text_owned(),
+            ]);
+        }
+
+        let mut keyboard = KeyboardBuilder::new();
+        for (i, (id, _, _)) in prompts.iter().enumerate() {
+            if i > 0 && i % BUTTONS_PER_ROW == 0 {
+                keyboard = keyboard.row();
+            }
+            keyboard = keyboard
+                .callback_button(format!("↺ {}", i + 1), &format!("history:{id}:{}", ctx.user.id));
+        }
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(keyboard.build()),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub async fn callback(ctx: &CallbackContext, arguments: &str) -> CommandResult {
+    let Some((Ok(id), Ok(user_id))) = arguments
+        .split_once(':')
+        .map(|(id, user_id)| (id.parse::<usize>(), user_id.parse::<i64>()))
+    else {
+        ctx.answer("", false).await?;
+        return Ok(());
+    };
+
+    if ctx.query.sender_user_id != user_id {
+        ctx.answer("only the author of these prompts can re-run them.", false).await?;
+        return Ok(());
+    }
+
+    let now = i32::try_from(OffsetDateTime::now_utc().unix_timestamp()).unwrap();
+    let cooldown =
+        ctx.bot_state.rate_limits.lock().unwrap().history_rerun.update_rate_limit(user_id, now);
+    if let Some(cooldown) = cooldown {
+        let text = format!(
+            "you can re-run a prompt again in {}.",
+            text_utils::format_duration(cooldown.unsigned_abs().into())
+        );
+        ctx.answer(&text, false).await?;
+        return Ok(());
+    }
+
+    let Some(context) = command_context(ctx, now).await? else {
+        ctx.answer("", false).await?;
+        return Ok(());
+    };
+
+    let generation = ctx
+        .bot_state
+        .database
+        .lock()
+        .unwrap()
+        .image_generations
+        .get(id)
+        .filter(|generation| generation.user_id == user_id)
+        .map(|generation| (generation.command.clone(), generation.prompt.clone()));
+    let Some((command_name, prompt)) = generation else {
+        ctx.answer("this prompt is no longer in your history.", false).await?;
+        return Ok(());
+    };
+
+    let Some(command) = ctx.command_manager.get_command(&command_name) else {
+        ctx.answer("this command is no longer available.", false).await?;
+        return Ok(());
+    };
+
+    ctx.answer("", false).await?;
+    log::info!("re-running /{command_name} for {user_id} from history");
+    // goes through the same access checks, rate limits and timeout as a typed
+    // command
+    Box::pin(command_dispatcher::dispatch_command(command, prompt, context)).await;
+
+    Ok(())
+}
+
+/// the user's most recent prompts with their index in the database and the
+/// commands they were used with
+fn recent_prompts(bot_state: &BotState, user_id: i64) -> Vec<(usize, String, String)> {
+    bot_state
+        .database
+        .lock()
+        .unwrap()
+        .image_generations
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, generation)| generation.user_id == user_id)
+        .take(MAX_PROMPTS)
+        .map(|(id, generation)| (id, generation.command.clone(), generation.prompt.clone()))
+        .collect()
+}
+
+/// builds a context replying to the history message on behalf of the user who
+/// pressed the button
+async fn command_context(
+    ctx: &CallbackContext,
+    now: i32,
+) -> Result<Option<CommandContext>, CommandError> {
+    let (chat, user) = {
+        let cache = ctx.bot_state.cache.lock().unwrap();
+        (cache.get_chat(ctx.query.chat_id), cache.get_user(ctx.query.sender_user_id))
+    };
+    let (Some(chat), Some(user)) = (chat, user) else {
+        return Ok(None);
+    };
+
+    let enums::Message::Message(mut message) =
+        functions::get_message(ctx.query.chat_id, ctx.query.message_id, ctx.client_id).await?;
+    message.date = now;
+
+    Ok(Some(CommandContext {
+        client_id: ctx.client_id,
+        chat,
+        user,
+        message,
+        bot_state: ctx.bot_state.clone(),
+        command_manager: ctx.command_manager.clone(),
+    }))
+}
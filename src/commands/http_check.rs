@@ -0,0 +1,91 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use reqwest::header::{
+    HeaderMap, HeaderName, CONTENT_TYPE, SERVER, STRICT_TRANSPORT_SECURITY, X_FRAME_OPTIONS,
+};
+use reqwest::Url;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::open_graph::NON_PUBLIC_URL;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::message_entities::{self, ToEntity};
+use crate::utilities::net_utils;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct HttpCheck;
+
+#[async_trait]
+impl CommandTrait for HttpCheck {
+    fn command_names(&self) -> &[&str] {
+        &["headers", "httpcheck"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("inspect the HTTP headers of a website")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (url, _) = Url::convert(ctx, &arguments).await?;
+
+        if !net_utils::is_public_host(&url) {
+            return Err(NON_PUBLIC_URL.into());
+        }
+
+        ctx.send_typing().await?;
+
+        let start_time = Instant::now();
+        let response =
+            ctx.bot_state.public_http_client.head(url.clone()).timeout(TIMEOUT).send().await;
+        let response_time = start_time.elapsed();
+
+        let response = match response {
+            Ok(response) => response,
+            Err(err) if err.is_timeout() => {
+                return Err(format!("no response after {}s.", TIMEOUT.as_secs()).into())
+            }
+            Err(err) if net_utils::is_non_public_address(&err) => return Err(NON_PUBLIC_URL.into()),
+            Err(err) => Err(err.without_url())?,
+        };
+
+        let headers = response.headers();
+        let mut table = String::new();
+        let mut row = |name: &str, value: &str| {
+            writeln!(table, "{name:<18}{}", value.to_owned().truncate_with_ellipsis(48)).unwrap();
+        };
+
+        row("status", &response.status().to_string());
+        row("server", header(headers, &SERVER));
+        row("content-type", header(headers, &CONTENT_TYPE));
+        row("x-powered-by", header(headers, &HeaderName::from_static("x-powered-by")));
+        row(
+            "hsts",
+            if headers.contains_key(STRICT_TRANSPORT_SECURITY) { "present" } else { "missing" },
+        );
+        row("x-frame-options", header(headers, &X_FRAME_OPTIONS));
+        row("response time", &format!("{}ms", response_time.as_millis()));
+
+        let mut entities = vec![url.as_str().text(), "\n".text(), table.trim_end().code()];
+
+        if url.scheme() == "http" {
+            entities.push("\n⚠️ this URL uses unencrypted HTTP".text());
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &HeaderName) -> &'a str {
+    headers.get(name).and_then(|value| value.to_str().ok()).unwrap_or("–")
+}
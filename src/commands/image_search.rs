@@ -0,0 +1,147 @@
+use std::io::BufWriter;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use futures_util::StreamExt;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+use reqwest::Url;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::{bing, open_graph};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedy};
+use crate::utilities::file_download::MEBIBYTE;
+use crate::utilities::image_utils;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+const IMAGE_COUNT: usize = 4;
+const TILE_SIZE: u32 = 512;
+const MAX_IMAGE_SIZE: u64 = 10 * MEBIBYTE.unsigned_abs();
+
+pub struct ImageSearch {
+    api_key: String,
+}
+
+impl ImageSearch {
+    pub const fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for ImageSearch {
+    fn command_names(&self) -> &[&str] {
+        &["image", "img_search"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("search images on the web")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedy(query) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        ctx.send_typing().await?;
+
+        let results = bing::image_search(
+            ctx.bot_state.http_client.clone(),
+            &self.api_key,
+            &query,
+            IMAGE_COUNT,
+        )
+        .await?;
+
+        if results.is_empty() {
+            return Err("no images found.".into());
+        }
+
+        let http_client = &ctx.bot_state.public_http_client;
+        let images = join_all(
+            results
+                .iter()
+                .take(IMAGE_COUNT)
+                .map(|result| download_tile(http_client, &result.content_url)),
+        )
+        .await;
+
+        let image = image_utils::collage(images, (TILE_SIZE, TILE_SIZE), 8);
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: image.width().try_into().unwrap(),
+                    height: image.height().try_into().unwrap(),
+                    caption: Some(message_entities::formatted_text(vec![
+                        "Search: ".text(),
+                        query.truncate_with_ellipsis(256).bold_owned(),
+                    ])),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                None,
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        Ok(())
+    }
+}
+
+/// downloads an image cropped to a square tile, a gray tile is used if the
+/// download or decoding fails
+async fn download_tile(http_client: &reqwest::Client, url: &str) -> DynamicImage {
+    let result = async {
+        // the URLs come from arbitrary websites, so they get the same checks and
+        // timeout as link previews
+        let response =
+            open_graph::get(http_client, Url::parse(url).map_err(|_| "invalid URL.")?).await?;
+
+        if response.content_length().is_some_and(|length| length > MAX_IMAGE_SIZE) {
+            Err("the image is larger than 10 MiB.")?;
+        }
+
+        let mut bytes = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            bytes.extend_from_slice(&chunk?);
+            if u64::try_from(bytes.len()).unwrap() > MAX_IMAGE_SIZE {
+                Err("the image is larger than 10 MiB.")?;
+            }
+        }
+
+        let image = tokio::task::spawn_blocking(move || {
+            image_utils::load_image(&bytes)
+                .map(|image| image.resize_to_fill(TILE_SIZE, TILE_SIZE, FilterType::Triangle))
+        })
+        .await
+        .unwrap()
+        .map_err(|err| err.to_string())?;
+
+        Ok::<_, CommandError>(image)
+    }
+    .await;
+
+    result.unwrap_or_else(|err| {
+        log::warn!("downloading image search result {url} failed: {err:?}");
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(TILE_SIZE, TILE_SIZE, Rgb([128; 3])))
+    })
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::ipinfo;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, NonPrivateIpAddr};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils;
+
+pub struct IpInfo;
+
+#[async_trait]
+impl CommandTrait for IpInfo {
+    fn command_names(&self) -> &[&str] {
+        &["ipinfo", "geoip"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("look up the location of an IP address")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let NonPrivateIpAddr(ip) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let info = ipinfo::lookup(ctx.bot_state.http_client.clone(), ip).await?;
+
+        if info.bogon {
+            return Err("this is a bogon IP address, it isn't publicly routable.".into());
+        }
+
+        let location = [info.city, info.region]
+            .into_iter()
+            .flatten()
+            .chain(info.country.map(|country| match text_utils::flag_emoji(&country) {
+                Some(flag) => format!("{flag} {country}"),
+                None => country,
+            }))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut entities = vec!["🌐 ".text(), info.ip.code_owned()];
+
+        for (name, value) in
+            [("location", Some(location)), ("organization", info.org), ("timezone", info.timezone)]
+        {
+            if let Some(value) = value.filter(|value| !value.is_empty()) {
+                entities.extend([format!("\n{name}: ").bold_owned(), value.text_owned()]);
+            }
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::telegram_utils::MAX_MESSAGE_LENGTH;
+
+/// leaves room for the type and the note about truncated output
+const MAX_OUTPUT_LENGTH: usize = MAX_MESSAGE_LENGTH - 96;
+
+pub struct JsonPretty;
+
+#[async_trait]
+impl CommandTrait for JsonPretty {
+    fn command_names(&self) -> &[&str] {
+        &["json", "prettyjson"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("pretty-print JSON")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(json) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let value =
+            serde_json::from_str::<Value>(&json).map_err(|err| format!("invalid JSON: {err}."))?;
+
+        let json_type = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+
+        let mut pretty = serde_json::to_string_pretty(&value).unwrap();
+        let truncated = match pretty.char_indices().nth(MAX_OUTPUT_LENGTH) {
+            Some((index, _)) => {
+                pretty.truncate(index);
+                true
+            }
+            None => false,
+        };
+
+        let mut entities =
+            vec![format!("type: {json_type}").italic_owned(), "\n".text(), pretty.pre_owned()];
+
+        if truncated {
+            entities.push(format!("\n(truncated to {MAX_OUTPUT_LENGTH} characters)").text_owned());
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use rand::seq::IndexedRandom;
 
 use super::{CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 
 const WORDS: [&str; 7] = ["kebab", "king", "house", "super", "arab", "hot", "sauce"];
 
@@ -20,7 +20,7 @@ impl CommandTrait for Kebab {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(10, 30)
+        rate_limit_from_env(self.name(), 10, 30)
     }
 
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
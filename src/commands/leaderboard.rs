@@ -0,0 +1,153 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use counter::Counter;
+use tdlib::types::FormattedText;
+use tdlib::{enums, functions};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::database::InvocationResult;
+use crate::utilities::message_entities::{self, Entity, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils;
+
+const TOP_USERS: usize = 10;
+const RANGE_DAYS: i64 = 7;
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Default)]
+pub struct Leaderboard {
+    cache: Mutex<Option<(FormattedText, Instant)>>,
+}
+
+#[async_trait]
+impl CommandTrait for Leaderboard {
+    fn command_names(&self) -> &[&str] {
+        &["top", "leaderboard"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show the most active users of the week")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(_, created_at)| created_at.elapsed() < CACHE_TTL)
+            .map(|(text, _)| text.clone());
+
+        let text = if let Some(text) = cached {
+            text
+        } else {
+            let text = leaderboard_text(ctx).await;
+            *self.cache.lock().unwrap() = Some((text.clone(), Instant::now()));
+            text
+        };
+
+        ctx.reply_formatted_text(text).await?;
+
+        Ok(())
+    }
+}
+
+async fn leaderboard_text(ctx: &CommandContext) -> FormattedText {
+    let now = OffsetDateTime::now_utc();
+    let since = now - time::Duration::days(RANGE_DAYS);
+    let since_timestamp = i32::try_from(since.unix_timestamp()).unwrap();
+
+    let (top_commands, top_generations): (Vec<_>, Vec<_>) = {
+        let database = ctx.bot_state.database.lock().unwrap();
+        (
+            database
+                .command_invocations
+                .iter()
+                .filter(|invocation| {
+                    invocation.date >= since_timestamp
+                        && invocation.result != InvocationResult::RateLimited
+                })
+                .map(|invocation| invocation.user_id)
+                .collect::<Counter<_>>()
+                .most_common()
+                .into_iter()
+                .take(TOP_USERS)
+                .collect(),
+            database
+                .image_generations
+                .iter()
+                .filter(|generation| generation.date >= since_timestamp)
+                .map(|generation| generation.user_id)
+                .collect::<Counter<_>>()
+                .most_common()
+                .into_iter()
+                .take(TOP_USERS)
+                .collect(),
+        )
+    };
+
+    let mut entities = vec!["🏆 leaderboard".bold(), "\n\nmost commands:".bold()];
+    entities.extend(ranking(ctx, top_commands).await);
+    entities.push("\n\nmost images generated:".bold());
+    entities.extend(ranking(ctx, top_generations).await);
+
+    let date_format = format_description!("[year]-[month]-[day]");
+    entities.push(
+        format!(
+            "\n\nfrom {} to {} (UTC)",
+            since.format(date_format).unwrap(),
+            now.format(date_format).unwrap()
+        )
+        .italic_owned(),
+    );
+
+    message_entities::formatted_text(entities)
+}
+
+async fn ranking(ctx: &CommandContext, top_users: Vec<(i64, usize)>) -> Vec<Entity<'static>> {
+    if top_users.is_empty() {
+        return vec!["\nnobody yet.".text()];
+    }
+
+    let mut entities = Vec::new();
+    for (i, (user_id, count)) in top_users.into_iter().enumerate() {
+        entities.extend([
+            format!("\n{}. ", i + 1).text_owned(),
+            display_name(ctx, user_id).await.text_owned(),
+            format!(
+                " – {}",
+                text_utils::format_number(
+                    u64::try_from(count).unwrap(),
+                    *text_utils::NUMBER_LOCALE
+                )
+            )
+            .text_owned(),
+        ]);
+    }
+
+    entities
+}
+
+/// falls back to fetching users who haven't been seen since the bot started
+async fn display_name(ctx: &CommandContext, user_id: i64) -> String {
+    let cached_user = ctx.bot_state.cache.lock().unwrap().get_user(user_id);
+    if let Some(user) = cached_user {
+        return user.to_string();
+    }
+
+    match functions::get_user(user_id, ctx.client_id).await {
+        Ok(enums::User::User(user)) => {
+            format!("{} {}", user.first_name, user.last_name).trim_end().into()
+        }
+        Err(_) => format!("user {user_id}"),
+    }
+}
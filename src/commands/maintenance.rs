@@ -0,0 +1,68 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use super::{CommandError, CommandResult, CommandTrait, Privilege};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedy};
+use crate::utilities::{bot_state, text_utils};
+
+pub struct Maintenance;
+
+#[async_trait]
+impl CommandTrait for Maintenance {
+    fn command_names(&self) -> &[&str] {
+        &["maintenance", "maint"]
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
+
+    fn hidden(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (mut action, rest) = String::convert(ctx, &arguments).await?;
+        action.make_ascii_lowercase();
+
+        match action.as_str() {
+            "on" => {
+                let reason =
+                    Option::<StringGreedy>::convert(ctx, rest).await?.0.map(|reason| reason.0);
+                log::warn!("maintenance mode enabled: {reason:?}");
+                *ctx.bot_state.maintenance.lock().unwrap() =
+                    Some(bot_state::Maintenance { reason, since: Instant::now() });
+                ctx.reply("maintenance mode is now enabled.".into()).await?;
+            }
+            "off" => {
+                let maintenance = ctx.bot_state.maintenance.lock().unwrap().take();
+                if let Some(maintenance) = maintenance {
+                    log::warn!("maintenance mode disabled");
+                    ctx.reply(format!(
+                        "maintenance mode is now disabled after {}.",
+                        text_utils::format_duration(maintenance.since.elapsed().as_secs())
+                    ))
+                    .await?;
+                } else {
+                    ctx.reply("maintenance mode wasn't enabled.".into()).await?;
+                }
+            }
+            "status" => {
+                let status =
+                    ctx.bot_state.maintenance.lock().unwrap().as_ref().map(|maintenance| {
+                        format!(
+                            "maintenance mode is enabled for {} ({}).",
+                            text_utils::format_duration(maintenance.since.elapsed().as_secs()),
+                            maintenance.reason.as_deref().unwrap_or("no reason given")
+                        )
+                    });
+                ctx.reply(status.unwrap_or_else(|| "maintenance mode is disabled.".into())).await?;
+            }
+            _ => Err(CommandError::Custom("available actions: on, off, status.".into()))?,
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs;
+use std::io::BufWriter;
+
+use async_trait::async_trait;
+use image::ImageFormat;
+use tdlib::enums::{File, InputFile, InputMessageContent};
+use tdlib::functions;
+use tdlib::types::{InputFileLocal, InputMessageSticker};
+use tempfile::NamedTempFile;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::file_download::MEBIBYTE;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::{image_utils, telegram_utils};
+
+/// required length of the longer side of a sticker
+const STICKER_SIZE: u32 = 512;
+
+pub struct MakeSticker;
+
+#[async_trait]
+impl CommandTrait for MakeSticker {
+    fn command_names(&self) -> &[&str] {
+        &["sticker", "make_sticker"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("turn an image into a sticker")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let message_image = telegram_utils::get_message_or_reply_image(&ctx.message, ctx.client_id)
+            .await
+            .ok_or("send or reply to an image.")?;
+
+        if message_image.file.expected_size > 10 * MEBIBYTE {
+            Err("the image cannot be larger than 10 MiB.")?;
+        }
+
+        let File::File(file) =
+            functions::download_file(message_image.file.id, 1, 0, 0, true, ctx.client_id).await?;
+
+        let bytes = fs::read(file.local.path).unwrap();
+        let image = tokio::task::spawn_blocking(move || {
+            image_utils::load_image(&bytes)
+                .map(|image| image_utils::fit_square(&image, STICKER_SIZE))
+                .map_err(|err| err.to_string())
+        })
+        .await
+        .unwrap()?;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessageSticker(InputMessageSticker {
+                    sticker: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    width: STICKER_SIZE.try_into().unwrap(),
+                    height: STICKER_SIZE.try_into().unwrap(),
+                    emoji: String::new(),
+                }),
+                None,
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        Ok(())
+    }
+}
@@ -11,17 +11,35 @@ use tdlib::{enums, functions};
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
-use super::{CommandError, CommandResult, CommandTrait};
+use super::{ai_settings, CommandError, CommandResult, CommandTrait};
 use crate::apis::makersuite::{
-    self, Blob, Candidate, CitationSource, GenerateContentResponse, Part, PartResponse,
+    self, Blob, Candidate, CitationSource, GenerateContentResponse, GenerationConfig, Part,
+    PartResponse,
 };
-use crate::utilities::command_context::CommandContext;
-use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::command_context::{CommandContext, TypingGuard};
+use crate::utilities::convert_argument::{ConvertArgument, FormattedStringGreedyOrReply};
 use crate::utilities::file_download::MEBIBYTE;
-use crate::utilities::rate_limit::RateLimiter;
-use crate::utilities::telegram_utils;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::{telegram_utils, text_utils};
 
-pub struct GoogleGemini;
+pub struct GoogleGemini {
+    /// instructions prepended to the user's prompt
+    prompt_prefix: Option<&'static str>,
+}
+
+impl GoogleGemini {
+    pub const fn plain() -> Self {
+        Self { prompt_prefix: None }
+    }
+
+    pub const fn concise() -> Self {
+        Self {
+            prompt_prefix: Some(
+                "Answer the following briefly and to the point, using Markdown only when it helps.",
+            ),
+        }
+    }
+}
 
 #[async_trait]
 impl CommandTrait for GoogleGemini {
@@ -34,19 +52,29 @@ impl CommandTrait for GoogleGemini {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 45)
+        rate_limit_from_env(self.name(), 3, 45)
     }
 
+    /// streamed responses can take a while to finish
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
+    }
+
+    #[allow(clippy::too_many_lines)] // one linear request and streaming loop
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let prompt = Option::<StringGreedyOrReply>::convert(ctx, &arguments).await?.0;
+        let (settings, arguments) = ai_settings::resolve(ctx, &arguments).await?;
+        let prompt = Option::<FormattedStringGreedyOrReply>::convert(ctx, arguments).await?.0;
 
-        ctx.send_typing().await?;
+        let _typing = TypingGuard(ctx.send_typing_loop());
 
         let mut model = "gemini-1.0-pro-latest";
         let mut parts = Vec::new();
 
         if let Some(prompt) = prompt {
-            parts.push(Part::Text(prompt.0));
+            parts.push(Part::Text(match self.prompt_prefix {
+                Some(prefix) => format!("{prefix}\n\n{}", prompt.0),
+                None => prompt.0,
+            }));
         }
 
         if let Some(message_image) =
@@ -74,11 +102,17 @@ impl CommandTrait for GoogleGemini {
             return Err(CommandError::Custom("no prompt or image provided.".into()));
         }
 
+        let model = settings.model_or(&["gemini-"], model).to_owned();
+        let generation_config = GenerationConfig {
+            max_output_tokens: settings.max_tokens_or(512),
+            temperature: settings.temperature,
+        };
         let http_client = ctx.bot_state.http_client.clone();
         let (tx, mut rx) = mpsc::unbounded_channel();
 
         tokio::spawn(async move {
-            makersuite::stream_generate_content(http_client, tx, model, &parts, 512).await;
+            makersuite::stream_generate_content(http_client, tx, &model, &parts, generation_config)
+                .await;
         });
 
         let mut next_update = Instant::now() + Duration::from_secs(5);
@@ -166,16 +200,30 @@ impl CommandTrait for GooglePalm {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 45)
+        rate_limit_from_env(self.name(), 3, 45)
     }
 
-    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
-
-        ctx.send_typing().await?;
+    /// long responses can take a while to generate
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
+    }
 
-        let response =
-            makersuite::generate_text(ctx.bot_state.http_client.clone(), &prompt, 512).await?;
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (settings, arguments) = ai_settings::resolve(ctx, &arguments).await?;
+        let FormattedStringGreedyOrReply(prompt) =
+            ConvertArgument::convert(ctx, arguments).await?.0;
+
+        let _typing = TypingGuard(ctx.send_typing_loop());
+
+        let start = Instant::now();
+        let response = makersuite::generate_text(
+            ctx.bot_state.http_client.clone(),
+            &prompt,
+            settings.max_tokens_or(512),
+            settings.temperature,
+        )
+        .await?;
+        let latency = start.elapsed();
 
         let response = match response {
             Ok(response) => response,
@@ -218,11 +266,14 @@ impl CommandTrait for GooglePalm {
             text.push_str(&format_citations(&citation_metadata.citation_sources));
         }
 
-        let enums::FormattedText::FormattedText(formatted_text) =
-            functions::parse_markdown(FormattedText { text, ..Default::default() }, ctx.client_id)
-                .await?;
-
-        ctx.reply_formatted_text(formatted_text).await?;
+        ctx.reply_split_markdown(
+            &text,
+            text_utils::format_footer(&[
+                ("model", "text-bison-001".into()),
+                ("latency", format!("{:.1}s", latency.as_secs_f32())),
+            ]),
+        )
+        .await?;
 
         Ok(())
     }
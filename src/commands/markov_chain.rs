@@ -1,10 +1,9 @@
 use async_trait::async_trait;
-use tdlib::enums::ChatType;
 
 use super::{CommandResult, CommandTrait};
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 
 pub struct MarkovChain;
 
@@ -19,11 +18,11 @@ impl CommandTrait for MarkovChain {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 20)
+        rate_limit_from_env(self.name(), 3, 20)
     }
 
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
-        if !matches!(ctx.chat.r#type, ChatType::Private(_))
+        if !ctx.is_private_chat()
             && !ctx
                 .bot_state
                 .config
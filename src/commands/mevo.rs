@@ -29,9 +29,11 @@ impl CommandTrait for Mevo {
             " użytkowników), wczoraj: ".text(),
             stats.system_stats.trips_yesterday.to_string().bold(),
             "\nśredni czas dzisiaj: ".text(),
-            text_utils::format_duration(stats.system_stats.median_duration_today as u64).bold(),
+            text_utils::format_duration_string(stats.system_stats.median_duration_today as u64)
+                .bold(),
             ", w tym roku: ".text(),
-            text_utils::format_duration(stats.system_stats.median_duration_this_year as u64).bold(),
+            text_utils::format_duration_string(stats.system_stats.median_duration_this_year as u64)
+                .bold(),
         ]))
         .await?;
 
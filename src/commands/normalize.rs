@@ -0,0 +1,72 @@
+use std::fmt::Write;
+
+use async_trait::async_trait;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedy};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+
+const MAX_CHARS: usize = 20;
+
+pub struct Normalize;
+
+#[async_trait]
+impl CommandTrait for Normalize {
+    fn command_names(&self) -> &[&str] {
+        &["normalize", "norm"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show Unicode normalization forms of characters")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedy(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        // combining marks are normalized together with their base character,
+        // so the text is split into grapheme clusters rather than code points
+        let mut graphemes =
+            text.graphemes(true).filter(|grapheme| !grapheme.chars().all(char::is_whitespace));
+
+        let mut table =
+            format!("  {:<8}{:<12}{:<12}{:<12}{}\n", "input", "NFC", "NFD", "NFKC", "NFKD");
+
+        for grapheme in graphemes.by_ref().take(MAX_CHARS) {
+            let forms = [
+                code_points(grapheme.nfc()),
+                code_points(grapheme.nfd()),
+                code_points(grapheme.nfkc()),
+                code_points(grapheme.nfkd()),
+            ];
+            let original = code_points(grapheme.chars());
+            let marker = if forms.iter().all(|form| *form == original) { ' ' } else { '*' };
+
+            writeln!(
+                table,
+                "{marker} {:<8}{:<12}{:<12}{:<12}{}",
+                original, forms[0], forms[1], forms[2], forms[3]
+            )
+            .unwrap();
+        }
+
+        let mut entities = vec![table.trim_end().to_owned().pre_owned()];
+
+        if graphemes.next().is_some() {
+            entities
+                .push(format!("\nonly the first {MAX_CHARS} characters are shown").italic_owned());
+        }
+
+        entities.push("\n* the normalization forms differ".italic());
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
+
+/// formats characters as space-separated hexadecimal code points
+fn code_points(chars: impl Iterator<Item = char>) -> String {
+    chars.map(|char| format!("{:04X}", u32::from(char))).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,104 @@
+use std::fs;
+
+use async_trait::async_trait;
+use tdlib::enums::File;
+use tdlib::functions;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConversionError, ConvertArgument};
+use crate::utilities::file_download::MEBIBYTE;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::{image_utils, telegram_utils};
+
+/// leaves room for the note about truncated text
+const MAX_LENGTH: usize = telegram_utils::MAX_MESSAGE_LENGTH - 96;
+
+pub struct Ocr;
+
+#[async_trait]
+impl CommandTrait for Ocr {
+    fn command_names(&self) -> &[&str] {
+        &["ocr", "read_text"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("read text from an image")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let language = match arguments.trim_start().strip_prefix("--lang ") {
+            Some(rest) => String::convert(ctx, rest).await?.0,
+            None => "eng".into(),
+        };
+
+        // language codes like `eng` or `eng+pol`
+        if !language.chars().all(|char| char.is_ascii_alphanumeric() || "_+".contains(char)) {
+            Err("invalid language code.")?;
+        }
+
+        let message_image = telegram_utils::get_message_or_reply_image(&ctx.message, ctx.client_id)
+            .await
+            .ok_or("send or reply to an image.")?;
+
+        if message_image.file.expected_size > 10 * MEBIBYTE {
+            Err("the image cannot be larger than 10 MiB.")?;
+        }
+
+        let File::File(file) =
+            functions::download_file(message_image.file.id, 1, 0, 0, true, ctx.client_id).await?;
+
+        ctx.send_typing().await?;
+
+        let bytes = fs::read(file.local.path).unwrap();
+        let text = tokio::task::spawn_blocking(move || {
+            let image = image_utils::load_image(&bytes).map_err(|err| err.to_string())?.into_rgb8();
+            let (width, height) =
+                (i32::try_from(image.width()).unwrap(), i32::try_from(image.height()).unwrap());
+
+            tesseract::ocr_from_frame(image.as_raw(), width, height, 3, width * 3, &language)
+                .map_err(|err| {
+                    log::warn!("Tesseract error: {err}");
+                    "the text couldn't be read, check whether the language is supported.".to_owned()
+                })
+        })
+        .await
+        .unwrap()?;
+
+        let text = clean_text(&text);
+        if text.is_empty() {
+            Err(ConversionError::BadArgument("could not read text from image"))?;
+        }
+
+        let text = match text.char_indices().nth(MAX_LENGTH) {
+            Some((index, _)) => format!("{}…\n\n(the text was truncated)", &text[..index]),
+            None => text,
+        };
+
+        ctx.reply(text).await?;
+
+        Ok(())
+    }
+}
+
+/// trims the lines and collapses runs of empty lines
+fn clean_text(text: &str) -> String {
+    let mut lines = Vec::new();
+
+    for line in text.lines().map(|line| line.split_whitespace().collect::<Vec<_>>().join(" ")) {
+        if line.is_empty() && lines.last().map_or(true, String::is_empty) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
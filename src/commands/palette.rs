@@ -0,0 +1,136 @@
+use std::fs;
+use std::io::BufWriter;
+
+use async_trait::async_trait;
+use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+use imageproc::drawing;
+use imageproc::rect::Rect;
+use tdlib::enums::{File, InputFile, InputMessageContent};
+use tdlib::functions;
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::file_download::MEBIBYTE;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::{image_utils, telegram_utils};
+
+const COLORS: usize = 5;
+const SWATCH_SIZE: (u32, u32) = (120, 60);
+const LABEL_HEIGHT: u32 = 24;
+const MIN_SIZE: u32 = 50;
+
+pub struct Palette;
+
+#[async_trait]
+impl CommandTrait for Palette {
+    fn command_names(&self) -> &[&str] {
+        &["palette", "colors"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("extract the dominant colors of an image")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let message_image = telegram_utils::get_message_or_reply_image(&ctx.message, ctx.client_id)
+            .await
+            .ok_or("send or reply to an image.")?;
+
+        if message_image.file.expected_size > 10 * MEBIBYTE {
+            Err("the image cannot be larger than 10 MiB.")?;
+        }
+
+        let File::File(file) =
+            functions::download_file(message_image.file.id, 1, 0, 0, true, ctx.client_id).await?;
+
+        let bytes = fs::read(file.local.path).unwrap();
+        let colors = tokio::task::spawn_blocking(move || {
+            let image = image_utils::load_image(&bytes).map_err(|err| err.to_string())?;
+
+            if image.width() < MIN_SIZE || image.height() < MIN_SIZE {
+                return Err(format!("the image has to be at least {MIN_SIZE}×{MIN_SIZE} pixels."));
+            }
+
+            // the colors don't need every pixel, this keeps large images fast
+            Ok(image_utils::dominant_colors(&image.thumbnail(256, 256).into_rgb8(), COLORS))
+        })
+        .await
+        .unwrap()?;
+
+        let hex_codes = colors
+            .iter()
+            .map(|Rgb([r, g, b])| format!("#{r:02x}{g:02x}{b:02x}"))
+            .collect::<Vec<_>>();
+
+        let image = palette_image(&colors, &hex_codes);
+        let mut temp_file = NamedTempFile::new().unwrap();
+        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
+
+        let mut entities = vec!["dominant colors: ".text()];
+        for (i, hex_code) in hex_codes.into_iter().enumerate() {
+            if i > 0 {
+                entities.push(", ".text());
+            }
+            entities.push(hex_code.code_owned());
+        }
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: image.width().try_into().unwrap(),
+                    height: image.height().try_into().unwrap(),
+                    caption: Some(message_entities::formatted_text(entities)),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                None,
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        Ok(())
+    }
+}
+
+/// draws a strip of color swatches with their hex codes below
+fn palette_image(colors: &[Rgb<u8>], hex_codes: &[String]) -> DynamicImage {
+    let width = SWATCH_SIZE.0 * u32::try_from(colors.len()).unwrap();
+    let mut image = RgbImage::from_pixel(width, SWATCH_SIZE.1 + LABEL_HEIGHT, Rgb([255, 255, 255]));
+
+    for (i, (color, hex_code)) in colors.iter().zip(hex_codes).enumerate() {
+        let x = i32::try_from(SWATCH_SIZE.0 * u32::try_from(i).unwrap()).unwrap();
+        drawing::draw_filled_rect_mut(
+            &mut image,
+            Rect::at(x, 0).of_size(SWATCH_SIZE.0, SWATCH_SIZE.1),
+            *color,
+        );
+
+        if let Some(font) = image_utils::FONT.as_ref() {
+            drawing::draw_text_mut(
+                &mut image,
+                Rgb([0, 0, 0]),
+                x + 24,
+                i32::try_from(SWATCH_SIZE.1).unwrap() + 3,
+                18.0,
+                font,
+                hex_code,
+            );
+        }
+    }
+
+    DynamicImage::ImageRgb8(image)
+}
@@ -1,4 +1,4 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use tdlib::functions;
@@ -18,12 +18,20 @@ impl CommandTrait for Ping {
         Some("check if the bot is online")
     }
 
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
     async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
         let start = Instant::now();
         functions::test_network(ctx.client_id).await?;
         let duration = start.elapsed();
-        ctx.reply(format!("ping: {}ms", duration.as_millis())).await?;
+        ctx.reply(ping_text(duration)).await?;
 
         Ok(())
     }
 }
+
+pub fn ping_text(duration: Duration) -> String {
+    format!("ping: {}ms", duration.as_millis())
+}
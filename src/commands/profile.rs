@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use counter::Counter;
+use tdlib::enums::{Message, MessageReplyTo, MessageSender};
+use tdlib::functions;
+use tdlib::types::MessageSenderUser;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::database::InvocationResult;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils;
+
+pub struct Profile;
+
+#[async_trait]
+impl CommandTrait for Profile {
+    fn command_names(&self) -> &[&str] {
+        &["profile", "me"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show your usage stats, or of the replied user")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let user_id = replied_user_id(ctx).await.unwrap_or(ctx.user.id);
+        let is_self = user_id == ctx.user.id;
+
+        let (first_seen, commands, image_generations, most_used) = {
+            let database = ctx.bot_state.database.lock().unwrap();
+            let invocations = database
+                .command_invocations
+                .iter()
+                .filter(|invocation| invocation.user_id == user_id)
+                .filter(|invocation| invocation.result != InvocationResult::RateLimited);
+
+            (
+                invocations.clone().map(|invocation| invocation.date).min(),
+                invocations.clone().count(),
+                database
+                    .image_generations
+                    .iter()
+                    .filter(|generation| generation.user_id == user_id)
+                    .count(),
+                invocations
+                    .map(|invocation| invocation.command.clone())
+                    .collect::<Counter<_>>()
+                    .most_common()
+                    .into_iter()
+                    .next(),
+            )
+        };
+
+        let Some(first_seen) = first_seen else {
+            let text = if is_self {
+                "you haven't used any commands yet."
+            } else {
+                "this user hasn't used any commands yet."
+            };
+            return Err(text.into());
+        };
+
+        let name = if is_self {
+            ctx.user.to_string()
+        } else {
+            let cached_user = ctx.bot_state.cache.lock().unwrap().get_user(user_id);
+            cached_user.map_or_else(|| format!("user {user_id}"), |user| user.to_string())
+        };
+
+        let first_seen = OffsetDateTime::from_unix_timestamp(first_seen.into())
+            .unwrap()
+            .format(format_description!("[year]-[month]-[day]"))
+            .unwrap();
+
+        let mut entities = vec![
+            "👤 ".text(),
+            name.bold_owned(),
+            "\nfirst seen: ".text(),
+            first_seen.text_owned(),
+            format!(
+                "\ncommands used: {}",
+                text_utils::format_number(
+                    u64::try_from(commands).unwrap(),
+                    *text_utils::NUMBER_LOCALE
+                )
+            )
+            .text_owned(),
+        ];
+
+        // the other stats are only shown to the user themselves
+        if is_self {
+            entities.push(
+                format!(
+                    "\nimages generated: {}",
+                    text_utils::format_number(
+                        u64::try_from(image_generations).unwrap(),
+                        *text_utils::NUMBER_LOCALE
+                    )
+                )
+                .text_owned(),
+            );
+
+            if let Some((command, count)) = most_used {
+                entities.extend([
+                    "\nmost used command: ".text(),
+                    format!("/{command}").code_owned(),
+                    format!(" ({count}×)").text_owned(),
+                ]);
+            }
+
+            let cooldown = ctx
+                .bot_state
+                .rate_limits
+                .lock()
+                .unwrap()
+                .active_cooldown(user_id, ctx.message.date)
+                .map(|(command, remaining)| (command.to_owned(), remaining));
+
+            entities.push("\nrate limit: ".text());
+            entities.push(match cooldown {
+                Some((command, remaining)) => format!(
+                    "{command} available again in {}",
+                    text_utils::format_duration(remaining.unsigned_abs().into())
+                )
+                .text_owned(),
+                None => "none".text(),
+            });
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
+
+async fn replied_user_id(ctx: &CommandContext) -> Option<i64> {
+    let Some(MessageReplyTo::Message(_)) = &ctx.message.reply_to else {
+        return None;
+    };
+
+    let Message::Message(message) =
+        functions::get_replied_message(ctx.message.chat_id, ctx.message.id, ctx.client_id)
+            .await
+            .ok()?;
+
+    match message.sender_id {
+        MessageSender::User(MessageSenderUser { user_id }) => Some(user_id),
+        MessageSender::Chat(_) => None,
+    }
+}
@@ -39,9 +39,9 @@ impl CommandTrait for RadioPoligon {
                 "\n".text(),
                 text_utils::progress_bar(now_playing.elapsed, now_playing.duration).code_owned(),
                 " ".text(),
-                text_utils::format_duration(now_playing.elapsed.into()).text_owned(),
+                text_utils::format_duration_string(now_playing.elapsed.into()).text_owned(),
                 " / ".text(),
-                text_utils::format_duration(now_playing.duration.into()).text_owned(),
+                text_utils::format_duration_string(now_playing.duration.into()).text_owned(),
                 "\n".text(),
             ]);
         }
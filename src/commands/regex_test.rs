@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, RegexArg, StringGreedy};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+const MAX_MATCHES: usize = 10;
+
+pub struct RegexTest;
+
+#[async_trait]
+impl CommandTrait for RegexTest {
+    fn command_names(&self) -> &[&str] {
+        &["regex", "re"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("test a regex against some text")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (RegexArg { regex, flags }, StringGreedy(text)) =
+            ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        let group_names = regex.capture_names().collect::<Vec<_>>();
+        let mut entities = Vec::new();
+        let mut match_count = 0;
+
+        for captures in regex.captures_iter(&text) {
+            match_count += 1;
+
+            if match_count > MAX_MATCHES {
+                continue;
+            }
+
+            let whole = captures.get(0).unwrap();
+            entities.extend([
+                format!("\nmatch {match_count} at {}..{}: ", whole.start(), whole.end())
+                    .bold_owned(),
+                whole.as_str().to_owned().truncate_with_ellipsis(256).code_owned(),
+            ]);
+
+            for (index, group) in captures.iter().enumerate().skip(1) {
+                let name = group_names[index].map_or_else(String::new, |name| format!(" ({name})"));
+                entities.push(format!("\n  group {index}{name}: ").text_owned());
+                entities.push(group.map_or_else(
+                    || "not matched".italic(),
+                    |group| group.as_str().to_owned().truncate_with_ellipsis(256).code_owned(),
+                ));
+            }
+        }
+
+        if match_count == 0 {
+            return Err("no match.".into());
+        }
+
+        let mut header = vec![
+            format!("{match_count} match{}", if match_count == 1 { "" } else { "es" }).bold_owned(),
+            " · flags: ".text(),
+            if flags.is_empty() { "none".text() } else { flags.code() },
+        ];
+
+        if match_count > MAX_MATCHES {
+            header.push(format!("\nshowing the first {MAX_MATCHES}").italic_owned());
+        }
+
+        header.push("\n".text());
+        header.extend(entities);
+        ctx.reply_formatted_text(message_entities::formatted_text(header)).await?;
+
+        Ok(())
+    }
+}
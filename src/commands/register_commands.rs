@@ -0,0 +1,56 @@
+use tdlib::enums::{BotCommandScope, BotCommands};
+use tdlib::functions;
+use tdlib::types::{BotCommand, BotCommandScopeChat};
+
+use super::{Privilege, OWNER_ID};
+use crate::bot::TdResult;
+use crate::utilities::command_manager::CommandManager;
+
+/// fills the `/` menu of Telegram clients, run when the bot is ready.
+///
+/// there are no command categories, so commands are grouped by who can use
+/// them instead: everyone sees the commands for all users, group admins also
+/// see the admin commands in groups and the owner also sees the owner commands
+/// in their private chat
+pub async fn register_commands(command_manager: &CommandManager, client_id: i32) -> TdResult<()> {
+    sync(None, command_manager.public_command_list(&[Privilege::User]), client_id).await?;
+    sync(
+        Some(BotCommandScope::AllChatAdministrators),
+        command_manager.public_command_list(&[Privilege::User, Privilege::GroupAdmin]),
+        client_id,
+    )
+    .await?;
+    // fails when the owner hasn't started a chat with the bot yet, which
+    // shouldn't stop it from starting
+    if let Err(err) = sync(
+        Some(BotCommandScope::Chat(BotCommandScopeChat { chat_id: OWNER_ID })),
+        command_manager.public_command_list(&[Privilege::User, Privilege::Owner]),
+        client_id,
+    )
+    .await
+    {
+        log::warn!("failed to sync the owner's commands: {}", err.message);
+    }
+
+    Ok(())
+}
+
+async fn sync(
+    scope: Option<BotCommandScope>,
+    commands: Vec<BotCommand>,
+    client_id: i32,
+) -> TdResult<()> {
+    let BotCommands::BotCommands(bot_commands) =
+        functions::get_commands(scope.clone(), String::new(), client_id).await?;
+
+    if commands == bot_commands.commands {
+        log::debug!("commands already synced");
+        return Ok(());
+    }
+
+    let commands_len = commands.len();
+    functions::set_commands(scope, String::new(), commands, client_id).await?;
+    log::info!("synced {commands_len} commands");
+
+    Ok(())
+}
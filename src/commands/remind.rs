@@ -0,0 +1,181 @@
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tdlib::enums::{InputMessageContent, InputMessageReplyTo};
+use tdlib::functions;
+use tdlib::types::{InputMessageReplyToMessage, InputMessageText, LinkPreviewOptions};
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::bot_state::{BotState, BotStatus};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, DurationArg, StringGreedy};
+use crate::utilities::database::{Database, Reminder};
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::text_utils;
+
+const MAX_DURATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MAX_ACTIVE_REMINDERS: usize = 5;
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct Remind;
+
+#[async_trait]
+impl CommandTrait for Remind {
+    fn command_names(&self) -> &[&str] {
+        &["remind", "remindme"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("set a reminder")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (DurationArg(duration), StringGreedy(text)) =
+            ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if duration.is_zero() {
+            return Err("the reminder has to be at least 1 second in the future.".into());
+        }
+
+        if duration > MAX_DURATION {
+            return Err(format!(
+                "reminders can be set at most {} ahead.",
+                text_utils::format_duration(MAX_DURATION.as_secs())
+            )
+            .into());
+        }
+
+        let remind_at =
+            OffsetDateTime::now_utc().unix_timestamp() + i64::try_from(duration.as_secs()).unwrap();
+
+        add_reminder(
+            &mut ctx.bot_state.database.lock().unwrap(),
+            Reminder {
+                user_id: ctx.user.id,
+                chat_id: ctx.message.chat_id,
+                thread_id: ctx.thread_id().unwrap_or_default(),
+                message_id: ctx.message.id,
+                remind_at,
+                text,
+            },
+        )?;
+
+        ctx.reply(format!(
+            "okay, I'll remind you in {}.",
+            text_utils::format_duration(duration.as_secs())
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn add_reminder(database: &mut Database, reminder: Reminder) -> CommandResult {
+    let active_reminders =
+        database.reminders.iter().filter(|active| active.user_id == reminder.user_id).count();
+
+    if active_reminders >= MAX_ACTIVE_REMINDERS {
+        return Err(format!("you can have at most {MAX_ACTIVE_REMINDERS} active reminders.").into());
+    }
+
+    database.reminders.push(reminder);
+    save_database(database);
+
+    Ok(())
+}
+
+/// periodically sends reminders that are due until the bot starts closing
+pub async fn send_reminders(bot_state: Arc<BotState>, client_id: i32) {
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        if !matches!(*bot_state.status.lock().unwrap(), BotStatus::Running) {
+            break;
+        }
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let due_reminders = {
+            let mut database = bot_state.database.lock().unwrap();
+            let (due, pending) = mem::take(&mut database.reminders)
+                .into_iter()
+                .partition::<Vec<_>, _>(|reminder| reminder.remind_at <= now);
+            database.reminders = pending;
+            due
+        };
+
+        if due_reminders.is_empty() {
+            continue;
+        }
+
+        for reminder in due_reminders {
+            send_reminder(&bot_state, reminder, client_id).await;
+        }
+
+        save_database(&bot_state.database.lock().unwrap());
+    }
+}
+
+/// the database is otherwise only saved on shutdown, which a crash would skip
+fn save_database(database: &Database) {
+    if let Err(err) = database.save() {
+        log::error!("failed to save database: {err}");
+    }
+}
+
+async fn send_reminder(bot_state: &BotState, reminder: Reminder, client_id: i32) {
+    let content = InputMessageContent::InputMessageText(InputMessageText {
+        text: message_entities::formatted_text(vec![
+            "⏰ reminder: ".bold(),
+            reminder.text.text_owned(),
+        ]),
+        link_preview_options: Some(LinkPreviewOptions { is_disabled: true, ..Default::default() }),
+        ..Default::default()
+    });
+
+    let rate_limiter = &bot_state.telegram_rate_limiter;
+    let result = rate_limiter
+        .run(reminder.chat_id, || {
+            functions::send_message(
+                reminder.chat_id,
+                reminder.thread_id,
+                Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
+                    message_id: reminder.message_id,
+                    ..Default::default()
+                })),
+                None,
+                None,
+                content.clone(),
+                client_id,
+            )
+        })
+        .await;
+
+    // the original message could have been deleted in the meantime
+    if result.is_err() {
+        if let Err(err) = rate_limiter
+            .run(reminder.chat_id, || {
+                functions::send_message(
+                    reminder.chat_id,
+                    reminder.thread_id,
+                    None,
+                    None,
+                    None,
+                    content.clone(),
+                    client_id,
+                )
+            })
+            .await
+        {
+            log::error!(
+                "failed to send a reminder to {} in {}: {}: {}",
+                reminder.user_id,
+                reminder.chat_id,
+                err.code,
+                err.message
+            );
+        }
+    }
+}
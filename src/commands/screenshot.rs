@@ -1,10 +1,5 @@
-use std::io::Write;
-
 use async_trait::async_trait;
 use reqwest::Url;
-use tdlib::enums::{InputFile, InputMessageContent};
-use tdlib::types::{FormattedText, InputFileLocal, InputMessagePhoto};
-use tempfile::NamedTempFile;
 use url::ParseError;
 
 use super::CommandError::CustomFormattedText;
@@ -13,7 +8,7 @@ use crate::apis::microlink;
 use crate::utilities::command_context::CommandContext;
 use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
 use crate::utilities::message_entities::{self, ToEntity};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 
 pub struct Screenshot;
 
@@ -28,7 +23,7 @@ impl CommandTrait for Screenshot {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 60)
+        rate_limit_from_env(self.name(), 3, 60)
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
@@ -54,39 +49,7 @@ impl CommandTrait for Screenshot {
             ]))
         })?;
 
-        let screenshot = ctx
-            .bot_state
-            .http_client
-            .get(data.screenshot.url)
-            .send()
-            .await?
-            .error_for_status()?
-            .bytes()
-            .await?;
-
-        let mut temp_file = NamedTempFile::new().unwrap();
-        temp_file.write_all(&screenshot).unwrap();
-
-        let message = ctx
-            .reply_custom(
-                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
-                    photo: InputFile::Local(InputFileLocal {
-                        path: temp_file.path().to_str().unwrap().into(),
-                    }),
-                    thumbnail: None,
-                    added_sticker_file_ids: Vec::new(),
-                    width: 0,
-                    height: 0,
-                    caption: data.title.map(|t| FormattedText { text: t, ..Default::default() }),
-                    self_destruct_type: None,
-                    has_spoiler: false,
-                }),
-                None,
-            )
-            .await?;
-
-        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
-        temp_file.close().unwrap();
+        ctx.send_photo_from_url(&data.screenshot.url, data.title).await?;
 
         Ok(())
     }
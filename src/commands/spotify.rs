@@ -0,0 +1,184 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use reqwest::Url;
+use tdlib::enums::InputMessageContent;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::spotify::{self, AccessToken, Artist};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils;
+
+#[derive(Clone, Copy)]
+enum EntityKind {
+    Track,
+    Album,
+    Playlist,
+}
+
+pub struct Spotify {
+    client_id: String,
+    client_secret: String,
+    access_token: Mutex<Option<AccessToken>>,
+}
+
+impl Spotify {
+    pub const fn new(client_id: String, client_secret: String) -> Self {
+        Self { client_id, client_secret, access_token: Mutex::new(None) }
+    }
+
+    /// returns the cached access token or requests a new one once it expires
+    async fn access_token(&self, http_client: reqwest::Client) -> Result<String, CommandError> {
+        let cached_token = self
+            .access_token
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|token| token.is_valid())
+            .map(|token| token.token.clone());
+
+        if let Some(token) = cached_token {
+            return Ok(token);
+        }
+
+        let access_token =
+            spotify::access_token(http_client, &self.client_id, &self.client_secret).await?;
+        let token = access_token.token.clone();
+        *self.access_token.lock().unwrap() = Some(access_token);
+
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl CommandTrait for Spotify {
+    fn command_names(&self) -> &[&str] {
+        &["spotify"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show information about a Spotify track, album or playlist")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(link) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let (kind, id) =
+            parse_link(&link).ok_or("send a Spotify track, album or playlist link or URI.")?;
+
+        ctx.send_typing().await?;
+
+        let http_client = ctx.bot_state.http_client.clone();
+        let access_token = self.access_token(http_client.clone()).await?;
+
+        let (entities, url) = match kind {
+            EntityKind::Track => {
+                let track = spotify::track(http_client, &access_token, &id).await?;
+                (
+                    vec![
+                        format!("🎵 {}", track.name).bold_owned(),
+                        format!("\nby {}", join_artists(&track.artists)).text_owned(),
+                        "\nalbum: ".text(),
+                        track.album.name.text_owned(),
+                        format!(
+                            "\nduration: {}",
+                            text_utils::format_duration(track.duration_ms / 1000)
+                        )
+                        .text_owned(),
+                        format!("\nreleased: {}", track.album.release_date).text_owned(),
+                        format!("\npopularity: {}/100", track.popularity).text_owned(),
+                    ],
+                    track.external_urls.spotify,
+                )
+            }
+            EntityKind::Album => {
+                let album = spotify::album(http_client, &access_token, &id).await?;
+                let mut entities = vec![
+                    format!("💿 {}", album.name).bold_owned(),
+                    format!("\nby {}", join_artists(&album.artists)).text_owned(),
+                    format!("\ntracks: {}", album.total_tracks).text_owned(),
+                    format!("\nreleased: {}", album.release_date).text_owned(),
+                ];
+                if let Some(popularity) = album.popularity {
+                    entities.push(format!("\npopularity: {popularity}/100").text_owned());
+                }
+                (entities, album.external_urls.spotify)
+            }
+            EntityKind::Playlist => {
+                let playlist = spotify::playlist(http_client, &access_token, &id).await?;
+                (
+                    vec![
+                        format!("📃 {}", playlist.name).bold_owned(),
+                        format!(
+                            "\nby {}",
+                            playlist.owner.display_name.unwrap_or(playlist.owner.id)
+                        )
+                        .text_owned(),
+                        format!("\ntracks: {}", playlist.tracks.total).text_owned(),
+                        format!("\nfollowers: {}", playlist.followers.total).text_owned(),
+                    ],
+                    playlist.external_urls.spotify,
+                )
+            }
+        };
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(KeyboardBuilder::new().url_button("🎵 Open in Spotify", url).build()),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn join_artists(artists: &[Artist]) -> String {
+    artists.iter().map(|artist| artist.name.as_str()).collect::<Vec<_>>().join(", ")
+}
+
+/// accepts `spotify:track:ID` URIs and `open.spotify.com` links, including
+/// localized ones like `open.spotify.com/intl-de/track/ID`
+fn parse_link(link: &str) -> Option<(EntityKind, String)> {
+    let link = link.trim();
+
+    if let Some(uri) = link.strip_prefix("spotify:") {
+        let (kind, id) = uri.split_once(':')?;
+        return parse_kind(kind).zip(validate_id(id));
+    }
+
+    let url = Url::parse(link).ok()?;
+    if url.host_str()? != "open.spotify.com" {
+        return None;
+    }
+
+    let mut path = url.path_segments()?.filter(|segment| !segment.starts_with("intl-"));
+    parse_kind(path.next()?).zip(path.next().and_then(validate_id))
+}
+
+fn parse_kind(kind: &str) -> Option<EntityKind> {
+    match kind {
+        "track" => Some(EntityKind::Track),
+        "album" => Some(EntityKind::Album),
+        "playlist" => Some(EntityKind::Playlist),
+        _ => None,
+    }
+}
+
+fn validate_id(id: &str) -> Option<String> {
+    (!id.is_empty() && id.chars().all(|char| char.is_ascii_alphanumeric())).then(|| id.to_owned())
+}
@@ -1,25 +1,49 @@
-use std::io::BufWriter;
+use std::env;
+use std::fmt::Write;
+use std::io::{BufWriter, Cursor};
+use std::sync::LazyLock;
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use counter::Counter;
+use futures_util::future;
 use image::{DynamicImage, ImageFormat};
 use reqwest::Url;
-use tdlib::enums::{InlineKeyboardButtonType, InputFile, InputMessageContent, ReplyMarkup};
-use tdlib::types::{
-    FormattedText, InlineKeyboardButton, InlineKeyboardButtonTypeUrl, InputFileLocal,
-    InputMessagePhoto, ReplyMarkupInlineKeyboard,
-};
-use tempfile::NamedTempFile;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{FormattedText, InputFileLocal, InputMessagePhoto};
+use time::macros::format_description;
+use time::OffsetDateTime;
 
 use super::{CommandError, CommandResult, CommandTrait};
-use crate::apis::stablehorde::{self, GeneratedImage, Status};
+use crate::apis::stablehorde::{self, ControlNet, ControlType, GeneratedImage, HordeError, Status};
+use crate::style_presets::StylePreset;
+use crate::utilities::bot_state::PromptCacheKey;
 use crate::utilities::command_context::CommandContext;
-use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::convert_argument::{
+    ConversionError, ConvertArgument, ReplyPhoto, StringGreedyOrReply, StylePresetArg,
+};
+use crate::utilities::database::ImageGeneration;
+use crate::utilities::keyboard::KeyboardBuilder;
 use crate::utilities::message_entities::{self, formatted_text, ToEntity, ToEntityOwned};
-use crate::utilities::rate_limit::RateLimiter;
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
 use crate::utilities::text_utils::TruncateWithEllipsis;
-use crate::utilities::{api_utils, image_utils, text_utils};
+use crate::utilities::{api_utils, image_utils, text_utils, timing_history, vote_handler};
+
+#[derive(Clone, Copy)]
+enum EtaDisplay {
+    /// time left, like "1m 30s"
+    Relative,
+    /// time of completion, like "12:34:56 UTC"
+    Absolute,
+}
+
+/// set with `ETA_DISPLAY=absolute`, defaults to relative
+static ETA_DISPLAY: LazyLock<EtaDisplay> =
+    LazyLock::new(|| match env::var("ETA_DISPLAY").as_deref() {
+        Ok("absolute") => EtaDisplay::Absolute,
+        _ => EtaDisplay::Relative,
+    });
 
 pub struct StableHorde {
     command_names: &'static [&'static str],
@@ -77,26 +101,170 @@ impl CommandTrait for StableHorde {
     }
 
     fn rate_limit(&self) -> RateLimiter<i64> {
-        RateLimiter::new(3, 300)
+        rate_limit_from_env(self.name(), 3, 300)
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(600)
+    }
+
+    fn cooldown_message(&self, remaining: Duration) -> String {
+        format!(
+            "the image generation queue is cooling down, try again in {}.",
+            text_utils::format_duration(remaining.as_secs())
+        )
     }
 
     async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
-        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let (StylePresetArg(style), StringGreedyOrReply(prompt)) =
+            ConvertArgument::convert(ctx, &arguments).await?.0;
+        let (prompt, options) = parse_options(&prompt)?;
+        let control_image = match options.control_type {
+            Some(_) => Some(ReplyPhoto::convert(ctx, "").await?.0 .0),
+            None => None,
+        };
+        let control_net = options
+            .control_type
+            .zip(control_image.as_deref())
+            .map(|(control_type, image)| ControlNet { image, control_type });
+        let full = options.full;
 
-        if let Some(issue) = text_utils::check_prompt(&prompt) {
+        if let Some(issue) = text_utils::check_prompt(&prompt, &PROMPT_POLICY) {
             log::info!("prompt rejected: {issue:?}");
             Err(issue)?;
         }
 
         ctx.send_typing().await?;
 
-        let generation = Box::pin(self.generate(ctx, prompt)).await?;
+        let cache_key = (self.model.to_owned(), styled_prompt(&prompt, style), self.size);
+        // generations guided by a control image depend on more than the prompt
+        let cached_images = if control_net.is_some() {
+            None
+        } else {
+            ctx.bot_state.prompt_cache.lock().unwrap().get(&cache_key)
+        };
+
+        if let Some(images) = cached_images {
+            let caption = formatted_text(vec![
+                "generated ".text(),
+                prompt.clone().truncate_with_ellipsis(256).bold_owned(),
+                " (cached)".text(),
+            ]);
+
+            if full {
+                send_album(ctx, images, caption.text).await?;
+            } else {
+                self.send_collage(ctx, images, caption, Some(cache_key)).await?;
+            }
+        } else {
+            let has_control_net = control_net.is_some();
+            let generation =
+                Box::pin(self.generate(ctx, prompt.clone(), style, control_net, true)).await?;
+            let images =
+                download_images(ctx.bot_state.http_client.clone(), &generation.urls).await?;
+            let status_msg_id = generation.status_msg_id;
+            if !has_control_net {
+                ctx.bot_state
+                    .prompt_cache
+                    .lock()
+                    .unwrap()
+                    .insert(cache_key.clone(), images.clone());
+            }
+
+            if full {
+                let mut caption = format!(
+                    "generated {} in {}",
+                    generation.escaped_prompt,
+                    text_utils::format_duration(generation.time_taken.as_secs())
+                );
+                if let Some(control_type) = generation.control_type {
+                    write!(caption, " with ControlNet {}", control_type.name()).unwrap();
+                }
+                send_album(ctx, images, caption).await?;
+            } else {
+                let cache_key = (!has_control_net).then_some(cache_key);
+                self.send_collage(ctx, images, format_result_text(generation), cache_key).await?;
+            }
+
+            if let Some(status_msg_id) = status_msg_id {
+                ctx.delete_message(status_msg_id).await.ok();
+            }
+        }
+
+        ctx.bot_state.database.lock().unwrap().image_generations.push(ImageGeneration {
+            command: self.command_names[0].into(),
+            user_id: ctx.user.id,
+            chat_id: ctx.message.chat_id,
+            date: ctx.message.date,
+            prompt,
+        });
+
+        Ok(())
+    }
+}
+
+struct Generation {
+    urls: Vec<Url>,
+    time_taken: Duration,
+    escaped_prompt: String,
+    workers: Counter<Worker>,
+    status_msg_id: Option<i64>,
+    control_type: Option<ControlType>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Worker {
+    id: String,
+    name: String,
+}
+
+pub struct GeneratedCollage {
+    pub image: DynamicImage,
+    pub time_taken: Duration,
+    /// worker names, from the one which generated the most images
+    pub workers: Vec<String>,
+}
+
+impl StableHorde {
+    pub const fn model(&self) -> &'static str {
+        self.model
+    }
+
+    /// generates a collage without sending any status messages
+    pub async fn generate_collage(
+        &self,
+        ctx: &CommandContext,
+        prompt: String,
+    ) -> Result<GeneratedCollage, CommandError> {
+        let generation = Box::pin(self.generate(ctx, prompt, None, None, false)).await?;
         let images = download_images(ctx.bot_state.http_client.clone(), &generation.urls).await?;
-        let image = process_images(images, self.size);
-        let mut temp_file = NamedTempFile::new().unwrap();
-        image.write_to(&mut BufWriter::new(&mut temp_file), ImageFormat::Png).unwrap();
 
-        let status_msg_id = generation.status_msg_id;
+        Ok(GeneratedCollage {
+            image: process_images(images, self.size).await,
+            time_taken: generation.time_taken,
+            workers: generation
+                .workers
+                .most_common()
+                .into_iter()
+                .map(|(worker, _)| worker.name)
+                .collect(),
+        })
+    }
+
+    async fn send_collage(
+        &self,
+        ctx: &CommandContext,
+        images: Vec<Vec<u8>>,
+        caption: FormattedText,
+        cache_key: Option<PromptCacheKey>,
+    ) -> CommandResult {
+        let image = process_images(images, self.size).await;
+        let image_hash = vote_handler::image_hash(&image);
+        if let Some(cache_key) = cache_key {
+            ctx.bot_state.prompt_cache_keys.lock().unwrap().insert(image_hash.clone(), cache_key);
+        }
+        let mut temp_file = ctx.bot_state.temp_files.acquire().await;
+        image.write_to(&mut BufWriter::new(&mut *temp_file), ImageFormat::Png).unwrap();
 
         let message = ctx
             .reply_custom(
@@ -108,57 +276,68 @@ impl CommandTrait for StableHorde {
                     added_sticker_file_ids: Vec::new(),
                     width: image.width().try_into().unwrap(),
                     height: image.height().try_into().unwrap(),
-                    caption: Some(format_result_text(generation)),
+                    caption: Some(caption),
                     self_destruct_type: None,
                     has_spoiler: false,
                 }),
-                Some(ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard {
-                    rows: vec![vec![InlineKeyboardButton {
-                        text: "generated thanks to Stable Horde".into(),
-                        r#type: InlineKeyboardButtonType::Url(InlineKeyboardButtonTypeUrl {
-                            url: "https://stablehorde.net/".into(),
-                        }),
-                    }]],
-                })),
+                Some(
+                    vote_handler::with_vote_buttons(
+                        KeyboardBuilder::new(),
+                        &ctx.bot_state,
+                        &image_hash,
+                        ctx.user.id,
+                    )
+                    .row()
+                    .url_button("generated thanks to Stable Horde", "https://stablehorde.net/")
+                    .build(),
+                ),
             )
             .await?;
 
         ctx.bot_state.message_queue.wait_for_message(message.id).await?;
-        if let Some(status_msg_id) = status_msg_id {
-            ctx.delete_message(status_msg_id).await.ok();
-        }
-        temp_file.close().unwrap();
+        drop(temp_file);
 
         Ok(())
     }
-}
-
-struct Generation {
-    urls: Vec<Url>,
-    time_taken: Duration,
-    escaped_prompt: String,
-    workers: Counter<String>,
-    status_msg_id: Option<i64>,
-}
 
-impl StableHorde {
     async fn generate(
         &self,
         ctx: &CommandContext,
         prompt: String,
+        style: Option<&StylePreset>,
+        control_net: Option<ControlNet<'_>>,
+        show_status: bool,
     ) -> Result<Generation, CommandError> {
+        let control_type = control_net.as_ref().map(|control_net| control_net.control_type);
         let request_id = stablehorde::generate(
             ctx.bot_state.http_client.clone(),
-            &prompt,
+            &ctx.bot_state.stablehorde,
+            &styled_prompt(&prompt, style),
             self.model,
             self.size,
+            ctx.nsfw_allowed(),
+            control_net,
         )
-        .await??;
+        .await
+        .map_err(horde_error)?;
         let escaped_prompt = prompt.truncate_with_ellipsis(256);
-        let (results, status_msg_id, time_taken) =
-            Box::pin(wait_for_generation(ctx, &request_id, &escaped_prompt)).await?;
-        let workers =
-            results.iter().map(|generation| generation.worker_name.clone()).collect::<Counter<_>>();
+        let historical_mean = ctx.bot_state.timing_history.lock().unwrap().mean(self.model);
+        let (results, status_msg_id, time_taken) = Box::pin(wait_for_generation(
+            ctx,
+            &request_id,
+            &escaped_prompt,
+            historical_mean,
+            show_status,
+        ))
+        .await?;
+        ctx.bot_state.timing_history.lock().unwrap().push(self.model, time_taken);
+        let workers = results
+            .iter()
+            .map(|generation| Worker {
+                id: generation.worker_id.clone(),
+                name: generation.worker_name.clone(),
+            })
+            .collect::<Counter<_>>();
         let urls = results
             .into_iter()
             .filter_map(|generation| {
@@ -180,7 +359,7 @@ impl StableHorde {
             Err("no images were successfully generated.")?;
         }
 
-        Ok(Generation { urls, time_taken, escaped_prompt, workers, status_msg_id })
+        Ok(Generation { urls, time_taken, escaped_prompt, workers, status_msg_id, control_type })
     }
 }
 
@@ -188,15 +367,23 @@ async fn wait_for_generation(
     ctx: &CommandContext,
     request_id: &str,
     escaped_prompt: &str,
+    historical_mean: Option<Duration>,
+    show_status: bool,
 ) -> Result<(Vec<GeneratedImage>, Option<i64>, Duration), CommandError> {
     let start_time = Instant::now();
     let mut status_msg_id: Option<i64> = None;
     let mut last_edit: Option<Instant> = None;
     let mut last_status = None;
     let mut show_volunteer_notice = false;
+    let mut smoothed_queue_position = None;
 
     let time_taken = loop {
-        let status = stablehorde::check(ctx.bot_state.http_client.clone(), request_id).await??;
+        let status = stablehorde::check(
+            ctx.bot_state.http_client.clone(),
+            &ctx.bot_state.stablehorde,
+            request_id,
+        )
+        .await??;
 
         if status.done {
             break start_time.elapsed();
@@ -207,7 +394,12 @@ async fn wait_for_generation(
         }
 
         if !status.is_possible {
-            stablehorde::cancel_generation(ctx.bot_state.http_client.clone(), request_id).await?;
+            stablehorde::cancel_generation(
+                ctx.bot_state.http_client.clone(),
+                &ctx.bot_state.stablehorde,
+                request_id,
+            )
+            .await?;
             Err("there are no online workers for the requested model.")?;
         }
 
@@ -215,11 +407,19 @@ async fn wait_for_generation(
             show_volunteer_notice = true;
         }
 
-        if last_status.as_ref() != Some(&status) {
+        smoothed_queue_position =
+            smooth_queue_position(smoothed_queue_position, status.queue_position);
+
+        if show_status && last_status.as_ref() != Some(&status) {
             // the message doesn't exist yet or was edited more than 12 seconds ago
             if last_edit.map_or(true, |last_edit| last_edit.elapsed() >= Duration::from_secs(12)) {
-                let formatted_text =
-                    format_status_text(&status, escaped_prompt, show_volunteer_notice);
+                let formatted_text = format_status_text(
+                    &status,
+                    smoothed_queue_position,
+                    escaped_prompt,
+                    historical_mean,
+                    show_volunteer_notice,
+                );
                 status_msg_id = Some(match status_msg_id {
                     None => {
                         ctx.bot_state
@@ -229,7 +429,9 @@ async fn wait_for_generation(
                             .id
                     }
                     Some(status_msg) => {
-                        ctx.edit_message_formatted_text(status_msg, formatted_text).await?.id
+                        Box::pin(ctx.edit_or_resend_formatted_text(status_msg, formatted_text))
+                            .await?
+                            .id
                     }
                 });
 
@@ -241,10 +443,42 @@ async fn wait_for_generation(
         tokio::time::sleep(Duration::from_secs(2)).await;
     };
 
-    let results = stablehorde::results(ctx.bot_state.http_client.clone(), request_id).await??;
+    let results = stablehorde::results(
+        ctx.bot_state.http_client.clone(),
+        &ctx.bot_state.stablehorde,
+        request_id,
+    )
+    .await??;
     Ok((results, status_msg_id, time_taken))
 }
 
+fn horde_error(err: HordeError) -> CommandError {
+    match err {
+        HordeError::Http(err) => CommandError::Reqwest(err),
+        HordeError::Server(status_code) => CommandError::Server(status_code),
+        HordeError::Api { code, message } => format!("{code}: {message}").into(),
+        HordeError::RateLimited { retry_after } => format!(
+            "Stable Horde is rate limiting requests, try again in {}.",
+            text_utils::format_duration(retry_after.into())
+        )
+        .into(),
+        HordeError::Maintenance => "Stable Horde is under maintenance, try again later.".into(),
+        HordeError::InvalidJson(err) => {
+            log::error!("invalid Stable Horde response: {err}");
+            "Stable Horde returned an invalid response.".into()
+        }
+    }
+}
+
+/// appends the style to the prompt, Stable Horde takes the negative prompt
+/// after `###`
+fn styled_prompt(prompt: &str, style: Option<&StylePreset>) -> String {
+    style.map_or_else(
+        || prompt.into(),
+        |style| format!("{} ### {}", style.apply(prompt), style.negative_prefix),
+    )
+}
+
 async fn download_images(
     http_client: reqwest::Client,
     urls: &[Url],
@@ -259,41 +493,89 @@ async fn download_images(
     Ok(images)
 }
 
-fn process_images(images: Vec<Vec<u8>>, size: (u32, u32)) -> DynamicImage {
-    let images = images
+/// sends every image separately instead of in a collage
+async fn send_album(ctx: &CommandContext, images: Vec<Vec<u8>>, caption: String) -> CommandResult {
+    let photos = decode_images(images)
+        .await
         .into_iter()
-        .flat_map(|image| image::load_from_memory_with_format(&image, ImageFormat::WebP))
+        .map(|image| {
+            let mut photo = Vec::new();
+            image.write_to(&mut Cursor::new(&mut photo), ImageFormat::Png).unwrap();
+            photo
+        })
+        .enumerate()
+        .map(|(i, photo)| (photo, if i == 0 { caption.clone() } else { String::new() }))
         .collect();
 
-    image_utils::collage(images, size, 8)
+    ctx.reply_photo_album(photos).await
+}
+
+/// decodes the images in parallel on the blocking thread pool, skipping invalid
+/// ones
+async fn decode_images(images: Vec<Vec<u8>>) -> Vec<DynamicImage> {
+    future::join_all(images.into_iter().map(|image| {
+        // workers usually return WebP, but some of them send JPEG
+        tokio::task::spawn_blocking(move || image_utils::load_image(&image))
+    }))
+    .await
+    .into_iter()
+    .filter_map(|result| result.unwrap().ok())
+    .collect()
+}
+
+async fn process_images(images: Vec<Vec<u8>>, size: (u32, u32)) -> DynamicImage {
+    image_utils::collage(decode_images(images).await, size, 8)
+}
+
+/// exponential moving average of the queue position, which can jump around
+/// between checks
+fn smooth_queue_position(smoothed: Option<f64>, queue_position: u32) -> Option<f64> {
+    if queue_position == 0 {
+        return None;
+    }
+
+    let queue_position = f64::from(queue_position);
+    Some(smoothed.map_or(queue_position, |smoothed| 0.7f64.mul_add(smoothed, 0.3 * queue_position)))
 }
 
 fn format_status_text(
     status: &Status,
+    queue_position: Option<f64>,
     escaped_prompt: &str,
+    historical_mean: Option<Duration>,
     volunteer_notice: bool,
 ) -> FormattedText {
-    let queue_info = if status.queue_position > 0 {
-        format!("queue position: {}\n", status.queue_position)
-    } else {
-        String::new()
-    };
+    let queue_info = queue_position.map_or_else(String::new, |queue_position| {
+        // the average of positive integers is positive and in range
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let queue_position = queue_position.round() as u64;
+        format!(
+            "queue position: {}\n",
+            text_utils::format_number(queue_position, *text_utils::NUMBER_LOCALE)
+        )
+    });
 
     let mut entities = vec![
         "generating ".text(),
         escaped_prompt.text(),
         "…\n".text(),
         queue_info.text(),
-        progress_bar(
-            status.waiting.unsigned_abs() as usize,
-            status.processing.unsigned_abs() as usize,
-            status.finished.unsigned_abs() as usize,
+        text_utils::progress_bar_emoji(
+            status.waiting.unsigned_abs().into(),
+            status.processing.unsigned_abs().into(),
+            status.finished.unsigned_abs().into(),
         )
-        .code_owned(),
-        " ETA: ".text(),
-        text_utils::format_duration(status.wait_time.into()).text_owned(),
+        .text_owned(),
     ];
 
+    let api_estimate = Duration::from_secs(status.wait_time.into());
+    if let Some(historical_mean) = historical_mean {
+        let estimate = timing_history::blend_estimate(api_estimate, historical_mean);
+        entities.push(format!(" est. {} (adj.)", format_eta(estimate, *ETA_DISPLAY)).text_owned());
+    } else {
+        entities.push(format!(" ETA: {}", format_eta(api_estimate, *ETA_DISPLAY)).text_owned());
+    }
+
     if volunteer_notice {
         entities.extend([
             "\n\nStable Horde is run by volunteers. to make wait times shorter, ".text(),
@@ -305,14 +587,59 @@ fn format_status_text(
     message_entities::formatted_text(entities)
 }
 
+#[derive(Default)]
+struct Options {
+    full: bool,
+    control_type: Option<ControlType>,
+}
+
+/// strips the `--full` and `--controlnet <type>` flags from the start of the
+/// prompt
+fn parse_options(mut prompt: &str) -> Result<(String, Options), ConversionError> {
+    let mut options = Options::default();
+
+    loop {
+        if let Some(rest) = prompt.strip_prefix("--full ") {
+            options.full = true;
+            prompt = rest.trim_start();
+        } else if let Some(rest) = prompt.strip_prefix("--controlnet ") {
+            let rest = rest.trim_start();
+            let (name, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+            options.control_type =
+                Some(ControlType::from_name(name).ok_or(ConversionError::BadArgument(
+                    "unknown ControlNet type, use canny, depth, openpose or scribble.",
+                ))?);
+            prompt = rest.trim_start();
+        } else {
+            break;
+        }
+    }
+
+    Ok((prompt.to_owned(), options))
+}
+
+fn format_eta(estimate: Duration, display: EtaDisplay) -> String {
+    match display {
+        EtaDisplay::Relative => text_utils::format_duration_string(estimate.as_secs()),
+        EtaDisplay::Absolute => (OffsetDateTime::now_utc() + estimate)
+            .format(format_description!("[hour]:[minute]:[second] UTC"))
+            .unwrap(),
+    }
+}
+
 fn format_result_text(generation: Generation) -> FormattedText {
     let workers = generation
         .workers
         .most_common()
         .into_iter()
-        .flat_map(|(worker_name, generated_images)| {
-            let mut entities =
-                vec![", ".text(), worker_name.truncate_with_ellipsis(64).text_owned()];
+        .flat_map(|(worker, generated_images)| {
+            let mut entities = vec![
+                ", ".text(),
+                worker
+                    .name
+                    .truncate_with_ellipsis(64)
+                    .text_url_owned(format!("https://stablehorde.net/worker/{}", worker.id)),
+            ];
 
             if generated_images > 1 {
                 entities.extend([
@@ -339,23 +666,54 @@ fn format_result_text(generation: Generation) -> FormattedText {
         "generated ".text(),
         generation.escaped_prompt.bold(),
         " in ".text(),
-        text_utils::format_duration(generation.time_taken.as_secs()).text_owned(),
+        text_utils::format_duration_string(generation.time_taken.as_secs()).text_owned(),
         " by ".text(),
     ];
 
     entities.extend(workers);
+
+    if let Some(control_type) = generation.control_type {
+        entities.extend([" with ControlNet ".text(), control_type.name().code()]);
+    }
+
     entities.push(".\ndownload: ".text());
     entities.extend(download_urls);
 
     formatted_text(entities)
 }
 
-fn progress_bar(waiting: usize, processing: usize, finished: usize) -> String {
-    let mut bar = String::with_capacity(4 * (waiting + processing + finished) + 2);
-    bar.push('[');
-    bar.push_str(&"=".repeat(5 * finished));
-    bar.push_str(&"-".repeat(5 * processing));
-    bar.push_str(&" ".repeat(5 * waiting));
-    bar.push(']');
-    bar
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// compares decoding on the blocking thread pool with decoding the images
+    /// one after another, run with
+    /// `cargo test --release bench_decode_images -- --ignored --nocapture`
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "benchmark"]
+    async fn bench_decode_images() {
+        // noise, so that the images take about as long to decode as generated ones
+        let mut seed = 1u32;
+        let pixels = image::RgbImage::from_fn(512, 512, |_, _| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            image::Rgb(seed.to_be_bytes()[..3].try_into().unwrap())
+        });
+        let mut image = Vec::new();
+        DynamicImage::ImageRgb8(pixels)
+            .write_to(&mut Cursor::new(&mut image), ImageFormat::WebP)
+            .unwrap();
+        let images = vec![image; 4];
+
+        let start = Instant::now();
+        assert_eq!(decode_images(images.clone()).await.len(), 4);
+        let concurrent = start.elapsed();
+
+        let start = Instant::now();
+        for image in &images {
+            image_utils::load_image(image).unwrap();
+        }
+        let sequential = start.elapsed();
+
+        println!("spawn_blocking: {concurrent:?}, sequential: {sequential:?}");
+    }
 }
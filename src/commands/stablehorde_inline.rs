@@ -0,0 +1,355 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::Url;
+use tdlib::enums::{InputInlineQueryResult, InputMessageContent, ReplyMarkup, TextEntityType};
+use tdlib::functions;
+use tdlib::types::{
+    FormattedText, InputInlineQueryResultArticle, InputMessageText, LinkPreviewOptions,
+    UpdateNewChosenInlineResult, UpdateNewInlineQuery,
+};
+use time::OffsetDateTime;
+
+use super::{CommandError, OWNER_ID};
+use crate::apis::stablehorde::{self, HordeConfig};
+use crate::utilities::bot_state::BotState;
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::prompt_policy::PROMPT_POLICY;
+use crate::utilities::text_utils::{self, TruncateWithEllipsis};
+use crate::utilities::{api_utils, command_dispatcher};
+
+pub const PREFIX: &str = "sd ";
+const MODEL: &str = "stable_diffusion";
+const SIZE: (u32, u32) = (512, 512);
+const TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// answers `sd <prompt>` inline queries with the cached result or a placeholder
+/// starting the generation once chosen
+pub async fn execute(query: UpdateNewInlineQuery, bot_state: Arc<BotState>, client_id: i32) {
+    let prompt = query.query.strip_prefix(PREFIX).unwrap_or_default().trim();
+
+    let result = if let Err(reason) = check_access(&bot_state, query.sender_user_id) {
+        let Some(reason) = reason else {
+            return;
+        };
+        Some(article(
+            "0".into(),
+            reason.clone(),
+            FormattedText { text: reason, ..Default::default() },
+        ))
+    } else if prompt.is_empty() {
+        None
+    } else if let Some(issue) = text_utils::check_prompt(prompt, &PROMPT_POLICY) {
+        log::info!("inline prompt rejected: {issue:?}");
+        Some(article(
+            "0".into(),
+            issue.message.clone(),
+            FormattedText { text: issue.message, ..Default::default() },
+        ))
+    } else {
+        let hash = prompt_hash(prompt);
+        let generation = bot_state.inline_generations.lock().unwrap().get(&hash);
+
+        Some(generation.map_or_else(
+            // the generation starts after choosing the result, so it doesn't run for every
+            // typed character
+            || {
+                let mut result =
+                    article(format!("sd:{hash}"), "⏳ Generating…".into(), generating_text(prompt));
+                result.description = prompt.to_owned().truncate_with_ellipsis(128);
+                result
+            },
+            |text| {
+                let mut result = article(format!("done:{hash}"), format!("send {prompt}"), text);
+                result.description = "generated with Stable Diffusion".into();
+                result
+            },
+        ))
+    };
+
+    functions::answer_inline_query(
+        query.id,
+        true,
+        None,
+        result.map(InputInlineQueryResult::Article).into_iter().collect(),
+        0,
+        String::new(),
+        client_id,
+    )
+    .await
+    .ok();
+}
+
+/// starts the generation of a chosen placeholder and edits it with the result
+pub async fn chosen(update: UpdateNewChosenInlineResult, bot_state: Arc<BotState>, client_id: i32) {
+    let Some(hash) = update.result_id.strip_prefix("sd:") else {
+        return;
+    };
+    let prompt = update.query.strip_prefix(PREFIX).unwrap_or_default().trim().to_owned();
+    let inline_message_id = update.inline_message_id;
+
+    if prompt.is_empty() || inline_message_id.is_empty() {
+        return;
+    }
+
+    if let Err(reason) = check_access(&bot_state, update.sender_user_id) {
+        if let Some(reason) = reason {
+            edit_inline_message(
+                &bot_state,
+                &inline_message_id,
+                FormattedText { text: reason, ..Default::default() },
+                client_id,
+            )
+            .await;
+        }
+        return;
+    }
+
+    // the pending generations stay locked while checking for a result, so a
+    // generation can't be started twice or finish in between
+    let (done, cooldown) = {
+        let mut pending_generations = bot_state.pending_inline_generations.lock().unwrap();
+        if let Some(inline_message_ids) = pending_generations.get_mut(hash) {
+            inline_message_ids.push(inline_message_id);
+            return;
+        }
+
+        let done = bot_state.inline_generations.lock().unwrap().get(&hash.to_owned());
+        if done.is_some() {
+            drop(pending_generations);
+            (done, None)
+        } else {
+            let now = i32::try_from(OffsetDateTime::now_utc().unix_timestamp()).unwrap();
+            let cooldown = bot_state
+                .rate_limits
+                .lock()
+                .unwrap()
+                .inline_generation
+                .update_rate_limit(update.sender_user_id, now);
+            if cooldown.is_none() {
+                pending_generations.insert(hash.into(), vec![inline_message_id.clone()]);
+            }
+            drop(pending_generations);
+            (None, cooldown)
+        }
+    };
+
+    if let Some(text) = done {
+        edit_inline_message(&bot_state, &inline_message_id, text, client_id).await;
+        return;
+    }
+
+    if let Some(cooldown) = cooldown {
+        let text = format!(
+            "you can generate images again in {}.",
+            text_utils::format_duration(cooldown.unsigned_abs().into())
+        );
+        edit_inline_message(
+            &bot_state,
+            &inline_message_id,
+            FormattedText { text, ..Default::default() },
+            client_id,
+        )
+        .await;
+        return;
+    }
+
+    log::info!("generating {prompt:?} inline for {}", update.sender_user_id);
+    let text = match tokio::time::timeout(
+        TIMEOUT,
+        generate(bot_state.http_client.clone(), &bot_state.stablehorde, &prompt),
+    )
+    .await
+    {
+        Ok(Ok(text)) => Some(text),
+        Ok(Err(err)) => {
+            log::warn!("inline generation failed: {err}");
+            None
+        }
+        Err(_) => {
+            log::warn!("inline generation timed out");
+            None
+        }
+    };
+
+    let inline_message_ids = {
+        let mut pending_generations = bot_state.pending_inline_generations.lock().unwrap();
+        if let Some(text) = &text {
+            bot_state.inline_generations.lock().unwrap().insert(hash.into(), text.clone());
+        }
+        pending_generations.remove(hash).unwrap_or_default()
+    };
+
+    let text = text.unwrap_or_else(|| FormattedText {
+        text: "generating the images failed, try again later.".into(),
+        ..Default::default()
+    });
+    for inline_message_id in inline_message_ids {
+        edit_inline_message(&bot_state, &inline_message_id, text.clone(), client_id).await;
+    }
+}
+
+/// inline queries don't go through the command dispatcher, so the blacklist and
+/// maintenance are checked here. `Err(None)` means the user should be ignored
+fn check_access(bot_state: &BotState, user_id: i64) -> Result<(), Option<String>> {
+    if bot_state.config.lock().unwrap().blacklist.contains(&user_id) {
+        return Err(None);
+    }
+
+    if user_id != OWNER_ID {
+        if let Some(message) = command_dispatcher::maintenance_message(bot_state) {
+            return Err(Some(message));
+        }
+    }
+
+    Ok(())
+}
+
+async fn generate(
+    http_client: reqwest::Client,
+    config: &HordeConfig,
+    prompt: &str,
+) -> Result<FormattedText, String> {
+    let start_time = Instant::now();
+    // inline results can be sent to any chat, so NSFW is never allowed
+    let request_id =
+        stablehorde::generate(http_client.clone(), config, prompt, MODEL, SIZE, false, None)
+            .await
+            .map_err(|_| "the generation couldn't be started")?;
+
+    loop {
+        let status = stablehorde::check(http_client.clone(), config, &request_id)
+            .await
+            .map_err(command_error)??;
+
+        if status.done {
+            break;
+        }
+
+        if status.faulted || !status.is_possible {
+            stablehorde::cancel_generation(http_client.clone(), config, &request_id).await.ok();
+            return Err("the generation can't be finished".into());
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+
+    let time_taken = start_time.elapsed();
+    let urls = stablehorde::results(http_client, config, &request_id)
+        .await
+        .map_err(command_error)??
+        .into_iter()
+        .filter_map(|generation| api_utils::cloudflare_storage_url(&generation.img).ok())
+        .collect::<Vec<_>>();
+
+    if urls.is_empty() {
+        return Err("no images were successfully generated".into());
+    }
+
+    Ok(result_text(prompt, &urls, time_taken))
+}
+
+fn command_error(err: CommandError) -> String {
+    match err {
+        CommandError::Reqwest(err) => err.without_url().to_string(),
+        CommandError::Server(status_code) => format!("server error ({status_code})"),
+        _ => "unknown error".into(),
+    }
+}
+
+fn result_text(prompt: &str, urls: &[Url], time_taken: Duration) -> FormattedText {
+    let mut entities = vec![
+        "generated ".text(),
+        prompt.to_owned().truncate_with_ellipsis(256).bold_owned(),
+        " in ".text(),
+        text_utils::format_duration_string(time_taken.as_secs()).text_owned(),
+        ".\ndownload:".text(),
+    ];
+
+    for (i, url) in urls.iter().enumerate() {
+        entities.extend([" ".text(), (i + 1).to_string().text_url_owned(url.to_string())]);
+    }
+
+    message_entities::formatted_text(entities)
+}
+
+fn generating_text(prompt: &str) -> FormattedText {
+    message_entities::formatted_text(vec![
+        "⏳ generating ".text(),
+        prompt.to_owned().truncate_with_ellipsis(256).bold_owned(),
+        "…".text(),
+    ])
+}
+
+/// the first image is shown as a large link preview
+fn message_content(text: FormattedText) -> InputMessageContent {
+    let url = text.entities.iter().find_map(|entity| match &entity.r#type {
+        TextEntityType::TextUrl(text_url) => Some(text_url.url.clone()),
+        _ => None,
+    });
+
+    InputMessageContent::InputMessageText(InputMessageText {
+        text,
+        link_preview_options: Some(url.map_or_else(
+            || LinkPreviewOptions { is_disabled: true, ..Default::default() },
+            |url| LinkPreviewOptions {
+                url,
+                force_large_media: true,
+                show_above_text: true,
+                ..Default::default()
+            },
+        )),
+        ..Default::default()
+    })
+}
+
+fn article(id: String, title: String, text: FormattedText) -> InputInlineQueryResultArticle {
+    InputInlineQueryResultArticle {
+        id,
+        url: String::new(),
+        hide_url: true,
+        title,
+        description: String::new(),
+        thumbnail_url: String::new(),
+        thumbnail_width: 0,
+        thumbnail_height: 0,
+        reply_markup: Some(stable_horde_markup()),
+        input_message_content: message_content(text),
+    }
+}
+
+fn stable_horde_markup() -> ReplyMarkup {
+    KeyboardBuilder::new()
+        .url_button("generated thanks to Stable Horde", "https://stablehorde.net/")
+        .build()
+}
+
+async fn edit_inline_message(
+    bot_state: &BotState,
+    inline_message_id: &str,
+    text: FormattedText,
+    client_id: i32,
+) {
+    let content = message_content(text);
+    // inline messages aren't in any of the bot's chats, so they share a flood wait
+    let result = bot_state
+        .telegram_rate_limiter
+        .run(0, || {
+            functions::edit_inline_message_text(
+                inline_message_id.into(),
+                Some(stable_horde_markup()),
+                content.clone(),
+                client_id,
+            )
+        })
+        .await;
+
+    if let Err(err) = result {
+        log::warn!("failed to edit an inline message: {}: {}", err.code, err.message);
+    }
+}
+
+fn prompt_hash(prompt: &str) -> String {
+    format!("{:x}", md5::compute(prompt))
+}
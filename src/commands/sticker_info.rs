@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use tdlib::enums::{self, InputMessageContent, StickerFormat, StickerType};
+use tdlib::functions;
+use tdlib::types::{InputMessageText, LinkPreviewOptions, StickerSet};
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, ReplySticker};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+pub struct StickerInfo;
+
+#[async_trait]
+impl CommandTrait for StickerInfo {
+    fn command_names(&self) -> &[&str] {
+        &["stickerpack", "sp"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show information about a sticker set")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let name = arguments.trim();
+        let name = name.strip_prefix("https://t.me/addstickers/").unwrap_or(name);
+
+        let enums::StickerSet::StickerSet(sticker_set) = if name.is_empty() {
+            let ReplySticker(sticker) = ConvertArgument::convert(ctx, &arguments).await?.0;
+            if sticker.set_id == 0 {
+                return Err("this sticker isn't part of a sticker set.".into());
+            }
+            functions::get_sticker_set(sticker.set_id, ctx.client_id).await?
+        } else {
+            functions::search_sticker_set(name.into(), ctx.client_id)
+                .await
+                .map_err(|_| "sticker set not found.")?
+        };
+
+        let mut entities = vec![
+            sticker_set.title.clone().bold_owned(),
+            "\nname: ".text(),
+            sticker_set.name.clone().code_owned(),
+            format!("\nstickers: {}", sticker_set.stickers.len()).text_owned(),
+            format!("\ntype: {}", sticker_kind(&sticker_set)).text_owned(),
+        ];
+
+        if sticker_set.is_official {
+            entities.push("\nofficial: yes".text());
+        }
+
+        if let Some(creator) = creator(ctx, sticker_set.id).await {
+            entities.extend(["\ncreator: ".text(), creator.text_owned()]);
+        }
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(
+                KeyboardBuilder::new()
+                    .url_button(
+                        "Open sticker set",
+                        format!("https://t.me/addstickers/{}", sticker_set.name),
+                    )
+                    .build(),
+            ),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn sticker_kind(sticker_set: &StickerSet) -> &'static str {
+    let format = match sticker_set.stickers.first().map(|sticker| &sticker.format) {
+        Some(StickerFormat::Webp) | None => "static",
+        Some(StickerFormat::Tgs) => "animated",
+        Some(StickerFormat::Webm) => "video",
+    };
+
+    match sticker_set.sticker_type {
+        StickerType::Regular => format,
+        StickerType::Mask => "masks",
+        StickerType::CustomEmoji => "custom emoji",
+    }
+}
+
+/// the name of the user who created the set, if Telegram knows them
+async fn creator(ctx: &CommandContext, sticker_set_id: i64) -> Option<String> {
+    // the creator's ID isn't exposed by the API, but is encoded in the upper bits
+    // of the set ID
+    let mut user_id = sticker_set_id >> 32;
+    if (sticker_set_id >> 24) & 0xff != 0 {
+        user_id += 0x1_0000_0000;
+    }
+
+    let enums::User::User(user) = functions::get_user(user_id, ctx.client_id).await.ok()?;
+
+    Some(format!("{} {}", user.first_name, user.last_name).trim_end().to_owned())
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::style_presets::STYLE_PRESETS;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::message_entities::{self, ToEntity};
+
+pub struct Styles;
+
+#[async_trait]
+impl CommandTrait for Styles {
+    fn command_names(&self) -> &[&str] {
+        &["styles"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("list style presets for image generation")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let mut entities = vec!["available styles:".bold()];
+        entities.extend(STYLE_PRESETS.iter().flat_map(|preset| ["\n".text(), preset.name.code()]));
+        entities.push("\n\nuse them like this: ".text());
+        entities.push("/sd --style anime a cat".code());
+
+        ctx.reply_formatted_text(message_entities::formatted_text(entities)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::makersuite::{self, Part};
+use crate::utilities::command_context::{CommandContext, TypingGuard};
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::message_entities::{self, ToEntity};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+#[derive(Clone, Copy, Debug)]
+enum ModelType {
+    StableDiffusion,
+    Sdxl,
+    Waifu,
+}
+
+impl ModelType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sd" => Some(Self::StableDiffusion),
+            "sdxl" => Some(Self::Sdxl),
+            "waifu" => Some(Self::Waifu),
+            _ => None,
+        }
+    }
+
+    const fn description(self) -> &'static str {
+        match self {
+            Self::StableDiffusion => "Stable Diffusion 1.5",
+            Self::Sdxl => "Stable Diffusion XL",
+            Self::Waifu => "Waifu Diffusion, an anime-style Stable Diffusion fine-tune",
+        }
+    }
+}
+
+pub struct SuggestNegative;
+
+#[async_trait]
+impl CommandTrait for SuggestNegative {
+    fn command_names(&self) -> &[&str] {
+        &["neg_suggest", "neg"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("suggest a negative prompt for image generation")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 2, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (model_type, arguments) = match arguments.trim_start().strip_prefix("--model ") {
+            Some(rest) => {
+                let (name, rest) = String::convert(ctx, rest).await?;
+                let model_type = ModelType::from_name(&name)
+                    .ok_or("unknown model type, available types: sd, sdxl, waifu.")?;
+                (model_type, rest)
+            }
+            None => (ModelType::StableDiffusion, arguments.as_str()),
+        };
+        let StringGreedyOrReply(prompt) = ConvertArgument::convert(ctx, arguments).await?.0;
+
+        log::info!("suggesting a negative prompt for {model_type:?}");
+
+        let _typing = TypingGuard(ctx.send_typing_loop());
+
+        let system_instruction = format!(
+            concat!(
+                "Write a negative prompt for {} that avoids artifacts and unwanted elements ",
+                "likely to appear when generating the given image prompt. ",
+                "Return only comma-separated keywords on a single line."
+            ),
+            model_type.description()
+        );
+
+        let response = makersuite::generate_content(
+            ctx.bot_state.http_client.clone(),
+            makersuite::GEMINI_FLASH,
+            &system_instruction,
+            &[Part::Text(prompt)],
+            256,
+        )
+        .await?;
+
+        let Some(negative_prompt) = response.into_text() else {
+            return Err("no negative prompt could be suggested, it was probably blocked.".into());
+        };
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            "negative prompt:\n".bold(),
+            negative_prompt.trim().trim_end_matches('.').code(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use async_trait::async_trait;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConversionError, ConvertArgument, StringGreedyOrReply};
+use crate::utilities::message_entities::{self, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+const MAX_LENGTH: usize = 50_000;
+
+pub struct TextStats;
+
+#[async_trait]
+impl CommandTrait for TextStats {
+    fn command_names(&self) -> &[&str] {
+        &["stats", "wordcount", "wc"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("count words, lines and characters")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 30)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if text.chars().count() > MAX_LENGTH {
+            Err(ConversionError::BadArgument("the text can be at most 50,000 characters long."))?;
+        }
+
+        let words = text.unicode_words().collect::<Vec<_>>();
+        let unique_words =
+            words.iter().map(|word| word.to_lowercase()).collect::<HashSet<_>>().len();
+        let sentences = text
+            .split(['.', '!', '?'])
+            .filter(|sentence| sentence.chars().any(char::is_alphanumeric))
+            .count();
+        let word_chars = words.iter().map(|word| word.chars().count()).sum::<usize>();
+        #[allow(clippy::cast_precision_loss)]
+        let average_word_length =
+            if words.is_empty() { 0. } else { word_chars as f64 / words.len() as f64 };
+
+        let rows = [
+            ("characters", text.graphemes(true).count().to_string()),
+            ("bytes", text.len().to_string()),
+            ("words", words.len().to_string()),
+            ("unique words", unique_words.to_string()),
+            ("sentences", sentences.to_string()),
+            ("lines", text.lines().count().to_string()),
+            ("avg. word length", format!("{average_word_length:.2}")),
+        ];
+
+        let mut table = String::new();
+        for (name, value) in rows {
+            writeln!(table, "{name:<18}{value}").unwrap();
+        }
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![table
+            .trim_end()
+            .to_owned()
+            .pre_owned()]))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use chrono::{NaiveTime, TimeZone, Utc};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+use super::{CommandResult, CommandTrait};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConversionError, ConvertArgument};
+use crate::utilities::telegram_utils::MAX_MESSAGE_LENGTH;
+
+pub struct Timezone;
+
+#[async_trait]
+impl CommandTrait for Timezone {
+    fn command_names(&self) -> &[&str] {
+        &["tz", "tzconvert"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("convert time between timezones")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (time, rest) = String::convert(ctx, &arguments).await?;
+
+        if time.eq_ignore_ascii_case("list") {
+            let region = Option::<String>::convert(ctx, rest).await?.0;
+            let list = list_timezones(region.as_deref());
+
+            if list.chars().count() > MAX_MESSAGE_LENGTH {
+                ctx.send_document(list.replace(", ", "\n").into_bytes(), "timezones.txt", None)
+                    .await?;
+            } else {
+                ctx.reply(list).await?;
+            }
+
+            return Ok(());
+        }
+
+        let time = NaiveTime::parse_from_str(&time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(&time, "%H:%M"))
+            .map_err(|_| {
+                ConversionError::BadArgument("time must be in the HH:MM or HH:MM:SS format.")
+            })?;
+        let (from_tz, rest) = String::convert(ctx, rest).await?;
+        let from_tz = parse_timezone(&from_tz)?;
+        let to_tz = match Option::<String>::convert(ctx, rest).await?.0 {
+            Some(to_tz) => parse_timezone(&to_tz)?,
+            None => Tz::UTC,
+        };
+
+        let date = Utc::now().with_timezone(&from_tz).date_naive();
+        let source = from_tz
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .ok_or("this time doesn't exist in the source timezone today.")?;
+        let target = source.with_timezone(&to_tz);
+
+        ctx.reply(format!(
+            "{} {} = {} {}",
+            source.format("%H:%M:%S"),
+            from_tz.name(),
+            target.format("%H:%M:%S"),
+            to_tz.name()
+        ))
+        .await?;
+
+        Ok(())
+    }
+}
+
+fn parse_timezone(name: &str) -> Result<Tz, ConversionError> {
+    name.parse().map_err(|_| {
+        ConversionError::BadArgument(
+            "unknown timezone name. try /tz list to see the available ones.",
+        )
+    })
+}
+
+fn list_timezones(region: Option<&str>) -> String {
+    let Some(region) = region else {
+        let regions = TZ_VARIANTS
+            .iter()
+            .filter_map(|tz| tz.name().split_once('/').map(|(region, _)| region))
+            .collect::<BTreeSet<_>>();
+
+        return format!(
+            "available regions: {}.\nuse /tz list <region> to see the timezones in a region.",
+            regions.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    };
+
+    let region = region.to_ascii_lowercase();
+    let timezones = TZ_VARIANTS
+        .iter()
+        .map(|tz| tz.name())
+        .filter(|name| name.to_ascii_lowercase().starts_with(&region))
+        .collect::<Vec<_>>();
+
+    if timezones.is_empty() {
+        return "no timezones found in this region.".into();
+    }
+
+    timezones.join(", ")
+}
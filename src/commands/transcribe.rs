@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::openai;
+use crate::utilities::api_key_pool::ApiKeyPool;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, ReplyVoice};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::telegram_utils::MAX_MESSAGE_LENGTH;
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+pub struct Transcribe {
+    api_keys: ApiKeyPool,
+}
+
+impl Transcribe {
+    pub const fn new(api_keys: ApiKeyPool) -> Self {
+        Self { api_keys }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for Transcribe {
+    fn command_names(&self) -> &[&str] {
+        &["transcribe", "stt"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("transcribe a voice message using Whisper")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    /// long voice messages can take a while to transcribe
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(300)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let mut arguments = arguments.as_str();
+        let mut language = None;
+        let mut timestamps = false;
+
+        loop {
+            arguments = arguments.trim_start();
+            if let Some(rest) = arguments
+                .strip_prefix("--timestamps")
+                .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            {
+                timestamps = true;
+                arguments = rest;
+            } else if let Some(rest) = arguments.strip_prefix("--lang ") {
+                let (code, rest) = String::convert(ctx, rest).await?;
+                language = Some(code);
+                arguments = rest;
+            } else {
+                break;
+            }
+        }
+
+        // ISO 639-1 codes like `en` or `pl`
+        if language.as_ref().is_some_and(|language| {
+            language.len() != 2 || !language.chars().all(|char| char.is_ascii_lowercase())
+        }) {
+            Err("invalid language code.")?;
+        }
+
+        let ReplyVoice(audio) = ConvertArgument::convert(ctx, arguments).await?.0;
+
+        ctx.send_typing().await?;
+
+        let transcription = openai::transcription(
+            ctx.bot_state.http_client.clone(),
+            "https://api.openai.com/v1",
+            self.api_keys.next(),
+            audio,
+            language.as_deref(),
+            timestamps,
+        )
+        .await?
+        .map_err(|err| CommandError::Custom(format!("error {}: {}", err.code, err.message)))?;
+
+        let text = match transcription.segments {
+            Some(segments) if timestamps => segments
+                .into_iter()
+                .map(|segment| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let start = segment.start as u64;
+                    format!("[{}:{:02}] {}", start / 60, start % 60, segment.text.trim())
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => transcription.text.trim().to_owned(),
+        };
+
+        if text.is_empty() {
+            Err("no speech was recognized.")?;
+        }
+
+        ctx.reply(text.truncate_with_ellipsis(MAX_MESSAGE_LENGTH)).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::io::Write;
+
+use async_trait::async_trait;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessageVoiceNote};
+use tempfile::NamedTempFile;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::{google_tts, translate};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+const MAX_LENGTH: usize = 500;
+
+pub struct Tts {
+    api_key: String,
+}
+
+impl Tts {
+    pub const fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for Tts {
+    fn command_names(&self) -> &[&str] {
+        &["tts", "say"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("read text aloud using Google Cloud Text-to-Speech")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(text) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        if text.chars().count() > MAX_LENGTH {
+            return Err(format!("the text can be at most {MAX_LENGTH} characters long.").into());
+        }
+
+        ctx.send_typing().await?;
+
+        // the translation endpoint is the cheapest way to detect the language
+        let language = translate::single(ctx.bot_state.http_client.clone(), &text, None, "en")
+            .await?
+            .source_language;
+
+        let audio = google_tts::synthesize(
+            ctx.bot_state.http_client.clone(),
+            &self.api_key,
+            &text,
+            &language,
+        )
+        .await?;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&audio).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessageVoiceNote(InputMessageVoiceNote {
+                    voice_note: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    duration: 0,
+                    waveform: String::new(),
+                    caption: None,
+                    self_destruct_type: None,
+                }),
+                None,
+            )
+            .await?;
+
+        ctx.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        Ok(())
+    }
+}
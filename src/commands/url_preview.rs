@@ -0,0 +1,102 @@
+use std::io::Write;
+
+use async_trait::async_trait;
+use reqwest::Url;
+use tdlib::enums::{InputFile, InputMessageContent};
+use tdlib::types::{InputFileLocal, InputMessagePhoto};
+use tempfile::NamedTempFile;
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::open_graph;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::ConvertArgument;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned, ToNestedEntity};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+pub struct UrlPreview;
+
+#[async_trait]
+impl CommandTrait for UrlPreview {
+    fn command_names(&self) -> &[&str] {
+        &["preview", "og"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show the Open Graph preview of a webpage")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let (url, _) = Url::convert(ctx, &arguments).await?;
+
+        ctx.send_typing().await?;
+
+        let http_client = &ctx.bot_state.public_http_client;
+        let open_graph = open_graph::fetch(http_client, url.clone()).await?;
+
+        let mut entities = vec![open_graph
+            .title
+            .unwrap_or_else(|| url.to_string())
+            .truncate_with_ellipsis(256)
+            .text_url_owned(url.to_string())
+            .bold()];
+
+        if let Some(description) = open_graph.description {
+            entities.extend(["\n".text(), description.truncate_with_ellipsis(512).text_owned()]);
+        }
+
+        if let Some(site_name) = open_graph.site_name {
+            entities.extend(["\n\n".text(), site_name.truncate_with_ellipsis(64).italic_owned()]);
+        }
+
+        let caption = message_entities::formatted_text(entities);
+
+        let image = match open_graph.image {
+            Some(image_url) => match open_graph::get(http_client, image_url).await {
+                Ok(response) => open_graph::read_limited(response).await.ok(),
+                Err(_) => None,
+            },
+            None => None,
+        };
+
+        let Some(image) = image else {
+            ctx.reply_formatted_text(caption).await?;
+            return Ok(());
+        };
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&image).unwrap();
+
+        let message = ctx
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: 0,
+                    height: 0,
+                    caption: Some(caption.clone()),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                None,
+            )
+            .await?;
+
+        // the image can be invalid or too large for Telegram
+        let result = ctx.bot_state.message_queue.wait_for_message(message.id).await;
+        temp_file.close().unwrap();
+
+        if result.is_err() {
+            ctx.reply_formatted_text(caption).await?;
+        }
+
+        Ok(())
+    }
+}
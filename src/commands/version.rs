@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+use super::{CommandResult, CommandTrait, Privilege};
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+
+pub struct Version;
+
+#[async_trait]
+impl CommandTrait for Version {
+    fn command_names(&self) -> &[&str] {
+        &["version", "ver", "about"]
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        Privilege::Owner
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _: String) -> CommandResult {
+        let build_time =
+            OffsetDateTime::from_unix_timestamp(env!("BUILD_TIMESTAMP").parse().unwrap())
+                .unwrap()
+                .format(format_description!("[year]-[month]-[day] [hour]:[minute]:[second] UTC"))
+                .unwrap();
+
+        ctx.reply_formatted_text(message_entities::formatted_text(vec![
+            concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).bold(),
+            "\ncommit: ".text(),
+            env!("BUILD_GIT_HASH").code(),
+            "\nbuilt: ".text(),
+            build_time.text_owned(),
+            "\ncompiler: ".text(),
+            env!("BUILD_RUSTC_VERSION").text(),
+        ]))
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use tdlib::enums::{InputMessageContent, ReplyMarkup};
+use tdlib::types::{FormattedText, InputMessageText, LinkPreviewOptions};
+
+use super::{CommandError, CommandResult, CommandTrait};
+use crate::apis::wikipedia::{self, Summary};
+use crate::utilities::bot_state::BotState;
+use crate::utilities::callback_dispatcher::CallbackContext;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::TruncateWithEllipsis;
+
+const MAX_SEARCH_RESULTS: usize = 5;
+const MAX_EXTRACT_LENGTH: usize = 1024;
+
+pub struct Wikipedia;
+
+#[async_trait]
+impl CommandTrait for Wikipedia {
+    fn command_names(&self) -> &[&str] {
+        &["wikipedia", "wiki"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("look up an article on Wikipedia")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(title) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let title = title.trim();
+
+        ctx.send_typing().await?;
+
+        let summary = summary(&ctx.bot_state, title).await?;
+
+        let (text, reply_markup) = if summary.is_disambiguation() {
+            let results = wikipedia::search(
+                ctx.bot_state.http_client.clone(),
+                title.trim_end_matches("(disambiguation)").trim_end(),
+                MAX_SEARCH_RESULTS,
+            )
+            .await?;
+
+            if results.is_empty() {
+                Err(format!("“{title}” is ambiguous and no articles matched it."))?;
+            }
+
+            let mut entities = vec![format!("“{title}” may refer to:").bold_owned()];
+            let mut keyboard = KeyboardBuilder::new();
+            {
+                let mut cache = ctx.bot_state.cache.lock().unwrap();
+                for (i, result) in results.into_iter().enumerate() {
+                    entities.push(format!("\n{}. ", i + 1).text_owned());
+                    entities.push(result.title.clone().text_owned());
+                    if let Some(description) = result.description {
+                        entities.push(format!(" – {description}").text_owned());
+                    }
+
+                    keyboard = keyboard
+                        .row()
+                        .callback_button(result.title.clone(), &format!("wikipedia:{}", result.id));
+                    cache.set_wikipedia_title(result.id, result.title);
+                }
+            }
+
+            (message_entities::formatted_text(entities), keyboard.build())
+        } else {
+            (article_text(&summary), article_markup(&summary))
+        };
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text,
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(reply_markup),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+pub async fn callback(ctx: &CallbackContext, arguments: &str) -> CommandResult {
+    let Some(title) = arguments
+        .parse()
+        .ok()
+        .and_then(|page_id| ctx.bot_state.cache.lock().unwrap().get_wikipedia_title(page_id))
+    else {
+        ctx.answer("this search result has expired, search again.", false).await?;
+        return Ok(());
+    };
+
+    let summary = summary(&ctx.bot_state, &title).await?;
+    ctx.edit_message(article_text(&summary), Some(article_markup(&summary))).await?;
+    ctx.answer("", false).await?;
+
+    Ok(())
+}
+
+async fn summary(bot_state: &BotState, title: &str) -> Result<Summary, CommandError> {
+    let cached_summary = bot_state.cache.lock().unwrap().get_wikipedia_summary(title);
+    if let Some(summary) = cached_summary {
+        return Ok(summary);
+    }
+
+    let summary = wikipedia::summary(bot_state.http_client.clone(), title).await?;
+    bot_state.cache.lock().unwrap().set_wikipedia_summary(title.to_owned(), summary.clone());
+
+    Ok(summary)
+}
+
+fn article_text(summary: &Summary) -> FormattedText {
+    let mut entities = vec![summary.title.clone().bold_owned()];
+
+    if let Some(description) = &summary.description {
+        entities.extend(["\n".text(), description.clone().italic_owned()]);
+    }
+
+    entities.extend([
+        "\n\n".text(),
+        summary.extract.clone().truncate_at_word_boundary(MAX_EXTRACT_LENGTH).text_owned(),
+    ]);
+
+    message_entities::formatted_text(entities)
+}
+
+fn article_markup(summary: &Summary) -> ReplyMarkup {
+    KeyboardBuilder::new()
+        .url_button("read on Wikipedia", summary.content_urls.desktop.page.clone())
+        .build()
+}
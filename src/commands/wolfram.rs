@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use reqwest::Url;
+use tdlib::enums::InputMessageContent;
+use tdlib::types::{FormattedText, InputMessageText, LinkPreviewOptions};
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::wolfram;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedy};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+
+pub struct Wolfram {
+    app_id: String,
+}
+
+impl Wolfram {
+    pub const fn new(app_id: String) -> Self {
+        Self { app_id }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for Wolfram {
+    fn command_names(&self) -> &[&str] {
+        &["wolfram", "wa"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("ask Wolfram Alpha a question")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 3, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedy(query) = ConvertArgument::convert(ctx, &arguments).await?.0;
+
+        ctx.send_typing().await?;
+
+        let answer = wolfram::short_answer(ctx.bot_state.http_client.clone(), &self.app_id, &query)
+            .await?
+            .ok_or("Wolfram Alpha couldn't answer this query.")?;
+
+        let url =
+            Url::parse_with_params("https://www.wolframalpha.com/input", [("i", &query)]).unwrap();
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: FormattedText { text: answer, ..Default::default() },
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(KeyboardBuilder::new().url_button("powered by Wolfram Alpha", url).build()),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use reqwest::Url;
+use tdlib::enums::InputMessageContent;
+use tdlib::types::{InputMessageText, LinkPreviewOptions};
+
+use super::{CommandResult, CommandTrait};
+use crate::apis::youtube;
+use crate::utilities::command_context::CommandContext;
+use crate::utilities::convert_argument::{ConvertArgument, StringGreedyOrReply};
+use crate::utilities::keyboard::KeyboardBuilder;
+use crate::utilities::message_entities::{self, ToEntity, ToEntityOwned};
+use crate::utilities::rate_limit::{rate_limit_from_env, RateLimiter};
+use crate::utilities::text_utils::{self, TruncateWithEllipsis};
+
+const MAX_DESCRIPTION_LENGTH: usize = 200;
+
+pub struct YouTube {
+    api_key: String,
+}
+
+impl YouTube {
+    pub const fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+#[async_trait]
+impl CommandTrait for YouTube {
+    fn command_names(&self) -> &[&str] {
+        &["youtube", "yt"]
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        Some("show information about a YouTube video")
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        rate_limit_from_env(self.name(), 5, 60)
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let StringGreedyOrReply(video) = ConvertArgument::convert(ctx, &arguments).await?.0;
+        let id = video_id(&video).ok_or("send a YouTube link or an 11 character video ID.")?;
+
+        ctx.send_typing().await?;
+
+        let video = youtube::video(ctx.bot_state.http_client.clone(), &self.api_key, &id)
+            .await?
+            .ok_or("video not found.")?;
+
+        let mut entities = vec![
+            video.snippet.title.bold_owned(),
+            "\nchannel: ".text(),
+            video.snippet.channel_title.text_owned(),
+        ];
+
+        if let Some(seconds) = video.content_details.seconds().filter(|seconds| *seconds > 0) {
+            entities
+                .push(format!("\nduration: {}", text_utils::format_duration(seconds)).text_owned());
+        }
+
+        if let Some(view_count) = video.statistics.view_count {
+            entities.push(format!("\nviews: {view_count}").text_owned());
+        }
+
+        if let Some(like_count) = video.statistics.like_count {
+            entities.push(format!("\nlikes: {like_count}").text_owned());
+        }
+
+        let description = video.snippet.description.trim();
+        if !description.is_empty() {
+            entities.extend([
+                "\n\n".text(),
+                description
+                    .to_owned()
+                    .truncate_at_word_boundary(MAX_DESCRIPTION_LENGTH)
+                    .text_owned(),
+            ]);
+        }
+
+        ctx.reply_custom(
+            InputMessageContent::InputMessageText(InputMessageText {
+                text: message_entities::formatted_text(entities),
+                link_preview_options: Some(LinkPreviewOptions {
+                    is_disabled: true,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Some(
+                KeyboardBuilder::new()
+                    .url_button(
+                        "▶ Watch on YouTube",
+                        format!("https://www.youtube.com/watch?v={}", video.id),
+                    )
+                    .build(),
+            ),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// accepts a bare video ID or `youtu.be`, `youtube.com/watch`,
+/// `youtube.com/shorts`, `youtube.com/embed` and `youtube.com/live` links
+fn video_id(video: &str) -> Option<String> {
+    let video = video.trim();
+
+    if is_video_id(video) {
+        return Some(video.into());
+    }
+
+    let url = if video.contains("://") {
+        Url::parse(video)
+    } else {
+        Url::parse(&format!("https://{video}"))
+    }
+    .ok()?;
+
+    let host = url.host_str()?;
+    let host = host.strip_prefix("www.").or_else(|| host.strip_prefix("m.")).unwrap_or(host);
+    let mut path = url.path_segments()?;
+
+    let id = match host {
+        "youtu.be" => path.next()?.to_owned(),
+        "youtube.com" | "music.youtube.com" => match path.next()? {
+            "watch" => url.query_pairs().find(|(key, _)| key == "v")?.1.into_owned(),
+            "shorts" | "embed" | "live" => path.next()?.to_owned(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    is_video_id(&id).then_some(id)
+}
+
+fn is_video_id(id: &str) -> bool {
+    id.len() == 11
+        && id.chars().all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
@@ -0,0 +1,143 @@
+// the library only exists for the binary and the integration tests, so the
+// lints for public APIs don't apply
+#![allow(clippy::must_use_candidate, clippy::return_self_not_must_use, clippy::missing_errors_doc)]
+
+use std::env;
+use std::sync::LazyLock;
+
+use bot::Bot;
+use utilities::ab_test::AbTest;
+use utilities::api_key_pool::ApiKeyPool;
+use utilities::logchamp;
+use utilities::prompt_policy::PROMPT_POLICY;
+
+mod apis;
+mod bot;
+mod commands;
+mod style_presets;
+mod utilities;
+
+/// registers the commands and runs the bot until it's closed
+#[allow(clippy::too_many_lines)] // one line per registered command
+pub fn run() {
+    logchamp::init();
+    dotenvy::dotenv().ok();
+    // fail on an invalid policy file now instead of on the first prompt
+    LazyLock::force(&PROMPT_POLICY);
+
+    let mut bot = Bot::new();
+
+    bot.add_command(commands::start::Start);
+    bot.add_command(commands::craiyon::Generate);
+    bot.add_command(commands::craiyon::Craiyon::art());
+    bot.add_command(commands::craiyon::Craiyon::drawing());
+    bot.add_command(commands::craiyon::Craiyon::photo());
+    bot.add_command(commands::craiyon::Craiyon::none());
+    bot.add_command(commands::craiyon_compare::CraiyonCompare);
+    bot.add_command(commands::craiyon_search::CraiyonSearch);
+    if let Some(api_key) = env::var("IMAGE_SEARCH_API_KEY").ok().filter(|key| !key.is_empty()) {
+        bot.add_command(commands::image_search::ImageSearch::new(api_key));
+    }
+    bot.add_command(commands::stablehorde::StableHorde::stable_diffusion());
+    bot.add_command(commands::stablehorde::StableHorde::stable_diffusion_2());
+    bot.add_command(commands::stablehorde::StableHorde::waifu_diffusion());
+    bot.add_command(commands::stablehorde::StableHorde::furry_diffusion());
+    bot.add_command(commands::compare_models::CompareModels);
+    if let Some(base_url) = env::var("A1111_BASE_URL").ok().filter(|url| !url.is_empty()) {
+        bot.add_command(commands::a1111::A1111::new(base_url));
+    }
+    bot.add_command(commands::styles::Styles);
+    bot.add_command(commands::markov_chain::MarkovChain);
+    bot.add_command(commands::config::Config);
+    bot.add_command(commands::chat_commands::ChatCommands::disable());
+    bot.add_command(commands::chat_commands::ChatCommands::enable());
+    bot.add_command(commands::different_dimension_me::DifferentDimensionMe);
+    bot.add_command(commands::ocr::Ocr);
+    bot.add_command(commands::make_sticker::MakeSticker);
+    bot.add_command(commands::sticker_info::StickerInfo);
+    bot.add_command(commands::palette::Palette);
+    bot.add_command(AbTest::new(
+        commands::makersuite::GoogleGemini::plain(),
+        commands::makersuite::GoogleGemini::concise(),
+        "gemini concise prompt",
+    ));
+    bot.add_command(commands::makersuite::GooglePalm);
+    bot.add_command(commands::groq::Llama);
+    bot.add_command(commands::ai_settings::AiSet);
+    bot.add_command(commands::enhance_prompt::EnhancePrompt);
+    bot.add_command(commands::suggest_negative::SuggestNegative);
+    if let Some(api_keys) = ApiKeyPool::from_env("OPENAI_API_KEYS", "OPENAI_API_KEY") {
+        bot.add_command(commands::transcribe::Transcribe::new(api_keys));
+    }
+    bot.add_command(commands::translate::Translate);
+    bot.add_command(commands::badtranslate::BadTranslate);
+    bot.add_command(commands::trollslate::Trollslate);
+    if let Some(api_key) = env::var("GOOGLE_CLOUD_API_KEY").ok().filter(|key| !key.is_empty()) {
+        bot.add_command(commands::tts::Tts::new(api_key));
+    }
+    bot.add_command(commands::urbandictionary::UrbanDictionary);
+    bot.add_command(commands::wikipedia::Wikipedia);
+    if let Some(app_id) = env::var("WOLFRAM_ALPHA_APP_ID").ok().filter(|id| !id.is_empty()) {
+        bot.add_command(commands::wolfram::Wolfram::new(app_id));
+    }
+    bot.add_command(commands::screenshot::Screenshot);
+    bot.add_command(commands::url_preview::UrlPreview);
+    bot.add_command(commands::http_check::HttpCheck);
+    bot.add_command(commands::dns::Dns);
+    bot.add_command(commands::ipinfo::IpInfo);
+    bot.add_command(commands::github::GitHub);
+    bot.add_command(commands::cratesio::CratesIo);
+    if let Some(api_key) = env::var("YOUTUBE_API_KEY").ok().filter(|key| !key.is_empty()) {
+        bot.add_command(commands::youtube::YouTube::new(api_key));
+    }
+    if let (Some(client_id), Some(client_secret)) = (
+        env::var("SPOTIFY_CLIENT_ID").ok().filter(|id| !id.is_empty()),
+        env::var("SPOTIFY_CLIENT_SECRET").ok().filter(|secret| !secret.is_empty()),
+    ) {
+        bot.add_command(commands::spotify::Spotify::new(client_id, client_secret));
+    }
+    bot.add_command(commands::cobalt_download::CobaltDownload::auto());
+    bot.add_command(commands::cobalt_download::CobaltDownload::audio());
+    bot.add_command(commands::charinfo::CharInfo);
+    bot.add_command(commands::normalize::Normalize);
+    bot.add_command(commands::textstats::TextStats);
+    bot.add_command(commands::json_pretty::JsonPretty);
+    bot.add_command(commands::csv_table::CsvTable);
+    bot.add_command(commands::regex_test::RegexTest);
+    bot.add_command(commands::timezone::Timezone);
+    bot.add_command(commands::countdown::Countdown);
+    bot.add_command(commands::remind::Remind);
+    bot.add_command(commands::feedback::Feedback);
+    bot.add_command(commands::radio_poligon::RadioPoligon);
+    bot.add_command(commands::autocomplete::Autocomplete);
+    bot.add_command(commands::mevo::Mevo);
+    bot.add_command(commands::kiwifarms::KiwiFarms);
+    bot.add_command(commands::startit_joke::StartitJoke);
+    bot.add_command(commands::moveit_joke::MoveitJoke);
+    bot.add_command(commands::kebab::Kebab);
+    bot.add_command(commands::ping::Ping);
+    bot.add_command(commands::version::Version);
+    bot.add_command(commands::leaderboard::Leaderboard::default());
+    bot.add_command(commands::profile::Profile);
+    bot.add_command(commands::history::History);
+    bot.add_command(commands::dashboard::Dashboard);
+    bot.add_command(commands::maintenance::Maintenance);
+    bot.add_command(commands::health::Health);
+    bot.add_command(commands::delete::Delete);
+    bot.add_command(commands::blacklist::Blacklist);
+    bot.add_command(commands::sex::Sex);
+
+    bot.run();
+    log::logger().flush();
+}
+
+/// what the integration tests in `tests/integration` use, as the modules
+/// themselves are private
+#[doc(hidden)]
+pub mod testing {
+    pub use crate::apis::stablehorde::HordeConfig;
+    pub use crate::commands::charinfo::charinfo;
+    pub use crate::commands::ping::ping_text;
+    pub use crate::commands::stablehorde::StableHorde;
+    pub use crate::utilities::test_fixtures::CommandContextBuilder;
+}
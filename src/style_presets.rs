@@ -0,0 +1,74 @@
+pub struct StylePreset {
+    pub name: &'static str,
+    pub positive_suffix: &'static str,
+    pub negative_prefix: &'static str,
+}
+
+pub static STYLE_PRESETS: &[StylePreset] = &[
+    StylePreset {
+        name: "photorealistic",
+        positive_suffix: "photorealistic, 35mm photograph, natural lighting, highly detailed",
+        negative_prefix: "painting, drawing, illustration, cartoon, 3d render",
+    },
+    StylePreset {
+        name: "anime",
+        positive_suffix: "anime style, cel shading, vibrant colors, detailed line art",
+        negative_prefix: "photo, realistic, 3d render",
+    },
+    StylePreset {
+        name: "oil_painting",
+        positive_suffix: "oil painting, visible brush strokes, canvas texture, fine art",
+        negative_prefix: "photo, digital art, 3d render",
+    },
+    StylePreset {
+        name: "pixel_art",
+        positive_suffix: "pixel art, 16-bit, limited color palette, sprite",
+        negative_prefix: "blurry, smooth shading, photo",
+    },
+    StylePreset {
+        name: "watercolor",
+        positive_suffix: "watercolor painting, soft edges, paper texture, pastel colors",
+        negative_prefix: "photo, sharp edges, 3d render",
+    },
+];
+
+impl StylePreset {
+    pub fn find(name: &str) -> Option<&'static Self> {
+        STYLE_PRESETS.iter().find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn apply(&self, prompt: &str) -> String {
+        format!("{prompt}, {}", self.positive_suffix)
+    }
+
+    pub fn apply_negative(&self, negative_prompt: &str) -> String {
+        if negative_prompt.is_empty() {
+            self.negative_prefix.into()
+        } else {
+            format!("{}, {negative_prompt}", self.negative_prefix)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(StylePreset::find("anime").unwrap().name, "anime");
+        assert_eq!(StylePreset::find("Pixel_Art").unwrap().name, "pixel_art");
+        assert!(StylePreset::find("cubism").is_none());
+    }
+
+    #[test]
+    fn test_apply() {
+        let preset = StylePreset::find("pixel_art").unwrap();
+        assert_eq!(
+            preset.apply("a cat"),
+            "a cat, pixel art, 16-bit, limited color palette, sprite"
+        );
+        assert_eq!(preset.apply_negative(""), "blurry, smooth shading, photo");
+        assert_eq!(preset.apply_negative("text"), "blurry, smooth shading, photo, text");
+    }
+}
@@ -1,23 +1,38 @@
+pub mod ab_test;
+pub mod api_key_pool;
 pub mod api_utils;
 pub mod bot_state;
 pub mod cache;
+pub mod callback_dispatcher;
 pub mod command_context;
 pub mod command_dispatcher;
 pub mod command_manager;
 pub mod config;
 pub mod convert_argument;
+pub mod database;
 pub mod file_download;
 pub mod google_translate;
+pub mod health_server;
 pub mod image_utils;
+pub mod keyboard;
+pub mod last_messages;
 pub mod logchamp;
+pub mod lru_cache;
 pub mod markov_chain_manager;
 pub mod message_entities;
 pub mod message_filters;
 pub mod message_queue;
+pub mod net_utils;
+pub mod paginator;
 pub mod parsed_command;
+pub mod prompt_policy;
 pub mod rate_limit;
+pub mod telegram_rate_limiter;
 pub mod telegram_utils;
+pub mod temp_file_pool;
 pub mod text_utils;
+pub mod timing_history;
+pub mod vote_handler;
+pub mod watchdog;
 
-#[cfg(test)]
 pub mod test_fixtures;
@@ -0,0 +1,96 @@
+use std::env;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::command_context::CommandContext;
+use super::database::AbTestAssignment;
+use super::rate_limit::RateLimiter;
+use crate::commands::{CommandResult, CommandTrait, Privilege};
+
+/// splits users between two variants of a command by the parity of their ID,
+/// setting `AB_TESTING_ENABLED=0` routes everyone to variant `a`
+pub struct AbTest<A, B> {
+    a: A,
+    b: B,
+    /// identifies the test in the database
+    description: &'static str,
+    feature_enabled: bool,
+}
+
+impl<A: CommandTrait, B: CommandTrait> AbTest<A, B> {
+    pub fn new(a: A, b: B, description: &'static str) -> Self {
+        let feature_enabled = env::var("AB_TESTING_ENABLED").map_or(true, |value| value != "0");
+        Self { a, b, description, feature_enabled }
+    }
+
+    const fn variant(&self, user_id: i64) -> &'static str {
+        if self.feature_enabled && user_id.rem_euclid(2) == 1 {
+            "b"
+        } else {
+            "a"
+        }
+    }
+}
+
+#[async_trait]
+impl<A, B> CommandTrait for AbTest<A, B>
+where
+    A: CommandTrait + Send + Sync,
+    B: CommandTrait + Send + Sync,
+{
+    fn command_names(&self) -> &[&str] {
+        self.a.command_names()
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        self.a.description()
+    }
+
+    fn rate_limit(&self) -> RateLimiter<i64> {
+        self.a.rate_limit()
+    }
+
+    fn minimum_privilege(&self) -> Privilege {
+        self.a.minimum_privilege()
+    }
+
+    fn hidden(&self) -> bool {
+        self.a.hidden()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.a.timeout()
+    }
+
+    async fn execute(&self, ctx: &CommandContext, arguments: String) -> CommandResult {
+        let variant = self.variant(ctx.user.id);
+
+        if self.feature_enabled {
+            let mut database = ctx.bot_state.database.lock().unwrap();
+            let assigned = database.ab_test_assignments.iter().any(|assignment| {
+                assignment.test == self.description && assignment.user_id == ctx.user.id
+            });
+
+            if !assigned {
+                log::info!(
+                    "assigned {} to variant {variant} of {:?}",
+                    ctx.user.id,
+                    self.description
+                );
+                database.ab_test_assignments.push(AbTestAssignment {
+                    test: self.description.into(),
+                    user_id: ctx.user.id,
+                    variant: variant.into(),
+                    date: ctx.message.date,
+                });
+            }
+        }
+
+        if variant == "b" {
+            self.b.execute(ctx, arguments).await
+        } else {
+            self.a.execute(ctx, arguments).await
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// hands out API keys round-robin to spread requests across their quotas
+pub struct ApiKeyPool {
+    keys: Vec<String>,
+    index: AtomicUsize,
+}
+
+impl ApiKeyPool {
+    fn new(keys: Vec<String>) -> Option<Self> {
+        (!keys.is_empty()).then_some(Self { keys, index: AtomicUsize::new(0) })
+    }
+
+    /// reads comma-separated keys from `list_var`, falling back to the single
+    /// key in `single_var`, `None` if neither is set
+    pub fn from_env(list_var: &str, single_var: &str) -> Option<Self> {
+        Self::from_values(env::var(list_var).ok(), env::var(single_var).ok())
+    }
+
+    fn from_values(list: Option<String>, single: Option<String>) -> Option<Self> {
+        let keys = list.filter(|keys| !keys.trim().is_empty()).or(single).unwrap_or_default();
+
+        Self::new(
+            keys.split(',').map(str::trim).filter(|key| !key.is_empty()).map(Into::into).collect(),
+        )
+    }
+
+    pub fn next(&self) -> &str {
+        &self.keys[self.index.fetch_add(1, Ordering::Relaxed) % self.keys.len()]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ApiKeyPool;
+
+    #[test]
+    fn test_api_key_pool() {
+        let pool = ApiKeyPool::from_values(Some("first, second,,third".into()), None).unwrap();
+        let keys = (0..4).map(|_| pool.next()).collect::<Vec<_>>();
+        assert_eq!(keys, ["first", "second", "third", "first"]);
+
+        let pool = ApiKeyPool::from_values(Some(" ".into()), Some("single".into())).unwrap();
+        assert_eq!(pool.next(), "single");
+
+        assert!(ApiKeyPool::from_values(None, None).is_none());
+    }
+}
@@ -4,6 +4,9 @@ use reqwest::{Response, StatusCode, Url};
 
 const CLOUDFLARE_STORAGE: &str = "r2.cloudflarestorage.com";
 
+/// for APIs which require identifying the client
+pub const BOT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
 pub struct ServerError(pub StatusCode);
 
 pub trait DetectServerError {
@@ -1,20 +1,33 @@
+use std::collections::HashMap;
 use std::env;
-use std::sync::Mutex;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use markov_chain::MarkovChain;
-use reqwest::{redirect, Client};
-use tdlib::enums::{ChatMember, ChatMemberStatus, MessageSender};
+use reqwest::{redirect, Client, ClientBuilder};
+use tdlib::enums::{self, MessageSender};
 use tdlib::functions;
-use tdlib::types::MessageSenderUser;
+use tdlib::types::{ChatMember, FormattedText, MessageSenderUser};
+use time::OffsetDateTime;
 
 use super::cache::Cache;
 use super::config::Config;
+use super::database::Database;
+use super::last_messages::LastMessages;
+use super::lru_cache::LruCache;
 use super::markov_chain_manager;
 use super::message_queue::MessageQueue;
+use super::net_utils::PublicResolver;
 use super::rate_limit::{RateLimiter, RateLimits};
+use super::telegram_rate_limiter::TelegramRateLimiter;
+use super::temp_file_pool::TempFilePool;
+use super::timing_history::TimingHistory;
+use crate::apis::stablehorde::HordeConfig;
 use crate::bot::TdResult;
 
+const TEMP_FILE_POOL_SIZE: usize = 16;
+
 #[derive(Clone, Copy)]
 pub enum BotStatus {
     Running,
@@ -23,62 +36,133 @@ pub enum BotStatus {
     Closed,
 }
 
+/// model, prompt and size of a Stable Horde generation
+pub type PromptCacheKey = (String, String, (u32, u32));
+
+/// downloaded Stable Horde images by model, prompt and size
+pub type PromptCache = LruCache<PromptCacheKey, Vec<Vec<u8>>>;
+
+pub struct Maintenance {
+    pub reason: Option<String>,
+    pub since: Instant,
+}
+
 pub struct BotState {
     pub status: Mutex<BotStatus>,
+    pub start_time: Instant,
+    /// Unix timestamp of the last update received from Telegram
+    pub last_update: AtomicU64,
+    pub maintenance: Mutex<Option<Maintenance>>,
     pub config: Mutex<Config>,
+    pub database: Mutex<Database>,
     pub cache: Mutex<Cache>,
+    pub prompt_cache: Mutex<PromptCache>,
+    /// prompt cache keys by the hash of the collage sent for them, so down
+    /// votes can evict bad results
+    pub prompt_cache_keys: Mutex<LruCache<String, PromptCacheKey>>,
+    /// finished inline Stable Horde generations by prompt hash
+    pub inline_generations: Mutex<LruCache<String, FormattedText>>,
+    /// inline messages to edit once a running generation finishes by prompt
+    /// hash, kept however long the generation takes
+    pub pending_inline_generations: Mutex<HashMap<String, Vec<String>>>,
+    pub timing_history: Mutex<TimingHistory>,
     pub http_client: Client,
+    /// for user-supplied URLs, only connects to public addresses
+    pub public_http_client: Client,
+    pub stablehorde: HordeConfig,
     pub message_queue: MessageQueue,
+    pub telegram_rate_limiter: TelegramRateLimiter,
+    pub last_messages: LastMessages,
+    pub temp_files: TempFilePool,
     pub rate_limits: Mutex<RateLimits>,
     pub markov_chain: Mutex<MarkovChain>,
 }
 
 impl BotState {
     pub fn new() -> Self {
-        let mut http_client = Client::builder();
-
-        if let Ok(user_agent) = env::var("USER_AGENT") {
-            http_client = http_client.user_agent(user_agent);
-        }
-
         Self {
             status: Mutex::new(BotStatus::Closed),
+            start_time: Instant::now(),
+            last_update: AtomicU64::new(unix_now()),
+            maintenance: Mutex::new(None),
             config: Mutex::new(Config::load().unwrap()),
+            database: Mutex::new(Database::load().unwrap()),
             cache: Mutex::new(Cache::default()),
-            http_client: http_client
-                .redirect(redirect::Policy::none())
-                .timeout(Duration::from_secs(300))
+            prompt_cache: Mutex::new(LruCache::new(100, Duration::from_secs(10 * 60))),
+            prompt_cache_keys: Mutex::new(LruCache::new(100, Duration::from_secs(10 * 60))),
+            inline_generations: Mutex::new(LruCache::new(100, Duration::from_secs(5 * 60))),
+            pending_inline_generations: Mutex::new(HashMap::new()),
+            timing_history: Mutex::new(TimingHistory::default()),
+            http_client: client_builder().build().unwrap(),
+            public_http_client: client_builder()
+                .no_proxy()
+                .dns_resolver(Arc::new(PublicResolver))
                 .build()
                 .unwrap(),
-            rate_limits: Mutex::new(RateLimits { rate_limit_exceeded: RateLimiter::new(1, 20) }),
+            stablehorde: HordeConfig::from_env(),
+            rate_limits: Mutex::new(RateLimits {
+                rate_limit_exceeded: RateLimiter::new(1, 20),
+                access_denied: RateLimiter::new(1, 60),
+                history_rerun: RateLimiter::new(1, 60),
+                inline_generation: RateLimiter::new(3, 300),
+                cooldowns: HashMap::new(),
+            }),
             message_queue: MessageQueue::default(),
+            telegram_rate_limiter: TelegramRateLimiter::default(),
+            last_messages: LastMessages::default(),
+            temp_files: TempFilePool::new(
+                env::var("TEMP_FILE_POOL_SIZE")
+                    .ok()
+                    .and_then(|size| size.parse().ok())
+                    .unwrap_or(TEMP_FILE_POOL_SIZE),
+            ),
             markov_chain: Mutex::new(markov_chain_manager::load().unwrap()),
         }
     }
 
-    pub async fn get_member_status(
+    pub fn record_update(&self) {
+        self.last_update.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// seconds since the last update was received
+    pub fn last_update_age(&self) -> u64 {
+        unix_now().saturating_sub(self.last_update.load(Ordering::Relaxed))
+    }
+
+    pub async fn get_chat_member(
         &self,
         chat_id: i64,
         member_id: i64,
         client_id: i32,
-    ) -> TdResult<ChatMemberStatus> {
-        if let Some(status) = self.cache.lock().unwrap().get_member_status(chat_id, member_id) {
-            return Ok(status);
+    ) -> TdResult<ChatMember> {
+        if let Some(chat_member) = self.cache.lock().unwrap().get_chat_member(chat_id, member_id) {
+            return Ok(chat_member);
         }
 
-        let ChatMember::ChatMember(chat_member) = functions::get_chat_member(
+        let enums::ChatMember::ChatMember(chat_member) = functions::get_chat_member(
             chat_id,
             MessageSender::User(MessageSenderUser { user_id: member_id }),
             client_id,
         )
         .await?;
 
-        self.cache.lock().unwrap().set_member_status(
-            chat_id,
-            member_id,
-            chat_member.status.clone(),
-        );
+        self.cache.lock().unwrap().set_chat_member(chat_id, member_id, chat_member.clone());
+
+        Ok(chat_member)
+    }
+}
+
+fn unix_now() -> u64 {
+    OffsetDateTime::now_utc().unix_timestamp().unsigned_abs()
+}
 
-        Ok(chat_member.status)
+fn client_builder() -> ClientBuilder {
+    let mut http_client =
+        Client::builder().redirect(redirect::Policy::none()).timeout(Duration::from_secs(300));
+
+    if let Ok(user_agent) = env::var("USER_AGENT") {
+        http_client = http_client.user_agent(user_agent);
     }
+
+    http_client
 }
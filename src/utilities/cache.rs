@@ -1,19 +1,31 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, Instant};
 
-use tdlib::enums::{ChatMemberStatus, ChatType, MessageSender, UserType};
+use tdlib::enums::{ChatType, MessageSender, UserType};
 use tdlib::types::{
-    Chat, ChatPermissions, MessageSenderUser, UpdateChatMember, UpdateChatPermissions,
+    Chat, ChatMember, ChatPermissions, MessageSenderUser, UpdateChatMember, UpdateChatPermissions,
     UpdateChatTitle, UpdateNewChat, UpdateUser, User,
 };
 
 use super::telegram_utils::MainUsername;
+use crate::apis::wikipedia::Summary;
+
+const CHAT_MEMBER_TTL: Duration = Duration::from_secs(30);
+const PAGES_TTL: Duration = Duration::from_secs(60 * 60);
+const WIKIPEDIA_TTL: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Default)]
 pub struct Cache {
     chats: HashMap<i64, CompactChat>,
     users: HashMap<i64, CompactUser>,
-    member_status: HashMap<(i64, i64), ChatMemberStatus>,
+    chat_members: HashMap<(i64, i64), (ChatMember, Instant)>,
+    /// paginated messages by the hash of their first page
+    pages: HashMap<String, (Vec<String>, Instant)>,
+    /// Wikipedia article summaries by title
+    wikipedia_summaries: HashMap<String, (Summary, Instant)>,
+    /// titles of Wikipedia search results by page ID, for callback buttons
+    wikipedia_titles: HashMap<u64, (String, Instant)>,
 }
 
 impl Cache {
@@ -25,12 +37,51 @@ impl Cache {
         self.users.get(&id).cloned()
     }
 
-    pub fn get_member_status(&self, chat_id: i64, member_id: i64) -> Option<ChatMemberStatus> {
-        self.member_status.get(&(chat_id, member_id)).cloned()
+    pub fn get_chat_member(&self, chat_id: i64, member_id: i64) -> Option<ChatMember> {
+        self.chat_members
+            .get(&(chat_id, member_id))
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < CHAT_MEMBER_TTL)
+            .map(|(chat_member, _)| chat_member.clone())
+    }
+
+    pub fn set_chat_member(&mut self, chat_id: i64, member_id: i64, chat_member: ChatMember) {
+        self.chat_members.insert((chat_id, member_id), (chat_member, Instant::now()));
+    }
+
+    pub fn get_pages(&self, hash: &str) -> Option<Vec<String>> {
+        self.pages
+            .get(hash)
+            .filter(|(_, created_at)| created_at.elapsed() < PAGES_TTL)
+            .map(|(pages, _)| pages.clone())
+    }
+
+    pub fn set_pages(&mut self, hash: String, pages: Vec<String>) {
+        self.pages.retain(|_, (_, created_at)| created_at.elapsed() < PAGES_TTL);
+        self.pages.insert(hash, (pages, Instant::now()));
+    }
+
+    pub fn get_wikipedia_summary(&self, title: &str) -> Option<Summary> {
+        self.wikipedia_summaries
+            .get(title)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < WIKIPEDIA_TTL)
+            .map(|(summary, _)| summary.clone())
+    }
+
+    pub fn set_wikipedia_summary(&mut self, title: String, summary: Summary) {
+        self.wikipedia_summaries.retain(|_, (_, fetched_at)| fetched_at.elapsed() < WIKIPEDIA_TTL);
+        self.wikipedia_summaries.insert(title, (summary, Instant::now()));
+    }
+
+    pub fn get_wikipedia_title(&self, page_id: u64) -> Option<String> {
+        self.wikipedia_titles
+            .get(&page_id)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < WIKIPEDIA_TTL)
+            .map(|(title, _)| title.clone())
     }
 
-    pub fn set_member_status(&mut self, chat_id: i64, member_id: i64, status: ChatMemberStatus) {
-        self.member_status.insert((chat_id, member_id), status);
+    pub fn set_wikipedia_title(&mut self, page_id: u64, title: String) {
+        self.wikipedia_titles.retain(|_, (_, fetched_at)| fetched_at.elapsed() < WIKIPEDIA_TTL);
+        self.wikipedia_titles.insert(page_id, (title, Instant::now()));
     }
 
     pub fn update_new_chat(&mut self, update: UpdateNewChat) {
@@ -56,7 +107,7 @@ impl Cache {
     pub fn update_chat_member(&mut self, update: UpdateChatMember) {
         if let MessageSender::User(MessageSenderUser { user_id }) = update.new_chat_member.member_id
         {
-            self.member_status.insert((update.chat_id, user_id), update.new_chat_member.status);
+            self.set_chat_member(update.chat_id, user_id, update.new_chat_member);
         }
     }
 }
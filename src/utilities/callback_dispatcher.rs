@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use tdlib::enums::{CallbackQueryPayload, InputMessageContent, ReplyMarkup};
+use tdlib::functions;
+use tdlib::types::{
+    CallbackQueryPayloadData, FormattedText, InputMessageText, LinkPreviewOptions,
+    UpdateNewCallbackQuery,
+};
+
+use super::bot_state::BotState;
+use super::command_manager::CommandManager;
+use super::{paginator, vote_handler};
+use crate::bot::TdResult;
+use crate::commands::{dashboard, history, wikipedia, CommandError};
+
+pub struct CallbackContext {
+    pub client_id: i32,
+    pub query: UpdateNewCallbackQuery,
+    pub bot_state: Arc<BotState>,
+    pub command_manager: Arc<CommandManager>,
+}
+
+impl CallbackContext {
+    pub async fn answer(&self, text: &str, show_alert: bool) -> TdResult<()> {
+        functions::answer_callback_query(
+            self.query.id,
+            text.into(),
+            show_alert,
+            String::new(),
+            0,
+            self.client_id,
+        )
+        .await
+    }
+
+    pub async fn edit_message(
+        &self,
+        text: FormattedText,
+        reply_markup: Option<ReplyMarkup>,
+    ) -> TdResult<()> {
+        let content = InputMessageContent::InputMessageText(InputMessageText {
+            text,
+            link_preview_options: Some(LinkPreviewOptions {
+                is_disabled: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        self.bot_state
+            .telegram_rate_limiter
+            .run(self.query.chat_id, || {
+                functions::edit_message_text(
+                    self.query.chat_id,
+                    self.query.message_id,
+                    reply_markup.clone(),
+                    content.clone(),
+                    self.client_id,
+                )
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn edit_reply_markup(&self, reply_markup: ReplyMarkup) -> TdResult<()> {
+        self.bot_state
+            .telegram_rate_limiter
+            .run(self.query.chat_id, || {
+                functions::edit_message_reply_markup(
+                    self.query.chat_id,
+                    self.query.message_id,
+                    Some(reply_markup.clone()),
+                    self.client_id,
+                )
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub async fn dispatch_callback(
+    query: UpdateNewCallbackQuery,
+    bot_state: Arc<BotState>,
+    command_manager: Arc<CommandManager>,
+    client_id: i32,
+) {
+    let CallbackQueryPayload::Data(CallbackQueryPayloadData { data }) = &query.payload else {
+        return; // ignore game and password callback queries
+    };
+
+    let Some(data) = STANDARD.decode(data).ok().and_then(|data| String::from_utf8(data).ok())
+    else {
+        return; // ignore callback queries not sent by this bot
+    };
+
+    if bot_state.config.lock().unwrap().blacklist.contains(&query.sender_user_id) {
+        return; // ignore callback queries from blacklisted users
+    }
+
+    let (prefix, arguments) = data.split_once(':').unwrap_or((&data, ""));
+    let context = CallbackContext { client_id, query, bot_state, command_manager };
+
+    log::info!("handling {data:?} callback query for {}", context.query.sender_user_id);
+
+    let result = match prefix {
+        "dashboard" => dashboard::callback(&context, arguments).await,
+        "history" => history::callback(&context, arguments).await,
+        "page" => paginator::callback(&context, arguments).await,
+        "vote" => vote_handler::callback(&context, arguments).await,
+        "wikipedia" => wikipedia::callback(&context, arguments).await,
+        _ => {
+            log::warn!("unknown callback query: {data:?}");
+            context.answer("", false).await.map_err(Into::into)
+        }
+    };
+
+    if let Err(err) = result {
+        if let Err(err) = report_callback_error(&context, err).await {
+            log::error!(
+                "TDLib error occurred while handling the previous error: {}: {}",
+                err.code,
+                err.message
+            );
+        }
+    }
+}
+
+async fn report_callback_error(context: &CallbackContext, error: CommandError) -> TdResult<()> {
+    let text = match error {
+        CommandError::Custom(text) => text,
+        CommandError::CustomFormattedText(text) => text.text,
+        CommandError::ArgumentConversion(err) => err.to_string(),
+        CommandError::Telegram(err) => {
+            log::error!("TDLib error in a callback query: {}: {}", err.code, err.message);
+            format!("sending the message failed ({}) 😔", err.message)
+        }
+        CommandError::Server(status_code) => {
+            format!("an external service used by this button is currently offline ({status_code}).")
+        }
+        CommandError::Reqwest(err) => {
+            log::error!("HTTP error in a callback query: {err}");
+            err.without_url().to_string()
+        }
+        CommandError::Timeout { operation, after } => {
+            CommandError::timeout_message(operation, after)
+        }
+    };
+
+    context.answer(&text, true).await
+}
@@ -1,14 +1,47 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tdlib::enums::{self, ChatAction, InputMessageContent, InputMessageReplyTo};
+use futures_util::{future, StreamExt};
+use tdlib::enums::{
+    self, ChatAction, ChatMemberStatus, ChatType, InputFile, InputMessageContent,
+    InputMessageReplyTo, Messages, ReactionType, TextParseMode,
+};
 use tdlib::functions;
 use tdlib::types::{
-    FormattedText, InputMessageReplyToMessage, InputMessageText, LinkPreviewOptions, Message,
+    ChatMember, FormattedText, InputFileLocal, InputMessageDocument, InputMessagePhoto,
+    InputMessageReplyToMessage, InputMessageText, LinkPreviewOptions, Message, ReactionTypeEmoji,
+    TextEntity, TextParseModeMarkdown,
 };
+use tempfile::{NamedTempFile, TempDir};
+use tokio::task::AbortHandle;
 
+use super::api_utils::DetectServerError;
 use super::bot_state::BotState;
 use super::cache::{CompactChat, CompactUser};
-use crate::bot::TdResult;
+use super::command_manager::CommandManager;
+use super::file_download::MEBIBYTE;
+use super::message_entities::Utf16Len;
+use super::telegram_utils::MAX_MESSAGE_LENGTH;
+use super::text_utils;
+use crate::bot::{TdError, TdResult};
+use crate::commands::{CommandError, CommandResult};
+
+/// the typing indicator disappears after 5 seconds
+const TYPING_INTERVAL: Duration = Duration::from_millis(4500);
+const PHOTO_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(5);
+/// Telegram doesn't accept larger photos
+const MAX_PHOTO_SIZE: u64 = 10 * MEBIBYTE.unsigned_abs();
+
+/// how the text of a reply is turned into entities
+#[derive(Clone, Copy)]
+pub enum ParseMode {
+    Plain,
+    MarkdownV2,
+}
 
 pub struct CommandContext {
     pub client_id: i32,
@@ -16,27 +49,55 @@ pub struct CommandContext {
     pub user: CompactUser,
     pub message: Message,
     pub bot_state: Arc<BotState>,
+    pub command_manager: Arc<CommandManager>,
 }
 
 impl CommandContext {
+    /// forum topic the command was sent in, `None` outside of forum topics
+    pub const fn thread_id(&self) -> Option<i64> {
+        if self.message.message_thread_id == 0 {
+            None
+        } else {
+            Some(self.message.message_thread_id)
+        }
+    }
+
+    /// forum topic and message replies to the command are sent to
+    fn reply_target(&self) -> (i64, InputMessageReplyTo) {
+        (
+            self.thread_id().unwrap_or_default(),
+            InputMessageReplyTo::Message(InputMessageReplyToMessage {
+                message_id: self.message.id,
+                ..Default::default()
+            }),
+        )
+    }
+
     pub async fn reply_custom(
         &self,
-        message_content: InputMessageContent,
+        mut message_content: InputMessageContent,
         reply_markup: Option<enums::ReplyMarkup>,
     ) -> TdResult<Message> {
-        let enums::Message::Message(message) = functions::send_message(
-            self.message.chat_id,
-            self.message.message_thread_id,
-            Some(InputMessageReplyTo::Message(InputMessageReplyToMessage {
-                message_id: self.message.id,
-                ..Default::default()
-            })),
-            None,
-            reply_markup,
-            message_content,
-            self.client_id,
-        )
-        .await?;
+        self.bot_state.last_messages.dedup(self.message.chat_id, &mut message_content);
+        let (thread_id, reply_to) = self.reply_target();
+
+        let enums::Message::Message(message) = self
+            .bot_state
+            .telegram_rate_limiter
+            .run(self.message.chat_id, || {
+                functions::send_message(
+                    self.message.chat_id,
+                    thread_id,
+                    Some(reply_to.clone()),
+                    None,
+                    reply_markup.clone(),
+                    message_content.clone(),
+                    self.client_id,
+                )
+            })
+            .await?;
+
+        self.bot_state.last_messages.record(self.message.chat_id, &message_content);
 
         Ok(message)
     }
@@ -57,7 +118,215 @@ impl CommandContext {
     }
 
     pub async fn reply(&self, text: String) -> TdResult<Message> {
-        self.reply_formatted_text(FormattedText { text, ..Default::default() }).await
+        self.reply_formatted(text, ParseMode::Plain).await
+    }
+
+    pub async fn reply_formatted(&self, text: String, mode: ParseMode) -> TdResult<Message> {
+        self.reply_formatted_text(self.parse_text(text, mode).await?).await
+    }
+
+    pub async fn reply_markdown(&self, text: String) -> TdResult<Message> {
+        self.reply_formatted(text, ParseMode::MarkdownV2).await
+    }
+
+    /// replies with markdown split into as many messages as needed, appending
+    /// a `MarkdownV2` footer to the last one
+    pub async fn reply_split_markdown(&self, text: &str, footer: String) -> TdResult<()> {
+        let footer = self.parse_text(footer, ParseMode::MarkdownV2).await?;
+        let chunks = text_utils::split_for_telegram(
+            text,
+            MAX_MESSAGE_LENGTH - footer.text.chars().count() - 2,
+        );
+        let chunk_count = chunks.len();
+
+        for (i, text) in chunks.into_iter().enumerate() {
+            let enums::FormattedText::FormattedText(mut formatted_text) =
+                functions::parse_markdown(
+                    FormattedText { text, ..Default::default() },
+                    self.client_id,
+                )
+                .await?;
+
+            if i + 1 == chunk_count && !footer.text.is_empty() {
+                let offset = i32::try_from(formatted_text.text.utf16_len()).unwrap() + 2;
+                formatted_text.text.push_str("\n\n");
+                formatted_text.text.push_str(&footer.text);
+                formatted_text.entities.extend(
+                    footer.entities.iter().map(|entity| TextEntity {
+                        offset: entity.offset + offset,
+                        ..entity.clone()
+                    }),
+                );
+            }
+
+            self.reply_formatted_text(formatted_text).await?;
+        }
+
+        Ok(())
+    }
+
+    /// sends photos with their captions as a single media group
+    pub async fn reply_photo_album(&self, photos: Vec<(Vec<u8>, String)>) -> CommandResult {
+        let mut temp_files = Vec::with_capacity(photos.len());
+        let mut contents = Vec::with_capacity(photos.len());
+
+        for (photo, caption) in photos {
+            let mut temp_file = NamedTempFile::new().unwrap();
+            temp_file.write_all(&photo).unwrap();
+
+            contents.push(InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                photo: InputFile::Local(InputFileLocal {
+                    path: temp_file.path().to_str().unwrap().into(),
+                }),
+                thumbnail: None,
+                added_sticker_file_ids: Vec::new(),
+                width: 0,
+                height: 0,
+                caption: (!caption.is_empty())
+                    .then(|| FormattedText { text: caption, ..Default::default() }),
+                self_destruct_type: None,
+                has_spoiler: false,
+            }));
+            temp_files.push(temp_file);
+        }
+
+        // the temporary files have to exist until the upload is finished
+        self.reply_album(contents).await?;
+        drop(temp_files);
+
+        Ok(())
+    }
+
+    /// sends the contents as a single media group and waits until all of them
+    /// are uploaded
+    pub async fn reply_album(&self, contents: Vec<InputMessageContent>) -> CommandResult {
+        let (thread_id, reply_to) = self.reply_target();
+        let Messages::Messages(messages) = self
+            .bot_state
+            .telegram_rate_limiter
+            .run(self.message.chat_id, || {
+                functions::send_message_album(
+                    self.message.chat_id,
+                    thread_id,
+                    Some(reply_to.clone()),
+                    None,
+                    contents.clone(),
+                    self.client_id,
+                )
+            })
+            .await?;
+
+        for result in self
+            .bot_state
+            .message_queue
+            .wait_for_messages(
+                &messages
+                    .messages
+                    .into_iter()
+                    .flatten()
+                    .map(|message| message.id)
+                    .collect::<Vec<_>>(),
+            )
+            .await
+        {
+            result?;
+        }
+
+        Ok(())
+    }
+
+    /// sends a file, adding the extension of the detected file type if the name
+    /// has none
+    pub async fn send_document(
+        &self,
+        bytes: Vec<u8>,
+        filename: &str,
+        caption: Option<String>,
+    ) -> Result<Message, CommandError> {
+        let mut filename = filename.to_owned();
+        if Path::new(&filename).extension().is_none() {
+            if let Some(kind) = infer::get(&bytes[..bytes.len().min(16)]) {
+                write!(filename, ".{}", kind.extension()).unwrap();
+            }
+        }
+
+        // a temporary directory keeps the file name visible in Telegram
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(filename);
+        fs::write(&path, bytes).unwrap();
+
+        let message = self
+            .reply_custom(
+                InputMessageContent::InputMessageDocument(InputMessageDocument {
+                    document: InputFile::Local(InputFileLocal {
+                        path: path.to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    disable_content_type_detection: false,
+                    caption: caption.map(|text| FormattedText { text, ..Default::default() }),
+                }),
+                None,
+            )
+            .await?;
+
+        Ok(self.bot_state.message_queue.wait_for_message(message.id).await?)
+    }
+
+    /// downloads an image and sends it as a photo
+    pub async fn send_photo_from_url(
+        &self,
+        url: &str,
+        caption: Option<String>,
+    ) -> Result<Message, CommandError> {
+        let response = self
+            .bot_state
+            .http_client
+            .get(url)
+            .timeout(PHOTO_DOWNLOAD_TIMEOUT)
+            .send()
+            .await?
+            .server_error()?
+            .error_for_status()?;
+
+        let too_large = "the image is larger than 10 MiB.";
+        if response.content_length().is_some_and(|length| length > MAX_PHOTO_SIZE) {
+            Err(too_large)?;
+        }
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let mut size = 0;
+        let mut stream = response.bytes_stream();
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes?;
+            size += u64::try_from(bytes.len()).unwrap();
+            if size > MAX_PHOTO_SIZE {
+                Err(too_large)?;
+            }
+            temp_file.write_all(&bytes).unwrap();
+        }
+
+        let message = self
+            .reply_custom(
+                InputMessageContent::InputMessagePhoto(InputMessagePhoto {
+                    photo: InputFile::Local(InputFileLocal {
+                        path: temp_file.path().to_str().unwrap().into(),
+                    }),
+                    thumbnail: None,
+                    added_sticker_file_ids: Vec::new(),
+                    width: 0,
+                    height: 0,
+                    caption: caption.map(|text| FormattedText { text, ..Default::default() }),
+                    self_destruct_type: None,
+                    has_spoiler: false,
+                }),
+                None,
+            )
+            .await?;
+
+        let message = self.bot_state.message_queue.wait_for_message(message.id).await?;
+        temp_file.close().unwrap();
+
+        Ok(message)
     }
 
     pub async fn edit_message_formatted_text(
@@ -65,21 +334,28 @@ impl CommandContext {
         message_id: i64,
         text: FormattedText,
     ) -> TdResult<Message> {
-        let enums::Message::Message(message) = functions::edit_message_text(
-            self.message.chat_id,
-            message_id,
-            None,
-            InputMessageContent::InputMessageText(InputMessageText {
-                text,
-                link_preview_options: Some(LinkPreviewOptions {
-                    is_disabled: true,
-                    ..Default::default()
-                }),
+        let content = InputMessageContent::InputMessageText(InputMessageText {
+            text,
+            link_preview_options: Some(LinkPreviewOptions {
+                is_disabled: true,
                 ..Default::default()
             }),
-            self.client_id,
-        )
-        .await?;
+            ..Default::default()
+        });
+
+        let enums::Message::Message(message) = self
+            .bot_state
+            .telegram_rate_limiter
+            .run(self.message.chat_id, || {
+                functions::edit_message_text(
+                    self.message.chat_id,
+                    message_id,
+                    None,
+                    content.clone(),
+                    self.client_id,
+                )
+            })
+            .await?;
 
         Ok(message)
     }
@@ -89,22 +365,222 @@ impl CommandContext {
             .await
     }
 
-    pub async fn delete_messages(&self, message_ids: Vec<i64>) -> TdResult<()> {
-        functions::delete_messages(self.message.chat_id, message_ids, true, self.client_id).await
+    pub async fn edit_message_markdown(&self, message_id: i64, text: String) -> TdResult<Message> {
+        self.edit_message_formatted_text(
+            message_id,
+            self.parse_text(text, ParseMode::MarkdownV2).await?,
+        )
+        .await
+    }
+
+    /// edits the message or sends a new one if it was deleted or can't be
+    /// edited anymore
+    pub async fn edit_or_resend(
+        &self,
+        message_id: i64,
+        text: String,
+    ) -> Result<Message, CommandError> {
+        match self.edit_message_markdown(message_id, text.clone()).await {
+            Err(err) if is_message_gone(&err) => Ok(self
+                .bot_state
+                .message_queue
+                .wait_for_message(self.reply_markdown(text).await?.id)
+                .await?),
+            result => Ok(result?),
+        }
+    }
+
+    pub async fn edit_or_resend_formatted_text(
+        &self,
+        message_id: i64,
+        text: FormattedText,
+    ) -> Result<Message, CommandError> {
+        match self.edit_message_formatted_text(message_id, text.clone()).await {
+            Err(err) if is_message_gone(&err) => Ok(self
+                .bot_state
+                .message_queue
+                .wait_for_message(self.reply_formatted_text(text).await?.id)
+                .await?),
+            result => Ok(result?),
+        }
+    }
+
+    async fn parse_text(&self, text: String, mode: ParseMode) -> TdResult<FormattedText> {
+        let parse_mode = match mode {
+            ParseMode::Plain => return Ok(FormattedText { text, ..Default::default() }),
+            ParseMode::MarkdownV2 => TextParseMode::Markdown(TextParseModeMarkdown { version: 2 }),
+        };
+
+        let enums::FormattedText::FormattedText(text) =
+            functions::parse_text_entities(text, parse_mode, self.client_id).await?;
+
+        Ok(text)
+    }
+
+    /// deletes the messages concurrently, failures are only logged because
+    /// some of the messages might already be gone
+    pub async fn delete_messages(&self, message_ids: &[i64]) -> Result<(), CommandError> {
+        let results =
+            future::join_all(message_ids.iter().map(|&message_id| self.delete_message(message_id)))
+                .await;
+
+        for (message_id, result) in message_ids.iter().zip(results) {
+            if let Err(err) = result {
+                log::debug!("failed to delete message {message_id}: {}", err.message);
+            }
+        }
+
+        Ok(())
     }
 
     pub async fn delete_message(&self, message_id: i64) -> TdResult<()> {
-        self.delete_messages(vec![message_id]).await
+        functions::delete_messages(self.message.chat_id, vec![message_id], true, self.client_id)
+            .await
     }
 
-    pub async fn send_typing(&self) -> TdResult<()> {
-        functions::send_chat_action(
+    /// replaces the bot's reactions on a message in the current chat
+    pub async fn react(&self, message_id: i64, emoji: &str) -> Result<(), CommandError> {
+        functions::set_message_reactions(
             self.message.chat_id,
-            self.message.message_thread_id,
-            String::new(),
-            Some(ChatAction::Typing),
+            message_id,
+            vec![ReactionType::Emoji(ReactionTypeEmoji { emoji: emoji.into() })],
+            false,
             self.client_id,
         )
-        .await
+        .await?;
+
+        Ok(())
+    }
+
+    pub const fn is_private_chat(&self) -> bool {
+        self.message.chat_id == self.user.id
+    }
+
+    pub const fn is_group_chat(&self) -> bool {
+        matches!(self.chat.r#type, ChatType::BasicGroup(_) | ChatType::Supergroup(_))
+    }
+
+    /// NSFW content is allowed in private chats and in groups that opted in
+    pub fn nsfw_allowed(&self) -> bool {
+        self.is_private_chat()
+            || (self.is_group_chat()
+                && self.bot_state.config.lock().unwrap().nsfw.contains(&self.message.chat_id))
+    }
+
+    /// fetches a member of the current chat, reusing recently fetched members
+    pub async fn get_chat_member(&self, user_id: i64) -> Result<ChatMember, CommandError> {
+        Ok(self.bot_state.get_chat_member(self.message.chat_id, user_id, self.client_id).await?)
+    }
+
+    /// whether the sender is an administrator or the creator of the current
+    /// group, always `false` in private chats
+    pub async fn is_group_admin(&self) -> bool {
+        if !self.is_group_chat() {
+            return false;
+        }
+
+        match self
+            .bot_state
+            .get_chat_member(self.message.chat_id, self.user.id, self.client_id)
+            .await
+        {
+            Ok(chat_member) => matches!(
+                chat_member.status,
+                ChatMemberStatus::Creator(_) | ChatMemberStatus::Administrator(_)
+            ),
+            Err(err) => {
+                log::warn!(
+                    "failed to check if {} is an admin of {}: {}: {}",
+                    self.user.id,
+                    self.message.chat_id,
+                    err.code,
+                    err.message
+                );
+                false
+            }
+        }
+    }
+
+    pub async fn send_typing(&self) -> TdResult<()> {
+        send_typing(self.message.chat_id, self.thread_id().unwrap_or_default(), self.client_id)
+            .await
+    }
+
+    /// keeps the typing indicator visible until the returned handle is aborted
+    pub fn send_typing_loop(&self) -> AbortHandle {
+        let chat_id = self.message.chat_id;
+        let thread_id = self.thread_id().unwrap_or_default();
+        let client_id = self.client_id;
+
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = send_typing(chat_id, thread_id, client_id).await {
+                    log::warn!("failed to send typing action: {}: {}", err.code, err.message);
+                }
+                tokio::time::sleep(TYPING_INTERVAL).await;
+            }
+        })
+        .abort_handle()
+    }
+}
+
+/// stops the typing indicator loop when dropped
+pub struct TypingGuard(pub AbortHandle);
+
+impl Drop for TypingGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn is_message_gone(err: &TdError) -> bool {
+    err.code == 400
+        && matches!(err.message.as_str(), "Message not found" | "Message can't be edited")
+}
+
+async fn send_typing(chat_id: i64, thread_id: i64, client_id: i32) -> TdResult<()> {
+    functions::send_chat_action(
+        chat_id,
+        thread_id,
+        String::new(),
+        Some(ChatAction::Typing),
+        client_id,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod test {
+    use tdlib::enums::InputMessageReplyTo;
+    use tdlib::types::InputMessageReplyToMessage;
+
+    use crate::utilities::test_fixtures;
+
+    #[test]
+    fn test_thread_id() {
+        let mut ctx = test_fixtures::command_context();
+        assert_eq!(ctx.thread_id(), None);
+
+        ctx.message.message_thread_id = 42;
+        assert_eq!(ctx.thread_id(), Some(42));
+    }
+
+    #[test]
+    fn test_reply_target() {
+        let mut ctx = test_fixtures::command_context();
+        ctx.message.id = 7;
+        assert_eq!(ctx.reply_target().0, 0);
+
+        ctx.message.message_thread_id = 42;
+        assert_eq!(
+            ctx.reply_target(),
+            (
+                42,
+                InputMessageReplyTo::Message(InputMessageReplyToMessage {
+                    message_id: 7,
+                    ..Default::default()
+                })
+            )
+        );
     }
 }
@@ -1,31 +1,72 @@
-use std::sync::Arc;
+use std::env;
+use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 
+use super::bot_state::BotState;
 use super::command_context::CommandContext;
 use super::command_manager::CommandInstance;
+use super::database::{CommandInvocation, InvocationResult};
 use crate::bot::TdResult;
-use crate::commands::CommandError;
+use crate::commands::{CommandError, Privilege, OWNER_ID};
 use crate::utilities::text_utils;
 
+/// reacting to every command costs an extra API call, so it's opt-in
+static REACTIONS_ENABLED: LazyLock<bool> =
+    LazyLock::new(|| env::var("ENABLE_REACTIONS").is_ok_and(|value| value == "true"));
+
 pub async fn dispatch_command(
     command: Arc<CommandInstance>,
     arguments: String,
     context: CommandContext,
 ) {
+    match check_access(&command, &context).await {
+        Access::Granted => (),
+        Access::Ignored => return,
+        Access::Denied(reason) => {
+            if let Err(err) = report_denied_access(&context, reason).await {
+                log::error!(
+                    "TDLib error occurred while reporting denied access: {}: {}",
+                    err.code,
+                    err.message
+                );
+            }
+            return;
+        }
+    }
+
     if let Some(cooldown) = check_rate_limit(&command, &context) {
-        if let Err(err) = Box::pin(report_rate_limit(&context, cooldown)).await {
+        if let Err(err) = Box::pin(report_rate_limit(&command, &context, cooldown)).await {
             log::error!(
                 "TDLib error occurred while reporting a rate limit: {}: {}",
                 err.code,
                 err.message
             );
         }
+        record_invocation(&command, &context, InvocationResult::RateLimited);
         return;
     }
 
     log::info!("running {command} {:?} for {} in {}", arguments, context.user, context.chat);
 
-    if let Err(err) = command.command.execute(&context, arguments).await {
+    let timeout = command.command.timeout();
+    let result = tokio::time::timeout(timeout, command.command.execute(&context, arguments))
+        .await
+        .unwrap_or(Err(CommandError::Timeout { operation: "command", after: timeout }));
+    record_invocation(
+        &command,
+        &context,
+        if result.is_ok() { InvocationResult::Success } else { InvocationResult::Error },
+    );
+
+    if *REACTIONS_ENABLED {
+        // "❌" isn't one of the reactions Telegram allows
+        let emoji = if result.is_ok() { "👍" } else { "👎" };
+        if let Err(err) = context.react(context.message.id, emoji).await {
+            log::warn!("failed to react to a command: {err:?}");
+        }
+    }
+
+    if let Err(err) = result {
         if let Err(err) = report_command_error(command, &context, err).await {
             log::error!(
                 "TDLib error occurred while handling the previous error: {}: {}",
@@ -36,14 +77,90 @@ pub async fn dispatch_command(
     }
 }
 
-fn check_rate_limit(command: &CommandInstance, context: &CommandContext) -> Option<u64> {
-    let cooldown = command
-        .rate_limiter
+enum Access {
+    Granted,
+    Ignored,
+    Denied(String),
+}
+
+async fn check_access(command: &CommandInstance, context: &CommandContext) -> Access {
+    if context.user.id != OWNER_ID {
+        if let Some(message) = maintenance_message(&context.bot_state) {
+            return Access::Denied(message);
+        }
+    }
+
+    if !check_privilege(command, context).await {
+        // owner-only commands are silently ignored for everyone else
+        return match command.command.minimum_privilege() {
+            Privilege::GroupAdmin => {
+                Access::Denied("this command can only be used by group administrators.".into())
+            }
+            _ => Access::Ignored,
+        };
+    }
+
+    if is_disabled(command, context) {
+        return Access::Denied("this command is disabled in this chat.".into());
+    }
+
+    Access::Granted
+}
+
+/// what to tell users while the bot is under maintenance
+pub fn maintenance_message(bot_state: &BotState) -> Option<String> {
+    bot_state.maintenance.lock().unwrap().as_ref().map(|maintenance| {
+        maintenance.reason.as_ref().map_or_else(
+            || "🔧 Bot is under maintenance.".into(),
+            |reason| format!("🔧 Bot is under maintenance: {reason}."),
+        )
+    })
+}
+
+fn is_disabled(command: &CommandInstance, context: &CommandContext) -> bool {
+    context
+        .bot_state
+        .config
         .lock()
         .unwrap()
-        .update_rate_limit(context.user.id, context.message.date)?
-        .try_into()
-        .unwrap();
+        .disabled_commands
+        .get(&context.message.chat_id)
+        .is_some_and(|disabled_commands| {
+            command.command.command_names().iter().any(|name| disabled_commands.contains(*name))
+        })
+}
+
+async fn check_privilege(command: &CommandInstance, context: &CommandContext) -> bool {
+    if context.user.id == OWNER_ID {
+        return true;
+    }
+
+    match command.command.minimum_privilege() {
+        Privilege::User => true,
+        Privilege::GroupAdmin => context.is_group_admin().await,
+        Privilege::Owner => false,
+    }
+}
+
+fn check_rate_limit(command: &CommandInstance, context: &CommandContext) -> Option<u64> {
+    let cooldown = {
+        let mut rate_limiter = command.rate_limiter.lock().unwrap();
+        // commands are more disruptive in groups
+        if context.is_group_chat() {
+            rate_limiter.update_rate_limit_strict(context.user.id, context.message.date)?
+        } else {
+            rate_limiter.update_rate_limit(context.user.id, context.message.date)?
+        }
+    };
+
+    context.bot_state.rate_limits.lock().unwrap().record_cooldown(
+        context.user.id,
+        command.to_string(),
+        context.message.date,
+        cooldown,
+    );
+
+    let cooldown = cooldown.try_into().unwrap();
 
     log::info!(
         "{command} rate limit exceeded by {} by {}",
@@ -54,7 +171,45 @@ fn check_rate_limit(command: &CommandInstance, context: &CommandContext) -> Opti
     Some(cooldown)
 }
 
-async fn report_rate_limit(context: &CommandContext, cooldown: u64) -> TdResult<()> {
+fn record_invocation(
+    command: &CommandInstance,
+    context: &CommandContext,
+    result: InvocationResult,
+) {
+    context.bot_state.database.lock().unwrap().command_invocations.push(CommandInvocation {
+        command: command.command.name().into(),
+        user_id: context.user.id,
+        chat_id: context.message.chat_id,
+        date: context.message.date,
+        result,
+    });
+}
+
+/// replies at most once a minute per user, so that denied commands can't be
+/// used to make the bot flood a chat
+async fn report_denied_access(context: &CommandContext, reason: String) -> TdResult<()> {
+    if context
+        .bot_state
+        .rate_limits
+        .lock()
+        .unwrap()
+        .access_denied
+        .update_rate_limit(context.user.id, context.message.date)
+        .is_some()
+    {
+        return Ok(());
+    }
+
+    context.reply(reason).await?;
+
+    Ok(())
+}
+
+async fn report_rate_limit(
+    command: &CommandInstance,
+    context: &CommandContext,
+    cooldown: u64,
+) -> TdResult<()> {
     if context
         .bot_state
         .rate_limits
@@ -74,10 +229,7 @@ async fn report_rate_limit(context: &CommandContext, cooldown: u64) -> TdResult<
         .message_queue
         .wait_for_message(
             context
-                .reply(format!(
-                    "you can use this command again in {}.",
-                    text_utils::format_duration(cooldown)
-                ))
+                .reply(command.command.cooldown_message(Duration::from_secs(cooldown)))
                 .await?
                 .id,
         )
@@ -113,6 +265,10 @@ async fn report_command_error(
             log::error!("HTTP error in the {command} command: {err}");
             context.reply(err.without_url().to_string()).await?
         }
+        CommandError::Timeout { operation, after } => {
+            log::warn!("{operation} in the {command} command timed out after {after:?}");
+            context.reply(CommandError::timeout_message(operation, after)).await?
+        }
     };
 
     Ok(())
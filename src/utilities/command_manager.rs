@@ -4,7 +4,14 @@ use std::sync::{Arc, Mutex};
 use tdlib::types::BotCommand;
 
 use super::rate_limit::RateLimiter;
-use crate::commands::CommandTrait;
+use super::text_utils;
+use crate::commands::{CommandTrait, Privilege};
+
+/// larger distances are more likely a different word than a typo
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+/// short names only get suggestions for a single typo, since 2 edits can turn
+/// them into almost any other short name
+const CHARS_PER_SUGGESTION_DISTANCE: usize = 3;
 
 pub type CommandRef = Box<dyn CommandTrait + Send + Sync>;
 
@@ -15,7 +22,7 @@ pub struct CommandInstance {
 
 impl fmt::Display for CommandInstance {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "/{}", self.command.command_names().first().unwrap())
+        write!(f, "/{}", self.command.name())
     }
 }
 
@@ -39,14 +46,31 @@ impl CommandManager {
         self.commands.iter().find(|c| c.command.command_names().contains(&name)).cloned()
     }
 
-    pub fn public_command_list(&self) -> Vec<BotCommand> {
+    /// finds the closest name of a command which requires one of `privileges`
+    /// to a mistyped one
+    pub fn suggest_command(&self, name: &str, privileges: &[Privilege]) -> Option<String> {
+        let max_distance = (name.chars().count() / CHARS_PER_SUGGESTION_DISTANCE)
+            .clamp(1, MAX_SUGGESTION_DISTANCE);
+
+        self.commands
+            .iter()
+            .filter(|c| !c.command.hidden() && privileges.contains(&c.command.minimum_privilege()))
+            .flat_map(|c| c.command.command_names())
+            .map(|command_name| (command_name, text_utils::levenshtein(name, command_name)))
+            .filter(|(_, distance)| *distance <= max_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(command_name, _)| (*command_name).into())
+    }
+
+    /// visible commands with a description which require one of `privileges`
+    pub fn public_command_list(&self, privileges: &[Privilege]) -> Vec<BotCommand> {
         self.commands
             .iter()
+            .filter(|c| !c.command.hidden() && privileges.contains(&c.command.minimum_privilege()))
             .filter_map(|c| {
-                c.command.description().map(|d| BotCommand {
-                    command: (*c.command.command_names().first().unwrap()).into(),
-                    description: d.into(),
-                })
+                c.command
+                    .description()
+                    .map(|d| BotCommand { command: c.command.name().into(), description: d.into() })
             })
             .collect()
     }
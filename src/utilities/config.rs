@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter};
 use std::path::Path;
@@ -11,6 +11,10 @@ const FILENAME: &str = "config.dat";
 #[serde(default)]
 pub struct Config {
     pub markov_chain_learning: HashSet<i64>,
+    /// groups that allow NSFW generations
+    pub nsfw: HashSet<i64>,
+    pub blacklist: HashSet<i64>,
+    pub disabled_commands: HashMap<i64, HashSet<String>>,
 }
 
 impl Config {
@@ -1,12 +1,23 @@
 use std::borrow::Cow;
-use std::fmt;
+use std::net::IpAddr;
+use std::time::Duration;
+use std::{fmt, fs};
 
 use async_trait::async_trait;
-use tdlib::enums::{Message, MessageReplyTo};
+use regex::{Regex, RegexBuilder};
+use tdlib::enums::{File, Message, MessageContent, MessageReplyTo};
 use tdlib::functions;
+use tdlib::types::{FormattedText, Sticker};
+use url::{ParseError, Url};
 
 use super::command_context::CommandContext;
-use super::telegram_utils;
+use super::file_download::MEBIBYTE;
+use super::{net_utils, telegram_utils};
+use crate::style_presets::StylePreset;
+
+/// the limit of the Whisper API
+const MAX_VOICE_SIZE: i64 = 25 * MEBIBYTE;
+const MAX_PHOTO_SIZE: i64 = 5 * MEBIBYTE;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ConversionError {
@@ -87,33 +98,118 @@ impl ConvertArgument for Reply {
         ctx: &CommandContext,
         arguments: &'a str,
     ) -> Result<(Self, &'a str), ConversionError> {
-        let Some(MessageReplyTo::Message(reply)) = &ctx.message.reply_to else {
-            return Err(ConversionError::MissingArgument);
-        };
+        Ok((Self(get_replied_text(ctx).await?.text), arguments))
+    }
+}
 
-        if let Some(quote) = reply.quote.as_ref() {
-            return Ok((Self(quote.text.text.clone()), arguments));
-        };
+async fn get_replied_text(ctx: &CommandContext) -> Result<FormattedText, ConversionError> {
+    let Some(MessageReplyTo::Message(reply)) = &ctx.message.reply_to else {
+        return Err(ConversionError::MissingArgument);
+    };
 
-        let content = if let Some(content) = reply.content.as_ref() {
-            Cow::Borrowed(content)
-        } else {
-            let Message::Message(message) =
-                functions::get_replied_message(ctx.message.chat_id, ctx.message.id, ctx.client_id)
-                    .await
-                    .map_err(|_| {
-                        ConversionError::BadArgument("replied message couldn't be loaded.")
-                    })?;
-
-            Cow::Owned(message.content)
+    if let Some(quote) = reply.quote.as_ref() {
+        return Ok(quote.text.clone());
+    };
+
+    let content = get_replied_content(ctx).await?;
+    telegram_utils::get_message_text(&content)
+        .cloned()
+        .ok_or(ConversionError::BadArgument("replied message doesn't contain any text."))
+}
+
+async fn get_replied_content(
+    ctx: &CommandContext,
+) -> Result<Cow<'_, MessageContent>, ConversionError> {
+    let Some(MessageReplyTo::Message(reply)) = &ctx.message.reply_to else {
+        return Err(ConversionError::MissingArgument);
+    };
+
+    if let Some(content) = reply.content.as_ref() {
+        return Ok(Cow::Borrowed(content));
+    }
+
+    let Message::Message(message) =
+        functions::get_replied_message(ctx.message.chat_id, ctx.message.id, ctx.client_id)
+            .await
+            .map_err(|_| ConversionError::BadArgument("replied message couldn't be loaded."))?;
+
+    Ok(Cow::Owned(message.content))
+}
+
+/// OGG Opus audio of the replied voice message
+pub struct ReplyVoice(pub Vec<u8>);
+
+#[async_trait]
+impl ConvertArgument for ReplyVoice {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let MessageContent::MessageVoiceNote(message) = &*get_replied_content(ctx).await? else {
+            return Err(ConversionError::BadArgument("replied message isn't a voice message."));
         };
 
-        let argument = telegram_utils::get_message_text(&content)
-            .ok_or(ConversionError::BadArgument("replied message doesn't contain any text."))?
-            .text
-            .clone();
+        let voice = &message.voice_note.voice;
+        if voice.expected_size > MAX_VOICE_SIZE {
+            return Err(ConversionError::BadArgument("the voice message is too large."));
+        }
+
+        let File::File(file) =
+            functions::download_file(voice.id, 1, 0, 0, true, ctx.client_id).await.map_err(
+                |_| ConversionError::BadArgument("the voice message couldn't be downloaded."),
+            )?;
 
-        Ok((Self(argument), arguments))
+        let data = fs::read(file.local.path).map_err(|_| {
+            ConversionError::BadArgument("the voice message couldn't be downloaded.")
+        })?;
+
+        Ok((Self(data), arguments))
+    }
+}
+
+/// image data of the replied photo, image document or sticker
+pub struct ReplyPhoto(pub Vec<u8>);
+
+#[async_trait]
+impl ConvertArgument for ReplyPhoto {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let message_image = telegram_utils::get_message_image(&*get_replied_content(ctx).await?)
+            .ok_or(ConversionError::BadArgument("replied message isn't an image."))?;
+
+        if message_image.file.expected_size > MAX_PHOTO_SIZE {
+            return Err(ConversionError::BadArgument("the image is too large."));
+        }
+
+        let File::File(file) =
+            functions::download_file(message_image.file.id, 1, 0, 0, true, ctx.client_id)
+                .await
+                .map_err(|_| ConversionError::BadArgument("the image couldn't be downloaded."))?;
+
+        let data = fs::read(file.local.path)
+            .map_err(|_| ConversionError::BadArgument("the image couldn't be downloaded."))?;
+
+        Ok((Self(data), arguments))
+    }
+}
+
+/// the sticker of the replied message
+pub struct ReplySticker(pub Sticker);
+
+#[async_trait]
+impl ConvertArgument for ReplySticker {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let MessageContent::MessageSticker(message) = get_replied_content(ctx).await?.into_owned()
+        else {
+            return Err(ConversionError::BadArgument("replied message isn't a sticker."));
+        };
+
+        Ok((Self(message.sticker), arguments))
     }
 }
 
@@ -155,6 +251,38 @@ impl ConvertArgument for StringGreedyOrReply {
     }
 }
 
+/// like [`StringGreedyOrReply`], but keeps the formatting of the replied
+/// message as `MarkdownV2`
+pub struct FormattedStringGreedyOrReply(pub String);
+
+#[async_trait]
+impl ConvertArgument for FormattedStringGreedyOrReply {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = Option::<StringGreedy>::convert(ctx, arguments).await?;
+        if let Some(argument) = argument {
+            return Ok((Self(argument.0), rest));
+        }
+
+        let text = get_replied_text(ctx).await?;
+
+        if text.text.trim().is_empty() {
+            return Err(ConversionError::MissingArgument);
+        }
+
+        // plain text is kept unescaped to not confuse the models
+        let argument = if text.entities.is_empty() {
+            text.text
+        } else {
+            telegram_utils::entities_to_markdown(&text.text, &text.entities)
+        };
+
+        Ok((Self(argument), ""))
+    }
+}
+
 #[async_trait]
 impl ConvertArgument for bool {
     async fn convert<'a>(
@@ -176,11 +304,196 @@ impl ConvertArgument for bool {
     }
 }
 
+#[async_trait]
+impl ConvertArgument for i64 {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = String::convert(ctx, arguments).await?;
+        let value = argument
+            .parse()
+            .map_err(|_| ConversionError::BadArgument("argument must be an integer."))?;
+
+        Ok((value, rest))
+    }
+}
+
+/// a URL, assuming `http://` when the scheme is missing
+#[async_trait]
+impl ConvertArgument for Url {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = String::convert(ctx, arguments).await?;
+        let url = match Self::parse(&argument) {
+            Err(ParseError::RelativeUrlWithoutBase) => Self::parse(&format!("http://{argument}")),
+            url => url,
+        }
+        .map_err(|_| ConversionError::BadArgument("invalid URL."))?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            Err(ConversionError::BadArgument("only HTTP and HTTPS URLs are supported."))?;
+        }
+
+        Ok((url, rest))
+    }
+}
+
+/// an IP address which isn't private, loopback or otherwise reserved
+#[derive(Debug, PartialEq, Eq)]
+pub struct NonPrivateIpAddr(pub IpAddr);
+
+#[async_trait]
+impl ConvertArgument for NonPrivateIpAddr {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = String::convert(ctx, arguments).await?;
+        let ip = argument
+            .parse::<IpAddr>()
+            .map_err(|_| ConversionError::BadArgument("invalid IP address."))?;
+
+        if !net_utils::is_public_ip(ip) {
+            Err(ConversionError::BadArgument("private IP addresses are not allowed."))?;
+        }
+
+        Ok((Self(ip), rest))
+    }
+}
+
+/// a regex, either bare or as `/pattern/flags` with any of the `imsx` flags
+pub struct RegexArg {
+    pub regex: Regex,
+    pub flags: String,
+}
+
+#[async_trait]
+impl ConvertArgument for RegexArg {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = String::convert(ctx, arguments).await?;
+        let (pattern, flags) =
+            match argument.strip_prefix('/').and_then(|rest| rest.rsplit_once('/')) {
+                Some((pattern, flags)) => (pattern, flags),
+                None => (argument.as_str(), ""),
+            };
+
+        let mut builder = RegexBuilder::new(pattern);
+        // untrusted patterns shouldn't use much memory
+        builder.size_limit(1 << 20).dfa_size_limit(1 << 20);
+
+        for flag in flags.chars() {
+            match flag {
+                'i' => builder.case_insensitive(true),
+                'm' => builder.multi_line(true),
+                's' => builder.dot_matches_new_line(true),
+                'x' => builder.ignore_whitespace(true),
+                _ => {
+                    return Err(ConversionError::BadArgument(
+                        "supported regex flags are i, m, s and x.",
+                    ))
+                }
+            };
+        }
+
+        let regex = builder.build().map_err(|err| match err {
+            regex::Error::CompiledTooBig(_) => {
+                ConversionError::BadArgument("the regex is too large.")
+            }
+            _ => ConversionError::BadArgument("invalid regex."),
+        })?;
+
+        Ok((Self { regex, flags: flags.into() }, rest))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DurationArg(pub Duration);
+
+#[async_trait]
+impl ConvertArgument for DurationArg {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let (argument, rest) = String::convert(ctx, arguments).await?;
+        let duration = parse_duration(&argument).ok_or(ConversionError::BadArgument(
+            "duration must be a number of seconds or look like 30s, 5m or 1h30m.",
+        ))?;
+
+        Ok((Self(duration), rest))
+    }
+}
+
+/// an optional `--style <name>` flag selecting one of the [`STYLE_PRESETS`]
+///
+/// [`STYLE_PRESETS`]: crate::style_presets::STYLE_PRESETS
+pub struct StylePresetArg(pub Option<&'static StylePreset>);
+
+#[async_trait]
+impl ConvertArgument for StylePresetArg {
+    async fn convert<'a>(
+        ctx: &CommandContext,
+        arguments: &'a str,
+    ) -> Result<(Self, &'a str), ConversionError> {
+        let Some(rest) = arguments.trim_start().strip_prefix("--style ") else {
+            return Ok((Self(None), arguments));
+        };
+
+        let (name, rest) = String::convert(ctx, rest).await?;
+        let preset = StylePreset::find(&name)
+            .ok_or(ConversionError::BadArgument("unknown style, see /styles for the list."))?;
+
+        Ok((Self(Some(preset)), rest))
+    }
+}
+
+/// parses durations like `90`, `90s`, `5m`, `1h30m` or `2d`
+fn parse_duration(argument: &str) -> Option<Duration> {
+    if let Ok(seconds) = argument.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut total = 0u64;
+    let mut value = None::<u64>;
+
+    for char in argument.chars() {
+        if let Some(digit) = char.to_digit(10) {
+            value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add(digit.into())?);
+        } else {
+            let multiplier = match char.to_ascii_lowercase() {
+                's' => 1,
+                'm' => 60,
+                'h' => 60 * 60,
+                'd' => 24 * 60 * 60,
+                _ => return None,
+            };
+            total = total.checked_add(value.take()?.checked_mul(multiplier)?)?;
+        }
+    }
+
+    if value.is_some() {
+        return None;
+    }
+
+    Some(Duration::from_secs(total))
+}
+
 #[cfg(test)]
 mod test {
+    use craiyon_bot_derive::ConvertArgument;
+
     use super::*;
     use crate::utilities::test_fixtures;
 
+    #[derive(Debug, PartialEq, Eq, ConvertArgument)]
+    struct Word(String);
+
     #[tokio::test]
     async fn test_string_converter() {
         let ctx = test_fixtures::command_context();
@@ -205,6 +518,18 @@ mod test {
         assert_eq!(rest, " bar");
     }
 
+    #[tokio::test]
+    async fn test_derived_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let result = Word::convert(&ctx, "").await;
+        assert_eq!(result, Err(ConversionError::MissingArgument));
+
+        let (argument, rest) = Word::convert(&ctx, "foo bar").await.unwrap();
+        assert_eq!(argument, Word("foo".into()));
+        assert_eq!(rest, "bar");
+    }
+
     #[tokio::test]
     async fn test_option_converter() {
         let ctx = test_fixtures::command_context();
@@ -300,4 +625,110 @@ mod test {
         assert_eq!(argument, "foo  bar");
         assert_eq!(rest, "");
     }
+
+    #[tokio::test]
+    async fn test_integer_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let (argument, rest) = i64::convert(&ctx, "-123 foo").await.unwrap();
+        assert_eq!(argument, -123);
+        assert_eq!(rest, "foo");
+
+        let result = i64::convert(&ctx, "12a").await;
+        assert!(matches!(result, Err(ConversionError::BadArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn test_duration_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let result = DurationArg::convert(&ctx, "").await;
+        assert_eq!(result, Err(ConversionError::MissingArgument));
+
+        let (DurationArg(duration), rest) = ConvertArgument::convert(&ctx, "90 foo").await.unwrap();
+        assert_eq!(duration, Duration::from_secs(90));
+        assert_eq!(rest, "foo");
+
+        let (DurationArg(duration), _) = ConvertArgument::convert(&ctx, "5m").await.unwrap();
+        assert_eq!(duration, Duration::from_secs(300));
+
+        let (DurationArg(duration), _) = ConvertArgument::convert(&ctx, "1h30m15s").await.unwrap();
+        assert_eq!(duration, Duration::from_secs(5415));
+
+        let (DurationArg(duration), _) = ConvertArgument::convert(&ctx, "2D").await.unwrap();
+        assert_eq!(duration, Duration::from_secs(172_800));
+
+        for argument in ["m", "1h30", "5x", "1.5h"] {
+            let result = DurationArg::convert(&ctx, argument).await;
+            assert!(matches!(result, Err(ConversionError::BadArgument(_))), "{argument}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_style_preset_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let (StylePresetArg(preset), rest) = StylePresetArg::convert(&ctx, "a cat").await.unwrap();
+        assert!(preset.is_none());
+        assert_eq!(rest, "a cat");
+
+        let (StylePresetArg(preset), rest) =
+            StylePresetArg::convert(&ctx, "--style anime a cat").await.unwrap();
+        assert_eq!(preset.unwrap().name, "anime");
+        assert_eq!(rest, "a cat");
+
+        let result = StylePresetArg::convert(&ctx, "--style cubism a cat").await;
+        assert!(matches!(result, Err(ConversionError::BadArgument(_))));
+    }
+
+    #[tokio::test]
+    async fn test_url_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let (url, rest) = Url::convert(&ctx, "example.com/page foo").await.unwrap();
+        assert_eq!(url.as_str(), "http://example.com/page");
+        assert_eq!(rest, "foo");
+
+        let (url, _) = Url::convert(&ctx, "https://example.com").await.unwrap();
+        assert_eq!(url.as_str(), "https://example.com/");
+
+        for argument in ["ftp://example.com", "http://"] {
+            let result = Url::convert(&ctx, argument).await;
+            assert!(matches!(result, Err(ConversionError::BadArgument(_))), "{argument}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_private_ip_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let (NonPrivateIpAddr(ip), _) = ConvertArgument::convert(&ctx, "1.1.1.1").await.unwrap();
+        assert_eq!(ip, IpAddr::from([1, 1, 1, 1]));
+
+        for argument in ["192.168.0.1", "::1", "example.com"] {
+            let result = NonPrivateIpAddr::convert(&ctx, argument).await;
+            assert!(matches!(result, Err(ConversionError::BadArgument(_))), "{argument}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_regex_converter() {
+        let ctx = test_fixtures::command_context();
+
+        let (RegexArg { regex, flags }, rest) =
+            RegexArg::convert(&ctx, "a+b foo bar").await.unwrap();
+        assert_eq!(regex.as_str(), "a+b");
+        assert_eq!(flags, "");
+        assert_eq!(rest, "foo bar");
+
+        let (RegexArg { regex, flags }, _) = RegexArg::convert(&ctx, "/a/b+/im foo").await.unwrap();
+        assert_eq!(regex.as_str(), "a/b+");
+        assert_eq!(flags, "im");
+        assert!(regex.is_match("A/BB"));
+
+        for argument in ["(unclosed", "/a/q", "\\w{1000}{1000}"] {
+            let result = RegexArg::convert(&ctx, argument).await;
+            assert!(matches!(result, Err(ConversionError::BadArgument(_))), "{argument}");
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+const FILENAME: &str = "database.dat";
+const TEMP_FILENAME: &str = "database.dat.tmp";
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Database {
+    pub reminders: Vec<Reminder>,
+    pub feedback: Vec<FeedbackEntry>,
+    pub command_invocations: Vec<CommandInvocation>,
+    pub image_generations: Vec<ImageGeneration>,
+    pub votes: Vec<Vote>,
+    pub ab_test_assignments: Vec<AbTestAssignment>,
+    pub ai_settings: HashMap<i64, AiSettings>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Reminder {
+    pub user_id: i64,
+    pub chat_id: i64,
+    /// forum topic to send the reminder in, 0 outside of forum topics
+    #[serde(default)]
+    pub thread_id: i64,
+    pub message_id: i64,
+    /// Unix timestamp
+    pub remind_at: i64,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    pub id: usize,
+    pub user_id: i64,
+    pub chat_id: i64,
+    pub date: i32,
+    pub text: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvocationResult {
+    Success,
+    Error,
+    RateLimited,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CommandInvocation {
+    pub command: String,
+    pub user_id: i64,
+    pub chat_id: i64,
+    pub date: i32,
+    pub result: InvocationResult,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ImageGeneration {
+    pub command: String,
+    pub user_id: i64,
+    pub chat_id: i64,
+    pub date: i32,
+    pub prompt: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteKind {
+    Up,
+    Down,
+}
+
+/// a user's vote on a generated image, each user has at most one per image
+#[derive(Serialize, Deserialize)]
+pub struct Vote {
+    pub user_id: i64,
+    /// user who requested the image
+    pub author_id: i64,
+    pub image_hash: String,
+    // keeps the name saved by earlier versions
+    #[serde(rename = "vote")]
+    pub kind: VoteKind,
+}
+
+/// the variant of an A/B test a user was first routed to
+#[derive(Serialize, Deserialize)]
+pub struct AbTestAssignment {
+    pub test: String,
+    pub user_id: i64,
+    pub variant: String,
+    pub date: i32,
+}
+
+/// a user's defaults for the AI commands, unset fields keep each command's
+/// default
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AiSettings {
+    pub model: Option<String>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<u32>,
+}
+
+impl Database {
+    pub fn load() -> io::Result<Self> {
+        let path = Path::new(FILENAME);
+
+        if path.exists() {
+            log::debug!("loading database from drive");
+            Ok(rmp_serde::decode::from_read(BufReader::new(File::open(path)?)).unwrap())
+        } else {
+            log::debug!("creating an empty database");
+            Ok(Self::default())
+        }
+    }
+
+    /// also called while running, so the file is replaced only once fully
+    /// written and a crash can't leave it truncated
+    pub fn save(&self) -> io::Result<()> {
+        log::debug!("saving database to drive");
+        let file = File::options().write(true).truncate(true).create(true).open(TEMP_FILENAME)?;
+        let mut writer = BufWriter::new(file);
+        rmp_serde::encode::write_named(&mut writer, self).unwrap();
+        writer.into_inner()?.sync_all()?;
+        fs::rename(TEMP_FILENAME, FILENAME)
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use super::bot_state::BotState;
+
+/// serves `GET /health` for uptime monitors and container health checks and
+/// `GET /metrics` for Prometheus
+pub async fn serve(bot_state: Arc<BotState>, address: String) {
+    let listener = match TcpListener::bind(&address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("failed to bind the health check server to {address}: {err}");
+            return;
+        }
+    };
+
+    log::info!("health check server listening on {address}");
+
+    if let Err(err) = axum::serve(listener, router(bot_state)).await {
+        log::error!("health check server stopped: {err}");
+    }
+}
+
+fn router(bot_state: Arc<BotState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .with_state(bot_state)
+}
+
+async fn health(State(bot_state): State<Arc<BotState>>) -> Json<Value> {
+    Json(json!({ "status": "ok", "uptime_seconds": bot_state.start_time.elapsed().as_secs() }))
+}
+
+async fn metrics(State(bot_state): State<Arc<BotState>>) -> String {
+    format!(
+        "# HELP bot_last_update_age_seconds Seconds since the last update from TDLib.\n\
+         # TYPE bot_last_update_age_seconds gauge\n\
+         bot_last_update_age_seconds {}\n",
+        bot_state.last_update_age()
+    )
+}
@@ -1,14 +1,96 @@
-use image::{imageops, DynamicImage};
+use std::cmp::Reverse;
+use std::io::Cursor;
+use std::sync::LazyLock;
+use std::{env, fs, mem};
+
+use ab_glyph::FontVec;
+use image::imageops::FilterType;
+use image::io::{Limits, Reader as ImageReader};
+use image::{imageops, DynamicImage, ImageResult, Rgb, RgbImage, Rgba, RgbaImage};
+use imageproc::drawing;
+
+/// default limit for both sides of a collage, larger batches are scaled down
+/// so they don't run the bot out of memory
+const MAX_COLLAGE_DIMENSION: u32 = 4096;
+/// installed by `fonts-dejavu-core` in the Docker image
+const FONT_PATH: &str = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
+const LABEL_HEIGHT: u32 = 32;
+/// decoder limits for untrusted images, so a small file can't expand into
+/// gigabytes of pixels
+const MAX_DECODED_DIMENSION: u32 = 8192;
+const MAX_DECODED_ALLOC: u64 = 256 * 1024 * 1024;
+
+/// font for text drawn on images, which is left out if it couldn't be loaded
+pub static FONT: LazyLock<Option<FontVec>> = LazyLock::new(|| {
+    let font = fs::read(FONT_PATH).ok().and_then(|font| FontVec::try_from_vec(font).ok());
+    if font.is_none() {
+        log::warn!("couldn't load the font from {FONT_PATH}, images will have no labels");
+    }
+    font
+});
 
-#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)] // multiple lossy numeric conversions
 pub fn collage(images: Vec<DynamicImage>, image_size: (u32, u32), gap: u32) -> DynamicImage {
+    let max_dimension = env::var("MAX_COLLAGE_DIMENSION")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(MAX_COLLAGE_DIMENSION);
+
+    collage_with_limit(images, image_size, gap, max_dimension)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss, clippy::cast_sign_loss)] // multiple lossy numeric conversions
+fn collage_with_limit(
+    mut images: Vec<DynamicImage>,
+    mut image_size: (u32, u32),
+    gap: u32,
+    max_dimension: u32,
+) -> DynamicImage {
     let image_count_x = (images.len() as f32).sqrt().ceil() as u32;
     let image_count_y = (images.len() as f32 / image_count_x as f32).ceil() as u32;
+    let collage_size = |image_size: (u32, u32)| {
+        (
+            image_count_x * image_size.0 + (image_count_x - 1) * gap,
+            image_count_y * image_size.1 + (image_count_y - 1) * gap,
+        )
+    };
 
-    let mut base = DynamicImage::new_rgb8(
-        image_count_x * image_size.0 + (image_count_x - 1) * gap,
-        image_count_y * image_size.1 + (image_count_y - 1) * gap,
-    );
+    let (width, height) = collage_size(image_size);
+    let max_pixels = u64::from(max_dimension).pow(2);
+    let pixels = u64::from(width) * u64::from(height);
+
+    if pixels > max_pixels {
+        // solves (x * scale + gaps_x) * (y * scale + gaps_y) = max_pixels, as the gaps
+        // aren't scaled
+        let (images_x, images_y) =
+            (f64::from(image_count_x * image_size.0), f64::from(image_count_y * image_size.1));
+        let (gaps_x, gaps_y) =
+            (f64::from((image_count_x - 1) * gap), f64::from((image_count_y - 1) * gap));
+        let a = images_x * images_y;
+        let b = images_x.mul_add(gaps_y, images_y * gaps_x);
+        let c = gaps_x.mul_add(gaps_y, -(max_pixels as f64));
+        let scale = (-b + b.mul_add(b, -4.0 * a * c).sqrt()) / (2.0 * a);
+        let scaled = |size: u32| ((f64::from(size) * scale) as u32).max(1);
+
+        image_size = (scaled(image_size.0), scaled(image_size.1));
+        images = images
+            .into_iter()
+            .map(|image| {
+                image.resize_exact(
+                    scaled(image.width()),
+                    scaled(image.height()),
+                    FilterType::Triangle,
+                )
+            })
+            .collect();
+
+        let (reduced_width, reduced_height) = collage_size(image_size);
+        log::warn!(
+            "collage of {width}x{height} exceeds the limit, reduced to {reduced_width}x{reduced_height}"
+        );
+    }
+
+    let (width, height) = collage_size(image_size);
+    let mut base = DynamicImage::new_rgb8(width, height);
 
     for (image, i) in images.into_iter().zip(0..) {
         let col = i % image_count_x;
@@ -20,3 +102,193 @@ pub fn collage(images: Vec<DynamicImage>, image_size: (u32, u32), gap: u32) -> D
 
     base
 }
+
+/// places the images next to each other, aligned to the top
+pub fn side_by_side(images: Vec<DynamicImage>, gap: u32) -> DynamicImage {
+    let gaps = u32::try_from(images.len()).unwrap().saturating_sub(1) * gap;
+    let width = images.iter().map(DynamicImage::width).sum::<u32>() + gaps;
+    let height = images.iter().map(DynamicImage::height).max().unwrap_or_default();
+
+    let mut base = DynamicImage::new_rgb8(width, height);
+    let mut x = 0;
+
+    for image in images {
+        imageops::overlay(&mut base, &image, i64::from(x), 0);
+        x += image.width() + gap;
+    }
+
+    base
+}
+
+/// adds a white strip with the label above the image
+pub fn add_label(image: &DynamicImage, label: &str) -> DynamicImage {
+    let mut labeled =
+        RgbImage::from_pixel(image.width(), image.height() + LABEL_HEIGHT, Rgb([255, 255, 255]));
+    imageops::overlay(&mut labeled, &image.to_rgb8(), 0, i64::from(LABEL_HEIGHT));
+
+    if let Some(font) = FONT.as_ref() {
+        drawing::draw_text_mut(&mut labeled, Rgb([0, 0, 0]), 8, 4, 22.0, font, label);
+    }
+
+    DynamicImage::ImageRgb8(labeled)
+}
+
+/// scales the image to fit a `size`×`size` square and centers it on a
+/// transparent background
+pub fn fit_square(image: &DynamicImage, size: u32) -> RgbaImage {
+    // JPEGs have no alpha channel, convert before resizing so the padding stays
+    // transparent
+    let image = DynamicImage::ImageRgba8(image.to_rgba8()).resize(size, size, FilterType::Lanczos3);
+    let mut base = RgbaImage::from_pixel(size, size, Rgba([0, 0, 0, 0]));
+
+    imageops::overlay(
+        &mut base,
+        &image,
+        i64::from((size - image.width()) / 2),
+        i64::from((size - image.height()) / 2),
+    );
+
+    base
+}
+
+/// finds the `count` most common colors using a median cut variant, sorted from
+/// the most dominant
+pub fn dominant_colors(image: &RgbImage, count: usize) -> Vec<Rgb<u8>> {
+    let mut buckets = vec![image.pixels().copied().collect::<Vec<_>>()];
+
+    while buckets.len() < count {
+        // split the bucket with the widest channel range along that channel
+        let Some((index, channel)) = buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.len() > 1)
+            .flat_map(|(index, bucket)| {
+                (0..3).map(move |channel| {
+                    let values = bucket.iter().map(|pixel| pixel[channel]);
+                    let range = values.clone().max().unwrap() - values.min().unwrap();
+                    (range, index, channel)
+                })
+            })
+            .filter(|(range, ..)| *range > 0)
+            .max_by_key(|(range, ..)| *range)
+            .map(|(_, index, channel)| (index, channel))
+        else {
+            break;
+        };
+
+        // cutting at the middle of the range instead of the median pixel
+        // keeps large areas of a single color from being averaged with others
+        let values = buckets[index].iter().map(|pixel| pixel[channel]);
+        let (min, max) = (values.clone().min().unwrap(), values.max().unwrap());
+        let middle = min + (max - min) / 2;
+
+        let (lower, upper): (Vec<_>, Vec<_>) =
+            mem::take(&mut buckets[index]).into_iter().partition(|pixel| pixel[channel] <= middle);
+        buckets[index] = lower;
+        buckets.push(upper);
+    }
+
+    buckets.sort_unstable_by_key(|bucket| Reverse(bucket.len()));
+    buckets
+        .into_iter()
+        .filter(|bucket| !bucket.is_empty())
+        .map(|bucket| {
+            let mut sums = [0u64; 3];
+            for pixel in &bucket {
+                for (sum, value) in sums.iter_mut().zip(pixel.0) {
+                    *sum += u64::from(value);
+                }
+            }
+            let len = u64::try_from(bucket.len()).unwrap();
+            Rgb(sums.map(|sum| u8::try_from(sum / len).unwrap()))
+        })
+        .collect()
+}
+
+/// decodes an image, detecting its format from the magic bytes
+pub fn load_image(bytes: &[u8]) -> ImageResult<DynamicImage> {
+    let format = image::guess_format(bytes)?;
+    log::debug!("decoding a {format:?} image");
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODED_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODED_DIMENSION);
+    limits.max_alloc = Some(MAX_DECODED_ALLOC);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    reader.decode()
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use image::ImageFormat;
+
+    use super::*;
+
+    fn encode(format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::new_rgb8(4, 3).write_to(&mut Cursor::new(&mut bytes), format).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_load_image() {
+        for format in [ImageFormat::Jpeg, ImageFormat::Png] {
+            let image = load_image(&encode(format)).unwrap();
+            assert_eq!((image.width(), image.height()), (4, 3), "{format:?}");
+        }
+
+        assert!(load_image(b"not an image").is_err());
+
+        let mut too_wide = Vec::new();
+        DynamicImage::new_rgb8(MAX_DECODED_DIMENSION + 1, 1)
+            .write_to(&mut Cursor::new(&mut too_wide), ImageFormat::Png)
+            .unwrap();
+        assert!(load_image(&too_wide).is_err());
+    }
+
+    #[test]
+    fn test_fit_square() {
+        let image = fit_square(&DynamicImage::new_rgb8(200, 100), 512);
+        assert_eq!(image.dimensions(), (512, 512));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(256, 256).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_add_label() {
+        let image = add_label(&DynamicImage::new_rgb8(100, 50), "label").to_rgb8();
+        assert_eq!(image.dimensions(), (100, 50 + LABEL_HEIGHT));
+        assert_eq!(image.get_pixel(99, 0).0, [255, 255, 255]);
+        assert_eq!(image.get_pixel(0, LABEL_HEIGHT).0, [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_dominant_colors() {
+        let mut image = RgbImage::from_pixel(10, 10, Rgb([255, 0, 0]));
+        for x in 0..3 {
+            for y in 0..10 {
+                image.put_pixel(x, y, Rgb([0, 0, 255]));
+            }
+        }
+
+        assert_eq!(dominant_colors(&image, 2), [Rgb([255, 0, 0]), Rgb([0, 0, 255])]);
+        assert_eq!(dominant_colors(&RgbImage::new(4, 4), 5), [Rgb([0, 0, 0])]);
+    }
+
+    #[test]
+    fn test_collage_limit() {
+        let images = vec![DynamicImage::new_rgb8(512, 512); 4];
+
+        let image = collage_with_limit(images.clone(), (512, 512), 8, 4096);
+        assert_eq!((image.width(), image.height()), (1032, 1032));
+
+        let image = collage_with_limit(images, (512, 512), 8, 516);
+        assert!(u64::from(image.width()) * u64::from(image.height()) <= 516 * 516);
+        assert!((510..=516).contains(&image.width()));
+        assert_eq!(image.width(), image.height());
+    }
+}
@@ -0,0 +1,51 @@
+use tdlib::enums::{InlineKeyboardButtonType, ReplyMarkup};
+use tdlib::types::{InlineKeyboardButton, InlineKeyboardButtonTypeUrl, ReplyMarkupInlineKeyboard};
+
+use super::telegram_utils;
+
+/// builds inline keyboards row by row, buttons added before the first `row`
+/// start a new row
+#[derive(Default)]
+pub struct KeyboardBuilder {
+    rows: Vec<Vec<InlineKeyboardButton>>,
+}
+
+impl KeyboardBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn row(mut self) -> Self {
+        self.rows.push(Vec::new());
+        self
+    }
+
+    pub fn url_button(self, text: impl Into<String>, url: impl Into<String>) -> Self {
+        self.button(InlineKeyboardButton {
+            text: text.into(),
+            r#type: InlineKeyboardButtonType::Url(InlineKeyboardButtonTypeUrl { url: url.into() }),
+        })
+    }
+
+    pub fn callback_button(self, text: impl Into<String>, data: &str) -> Self {
+        self.button(telegram_utils::callback_button(text, data))
+    }
+
+    pub fn buttons(self, buttons: impl IntoIterator<Item = InlineKeyboardButton>) -> Self {
+        buttons.into_iter().fold(self, Self::button)
+    }
+
+    pub fn build(self) -> ReplyMarkup {
+        ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard {
+            rows: self.rows.into_iter().filter(|row| !row.is_empty()).collect(),
+        })
+    }
+
+    fn button(mut self, button: InlineKeyboardButton) -> Self {
+        match self.rows.last_mut() {
+            Some(row) => row.push(button),
+            None => self.rows.push(vec![button]),
+        }
+        self
+    }
+}
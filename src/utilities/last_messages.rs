@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tdlib::enums::InputMessageContent;
+use tdlib::types::FormattedText;
+
+/// Telegram silently drops a bot message identical to the previous one
+const DEDUP_SUFFIX: char = '\u{200B}';
+
+/// text of the last message the bot sent in each chat
+#[derive(Default)]
+pub struct LastMessages {
+    messages: Mutex<HashMap<i64, String>>,
+}
+
+impl LastMessages {
+    /// appends a zero width space to the text if it's the same as the last
+    /// message sent in the chat
+    pub fn dedup(&self, chat_id: i64, content: &mut InputMessageContent) {
+        let InputMessageContent::InputMessageText(message) = content else {
+            return;
+        };
+
+        if self.messages.lock().unwrap().get(&chat_id) == Some(&message.text.text) {
+            dedup_text(&mut message.text);
+        }
+    }
+
+    pub fn record(&self, chat_id: i64, content: &InputMessageContent) {
+        let mut messages = self.messages.lock().unwrap();
+
+        if let InputMessageContent::InputMessageText(message) = content {
+            messages.insert(chat_id, message.text.text.clone());
+        } else {
+            messages.remove(&chat_id);
+        }
+    }
+}
+
+fn dedup_text(text: &mut FormattedText) {
+    // the suffix goes after every entity so their offsets stay valid
+    text.text.push(DEDUP_SUFFIX);
+}
+
+#[cfg(test)]
+mod test {
+    use tdlib::types::InputMessageText;
+
+    use super::*;
+
+    fn text_content(text: &str) -> InputMessageContent {
+        InputMessageContent::InputMessageText(InputMessageText {
+            text: FormattedText { text: text.into(), ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    fn text(content: &InputMessageContent) -> &str {
+        let InputMessageContent::InputMessageText(message) = content else { unreachable!() };
+        &message.text.text
+    }
+
+    #[test]
+    fn test_dedup() {
+        let last_messages = LastMessages::default();
+
+        let mut content = text_content("hello");
+        last_messages.dedup(1, &mut content);
+        assert_eq!(text(&content), "hello");
+        last_messages.record(1, &content);
+
+        let mut content = text_content("hello");
+        last_messages.dedup(1, &mut content);
+        assert_eq!(text(&content), "hello\u{200B}");
+        last_messages.record(1, &content);
+
+        let mut content = text_content("hello");
+        last_messages.dedup(1, &mut content);
+        assert_eq!(text(&content), "hello");
+
+        let mut content = text_content("hello");
+        last_messages.dedup(2, &mut content);
+        assert_eq!(text(&content), "hello");
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// a cache evicting the least recently used entries once full and entries older
+/// than `ttl`
+pub struct LruCache<K, V> {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<K, (V, Instant)>,
+    /// keys from the least to the most recently used
+    order: VecDeque<K>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let (value, inserted_at) = self.entries.get(key)?;
+
+        if inserted_at.elapsed() >= self.ttl {
+            self.remove(key);
+            return None;
+        }
+
+        let value = value.clone();
+        self.touch(key);
+
+        Some(value)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.entries.insert(key.clone(), (value, Instant::now())).is_some() {
+            self.touch(&key);
+            return;
+        }
+
+        self.order.push_back(key);
+
+        while self.order.len() > self.capacity {
+            if let Some(key) = self.order.pop_front() {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, _) = self.entries.remove(key)?;
+        self.order.retain(|other| other != key);
+
+        Some(value)
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(position) = self.order.iter().position(|other| other == key) {
+            let key = self.order.remove(position).unwrap();
+            self.order.push_back(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::LruCache;
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = LruCache::new(2, Duration::from_secs(60));
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+
+        // "b" is the least recently used entry now
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+
+        cache.insert("a", 4);
+        assert_eq!(cache.get(&"a"), Some(4));
+        assert_eq!(cache.remove(&"a"), Some(4));
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.remove(&"a"), None);
+    }
+
+    #[test]
+    fn test_lru_cache_ttl() {
+        let mut cache = LruCache::new(2, Duration::ZERO);
+        cache.insert("a", 1);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.remove(&"a"), None);
+    }
+}
@@ -21,6 +21,7 @@ pub enum Entity<'a> {
     Bold(Vec<Entity<'a>>),
     Italic(Vec<Entity<'a>>),
     Code(Vec<Entity<'a>>),
+    Pre(Vec<Entity<'a>>),
     TextUrl { text: Vec<Entity<'a>>, url: Cow<'a, str> },
 }
 
@@ -60,6 +61,7 @@ pub trait ToEntityOwned<'a> {
     fn bold_owned(self) -> Entity<'a>;
     fn italic_owned(self) -> Entity<'a>;
     fn code_owned(self) -> Entity<'a>;
+    fn pre_owned(self) -> Entity<'a>;
     fn text_url_owned(self, url: impl Into<Cow<'a, str>>) -> Entity<'a>;
 }
 
@@ -80,6 +82,10 @@ impl<'a> ToEntityOwned<'a> for String {
         Entity::Text(self.into()).code()
     }
 
+    fn pre_owned(self) -> Entity<'a> {
+        Entity::Pre(vec![Entity::Text(self.into())])
+    }
+
     fn text_url_owned(self, url: impl Into<Cow<'a, str>>) -> Entity<'a> {
         Entity::Text(self.into()).text_url(url)
     }
@@ -135,6 +141,10 @@ fn format_entities(
                 let ret = format_entities(text, entities, offset);
                 (ret.0, ret.1, ret.2, Some(TextEntityType::Code))
             }
+            Entity::Pre(entities) => {
+                let ret = format_entities(text, entities, offset);
+                (ret.0, ret.1, ret.2, Some(TextEntityType::Pre))
+            }
             Entity::TextUrl { text: entities, url } => {
                 let ret = format_entities(text, entities, offset);
                 (
@@ -225,4 +235,18 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn test_pre_formatted_text() {
+        let formatted_text =
+            formatted_text(vec!["table:\n".text(), String::from("a  1\nb  2").pre_owned()]);
+
+        assert_eq!(
+            formatted_text,
+            FormattedText {
+                text: "table:\na  1\nb  2".into(),
+                entities: vec![TextEntity { offset: 7, length: 9, r#type: TextEntityType::Pre }]
+            }
+        );
+    }
 }
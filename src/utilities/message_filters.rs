@@ -9,9 +9,11 @@ use super::command_manager::CommandInstance;
 use super::parsed_command::ParsedCommand;
 use super::telegram_utils;
 use crate::bot::Bot;
+use crate::commands::{Privilege, OWNER_ID};
 
 pub enum MessageDestination {
     Command { command: Arc<CommandInstance>, arguments: String, context: CommandContext },
+    CommandSuggestion { suggestion: String, context: CommandContext },
     Dice { message: Message },
     MarkovChain { text: String },
 }
@@ -52,6 +54,10 @@ pub fn message_destination(
     };
 
     if let Some(parsed_command) = ParsedCommand::parse(text) {
+        if bot_state.config.lock().unwrap().blacklist.contains(&user_id) {
+            return None; // ignore commands from blacklisted users
+        }
+
         if let Some(bot_username) = &parsed_command.bot_username {
             let Some(me) = bot.get_me() else {
                 log::warn!("client user not cached");
@@ -66,13 +72,41 @@ pub fn message_destination(
         }
 
         let Some(command) = bot.get_command(&parsed_command.name) else {
-            return None; // ignore nonexistent commands
+            let ChatType::Private(_) = chat.r#type else {
+                return None; // ignore nonexistent commands in groups
+            };
+
+            // group admin commands can't be used in private chats
+            let privileges = if user_id == OWNER_ID {
+                [Privilege::User, Privilege::Owner].as_slice()
+            } else {
+                &[Privilege::User]
+            };
+            let suggestion = bot.suggest_command(&parsed_command.name, privileges)?;
+            return Some(MessageDestination::CommandSuggestion {
+                suggestion,
+                context: CommandContext {
+                    client_id: bot.client_id,
+                    chat,
+                    user,
+                    message,
+                    bot_state,
+                    command_manager: bot.command_manager.clone(),
+                },
+            });
         };
 
         Some(MessageDestination::Command {
             command,
             arguments: parsed_command.arguments,
-            context: CommandContext { client_id: bot.client_id, chat, user, message, bot_state },
+            context: CommandContext {
+                client_id: bot.client_id,
+                chat,
+                user,
+                message,
+                bot_state,
+                command_manager: bot.command_manager.clone(),
+            },
         })
     } else {
         let (ChatType::BasicGroup(_) | ChatType::Supergroup(_)) = chat.r#type else {
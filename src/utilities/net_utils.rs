@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{fmt, iter};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+use url::Host;
+
+/// whether the address is globally routable, so that users can't probe the
+/// bot's network
+pub const fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+const fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        // shared address space, 100.64.0.0/10
+        || (a == 100 && b & 0b1100_0000 == 64)
+        // reserved, 240.0.0.0/4
+        || a >= 240)
+}
+
+const fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return is_public_ipv4(ip);
+    }
+
+    let first_segment = ip.segments()[0];
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // unique local, fc00::/7
+        || first_segment & 0xfe00 == 0xfc00
+        // link-local, fe80::/10
+        || first_segment & 0xffc0 == 0xfe80
+        // documentation, 2001:db8::/32
+        || (first_segment == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+/// whether the URL's host isn't a non-public IP address. domains can't be
+/// checked here, as they could resolve differently when connecting, so
+/// [`PublicResolver`] checks them instead
+pub fn is_public_host(url: &Url) -> bool {
+    match url.host() {
+        Some(Host::Domain(_)) => true,
+        Some(Host::Ipv4(ip)) => is_public_ipv4(ip),
+        Some(Host::Ipv6(ip)) => is_public_ipv6(ip),
+        None => false,
+    }
+}
+
+/// a DNS resolver which only returns public addresses, so that requests to
+/// user-supplied URLs can't reach the bot's network
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|address| is_public_ip(address.ip()))
+                .collect::<Vec<_>>();
+
+            if addresses.is_empty() {
+                return Err(NonPublicAddress.into());
+            }
+
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct NonPublicAddress;
+
+impl fmt::Display for NonPublicAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the host doesn't resolve to a public IP address")
+    }
+}
+
+impl Error for NonPublicAddress {}
+
+/// whether the request failed because [`PublicResolver`] found no public
+/// addresses
+pub fn is_non_public_address(err: &reqwest::Error) -> bool {
+    iter::successors(err.source(), |&err| err.source()).any(<dyn Error>::is::<NonPublicAddress>)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["1.1.1.1", "8.8.8.8", "2606:4700:4700::1111", "::ffff:1.1.1.1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+
+        for ip in [
+            "0.0.0.0",
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_is_public_host() {
+        for url in ["https://example.com/", "http://1.1.1.1/", "http://[2606:4700:4700::1111]/"] {
+            assert!(is_public_host(&Url::parse(url).unwrap()), "{url}");
+        }
+
+        for url in ["http://127.0.0.1/", "http://2130706433/", "http://[::1]/", "data:,"] {
+            assert!(!is_public_host(&Url::parse(url).unwrap()), "{url}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_public_resolver() {
+        let http_client =
+            reqwest::Client::builder().dns_resolver(Arc::new(PublicResolver)).build().unwrap();
+
+        let err = http_client.get("http://localhost:1/").send().await.unwrap_err();
+        assert!(is_non_public_address(&err));
+    }
+}
@@ -0,0 +1,99 @@
+use tdlib::enums::{InputMessageContent, ReplyMarkup};
+use tdlib::types::{FormattedText, InputMessageText, LinkPreviewOptions, Message};
+
+use super::callback_dispatcher::CallbackContext;
+use super::command_context::CommandContext;
+use super::keyboard::KeyboardBuilder;
+use crate::commands::{CommandError, CommandResult};
+
+pub struct Paginator {
+    pub pages: Vec<String>,
+}
+
+impl Paginator {
+    /// splits lines into pages with a shared header
+    pub fn from_lines(header: &str, lines: &[String], lines_per_page: usize) -> Self {
+        Self {
+            pages: lines
+                .chunks(lines_per_page)
+                .map(|lines| format!("{header}\n{}", lines.join("\n")))
+                .collect(),
+        }
+    }
+
+    pub async fn send_first_page(self, ctx: &CommandContext) -> Result<Message, CommandError> {
+        if self.pages.len() <= 1 {
+            return Ok(ctx.reply(self.pages.into_iter().next().unwrap_or_default()).await?);
+        }
+
+        // callback data is limited to 64 bytes, so the pages are stored under a short
+        // hash
+        let hash = format!("{:x}", md5::compute(&self.pages[0]));
+        let text = format_page(&self.pages, 0);
+        let markup = page_markup(&hash, 0, self.pages.len());
+        ctx.bot_state.cache.lock().unwrap().set_pages(hash, self.pages);
+
+        Ok(ctx
+            .reply_custom(
+                InputMessageContent::InputMessageText(InputMessageText {
+                    text,
+                    link_preview_options: Some(LinkPreviewOptions {
+                        is_disabled: true,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                Some(markup),
+            )
+            .await?)
+    }
+}
+
+pub async fn callback(ctx: &CallbackContext, arguments: &str) -> CommandResult {
+    let mut arguments = arguments.split(':');
+    let (Some(direction), Some(hash), Some(Ok(page))) =
+        (arguments.next(), arguments.next(), arguments.next().map(str::parse::<usize>))
+    else {
+        ctx.answer("", false).await?;
+        return Ok(());
+    };
+
+    let pages = ctx.bot_state.cache.lock().unwrap().get_pages(hash);
+    let Some(pages) = pages else {
+        ctx.answer("this list has expired, use the command again.", false).await?;
+        return Ok(());
+    };
+
+    // the page comes from the callback data, so it can be out of range
+    let page = match direction {
+        "prev" => page.saturating_sub(1),
+        _ => page.saturating_add(1),
+    }
+    .min(pages.len() - 1);
+
+    ctx.edit_message(format_page(&pages, page), Some(page_markup(hash, page, pages.len()))).await?;
+    ctx.answer("", false).await?;
+
+    Ok(())
+}
+
+fn format_page(pages: &[String], page: usize) -> FormattedText {
+    FormattedText {
+        text: format!("{}\n\npage {}/{}", pages[page], page + 1, pages.len()),
+        ..Default::default()
+    }
+}
+
+fn page_markup(hash: &str, page: usize, page_count: usize) -> ReplyMarkup {
+    let mut keyboard = KeyboardBuilder::new();
+
+    if page > 0 {
+        keyboard = keyboard.callback_button("◀", &format!("page:prev:{hash}:{page}"));
+    }
+
+    if page + 1 < page_count {
+        keyboard = keyboard.callback_button("▶", &format!("page:next:{hash}:{page}"));
+    }
+
+    keyboard.build()
+}
@@ -1,6 +1,18 @@
+use std::env;
+use std::sync::LazyLock;
+
 use tdlib::enums::TextEntityType;
 use tdlib::types::FormattedText;
 
+/// commands are prefixed with `/` unless `COMMAND_PREFIX` is set, so the bot
+/// can share a group with other bots
+pub static COMMAND_PREFIX: LazyLock<String> = LazyLock::new(|| {
+    env::var("COMMAND_PREFIX")
+        .ok()
+        .filter(|prefix| !prefix.is_empty())
+        .unwrap_or_else(|| "/".into())
+});
+
 pub struct ParsedCommand {
     pub name: String,
     pub bot_username: Option<String>,
@@ -9,6 +21,23 @@ pub struct ParsedCommand {
 
 impl ParsedCommand {
     pub fn parse(formatted_text: &FormattedText) -> Option<Self> {
+        Self::parse_with_prefix(formatted_text, &COMMAND_PREFIX)
+    }
+
+    fn parse_with_prefix(formatted_text: &FormattedText, prefix: &str) -> Option<Self> {
+        // Telegram only marks commands starting with a slash
+        if prefix != "/" {
+            let text = formatted_text.text.strip_prefix(prefix)?;
+            let (command, arguments) =
+                text.split_at(text.find(char::is_whitespace).unwrap_or(text.len()));
+
+            if command.is_empty() {
+                return None;
+            }
+
+            return Some(Self::from_parts(command, arguments));
+        }
+
         let entity = formatted_text
             .entities
             .iter()
@@ -20,17 +49,61 @@ impl ParsedCommand {
             start..entity.length as usize
         };
 
-        let command = &formatted_text.text[command_name_range.clone()];
+        Some(Self::from_parts(
+            &formatted_text.text[command_name_range.clone()],
+            &formatted_text.text[command_name_range.end..],
+        ))
+    }
 
+    fn from_parts(command: &str, arguments: &str) -> Self {
         let (command_name, username) =
             command.split_once('@').map_or((command, None), |parts| (parts.0, Some(parts.1)));
 
-        let arguments = formatted_text.text[command_name_range.end..].trim_start().into();
-
-        Some(Self {
+        Self {
             name: command_name.to_lowercase(),
             bot_username: username.map(str::to_string),
-            arguments,
-        })
+            arguments: arguments.trim_start().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tdlib::types::TextEntity;
+
+    use super::*;
+
+    fn text(text: &str, entities: Vec<TextEntity>) -> FormattedText {
+        FormattedText { text: text.into(), entities }
+    }
+
+    #[test]
+    fn test_parse_with_prefix() {
+        let command = ParsedCommand::parse_with_prefix(
+            &text(
+                "/sd@craiyonbot  a cat",
+                vec![TextEntity { offset: 0, length: 14, r#type: TextEntityType::BotCommand }],
+            ),
+            "/",
+        )
+        .unwrap();
+        assert_eq!(command.name, "sd");
+        assert_eq!(command.bot_username.as_deref(), Some("craiyonbot"));
+        assert_eq!(command.arguments, "a cat");
+
+        assert!(ParsedCommand::parse_with_prefix(&text("/sd a cat", Vec::new()), "/").is_none());
+
+        let command =
+            ParsedCommand::parse_with_prefix(&text("./SD a cat", Vec::new()), "./").unwrap();
+        assert_eq!(command.name, "sd");
+        assert_eq!(command.bot_username, None);
+        assert_eq!(command.arguments, "a cat");
+
+        let command = ParsedCommand::parse_with_prefix(&text("!ping", Vec::new()), "!").unwrap();
+        assert_eq!(command.name, "ping");
+        assert_eq!(command.arguments, "");
+
+        assert!(ParsedCommand::parse_with_prefix(&text("! ping", Vec::new()), "!").is_none());
+        assert!(ParsedCommand::parse_with_prefix(&text("/ping", Vec::new()), "!").is_none());
     }
 }
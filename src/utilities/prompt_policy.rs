@@ -0,0 +1,106 @@
+use std::path::Path;
+use std::sync::LazyLock;
+use std::{env, fs};
+
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+
+const DEFAULT_PATH: &str = "config/prompt_policy.toml";
+
+/// set with the file at `PROMPT_POLICY_FILE`, defaults to
+/// `config/prompt_policy.toml` when unset or empty, missing files use the
+/// default policy
+pub static PROMPT_POLICY: LazyLock<PromptPolicy> = LazyLock::new(|| {
+    let path = env::var("PROMPT_POLICY_FILE")
+        .ok()
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| DEFAULT_PATH.into());
+    PromptPolicy::load(Path::new(&path))
+        .unwrap_or_else(|err| panic!("invalid prompt policy in {path}: {err}"))
+});
+
+/// limits for prompts of the image generation commands
+#[derive(Deserialize)]
+#[serde(try_from = "RawPromptPolicy")]
+pub struct PromptPolicy {
+    pub max_chars: usize,
+    pub max_lines: usize,
+    pub min_chars: usize,
+    /// case-insensitive patterns rejecting any prompt they match
+    pub banned_patterns: Vec<Regex>,
+}
+
+impl Default for PromptPolicy {
+    fn default() -> Self {
+        Self { max_chars: 1024, max_lines: 8, min_chars: 0, banned_patterns: Vec::new() }
+    }
+}
+
+impl PromptPolicy {
+    fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            log::debug!("no prompt policy at {}, using the default one", path.display());
+            return Ok(Self::default());
+        }
+
+        log::debug!("loading prompt policy from {}", path.display());
+        let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        toml::from_str(&text).map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct RawPromptPolicy {
+    max_chars: usize,
+    max_lines: usize,
+    min_chars: usize,
+    banned_patterns: Vec<String>,
+}
+
+impl Default for RawPromptPolicy {
+    fn default() -> Self {
+        let policy = PromptPolicy::default();
+        Self {
+            max_chars: policy.max_chars,
+            max_lines: policy.max_lines,
+            min_chars: policy.min_chars,
+            banned_patterns: Vec::new(),
+        }
+    }
+}
+
+impl TryFrom<RawPromptPolicy> for PromptPolicy {
+    type Error = regex::Error;
+
+    fn try_from(value: RawPromptPolicy) -> Result<Self, Self::Error> {
+        Ok(Self {
+            max_chars: value.max_chars,
+            max_lines: value.max_lines,
+            min_chars: value.min_chars,
+            banned_patterns: value
+                .banned_patterns
+                .iter()
+                .map(|pattern| RegexBuilder::new(pattern).case_insensitive(true).build())
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PromptPolicy;
+
+    #[test]
+    fn test_parse_prompt_policy() {
+        let policy = toml::from_str::<PromptPolicy>(
+            "max_chars = 512\nbanned_patterns = ['\\bforbidden\\b']",
+        )
+        .unwrap();
+        assert_eq!((policy.max_chars, policy.max_lines, policy.min_chars), (512, 8, 0));
+        assert!(policy.banned_patterns[0].is_match("a FORBIDDEN cat"));
+        assert!(!policy.banned_patterns[0].is_match("unforbiddenness"));
+
+        assert!(toml::from_str::<PromptPolicy>("banned_patterns = ['(']").is_err());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::env;
 use std::hash::Hash;
+use std::str::FromStr;
 
 pub struct RateLimiter<K> {
     limit: usize,
@@ -13,30 +15,143 @@ impl<K: Eq + Hash> RateLimiter<K> {
     }
 
     pub fn update_rate_limit(&mut self, key: K, time: i32) -> Option<i32> {
-        if let Some(value) = self.history.get_mut(&key) {
-            let cooldown = if value.len() >= self.limit {
-                value.truncate(self.limit);
-                let last_time = *value.last().unwrap();
-                if time - last_time < self.duration {
-                    Some(self.duration - (time - last_time))
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            if cooldown.is_none() {
-                value.insert(0, time);
-            }
-            cooldown
-        } else {
+        self.update(key, time, self.limit)
+    }
+
+    /// like [`Self::update_rate_limit`], but allows only half of the uses
+    pub fn update_rate_limit_strict(&mut self, key: K, time: i32) -> Option<i32> {
+        self.update(key, time, (self.limit / 2).max(1))
+    }
+
+    fn update(&mut self, key: K, time: i32, limit: usize) -> Option<i32> {
+        let Some(history) = self.history.get_mut(&key) else {
             self.history.insert(key, vec![time]);
+            return None;
+        };
+
+        // the strict limit shares the history, so it has to keep enough uses
+        // for the normal one
+        history.truncate(self.limit);
+        let cooldown = history
+            .get(limit - 1)
+            .map(|last_time| self.duration - (time - last_time))
+            .filter(|&cooldown| cooldown > 0);
+
+        if cooldown.is_none() {
+            history.insert(0, time);
+        }
+        cooldown
+    }
+}
+
+/// builds a command's rate limiter, `RATE_LIMIT_{COMMAND_NAME}_COUNT` and
+/// `RATE_LIMIT_{COMMAND_NAME}_WINDOW_SECS` override the defaults
+pub fn rate_limit_from_env(
+    command_name: &str,
+    default_count: u32,
+    default_window: u64,
+) -> RateLimiter<i64> {
+    rate_limit_from_vars(command_name, default_count, default_window, |name| env::var(name).ok())
+}
+
+fn rate_limit_from_vars(
+    command_name: &str,
+    default_count: u32,
+    default_window: u64,
+    var: impl Fn(&str) -> Option<String>,
+) -> RateLimiter<i64> {
+    let prefix = format!("RATE_LIMIT_{}", command_name.to_ascii_uppercase());
+    let count_name = format!("{prefix}_COUNT");
+    let count = match parse_value(&count_name, var(&count_name)) {
+        // a limiter without any allowed uses would have no history to compare against
+        Some(0) => {
+            log::warn!("ignoring invalid {count_name}: 0, it has to be at least 1");
             None
         }
+        count => count,
+    }
+    .unwrap_or(default_count);
+    let window_name = format!("{prefix}_WINDOW_SECS");
+    let window = parse_value(&window_name, var(&window_name)).unwrap_or(default_window);
+
+    RateLimiter::new(
+        usize::try_from(count).unwrap_or(usize::MAX),
+        i32::try_from(window).unwrap_or(i32::MAX),
+    )
+}
+
+fn parse_value<T: FromStr>(name: &str, value: Option<String>) -> Option<T> {
+    let value = value?;
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        log::warn!("ignoring invalid {name}: {value:?}");
     }
+    parsed
 }
 
 pub struct RateLimits {
     pub rate_limit_exceeded: RateLimiter<i64>,
+    /// replies to denied commands, e.g. during maintenance
+    pub access_denied: RateLimiter<i64>,
+    /// re-runs from /history, separate from the commands' own rate limits
+    pub history_rerun: RateLimiter<i64>,
+    pub inline_generation: RateLimiter<i64>,
+    /// the last command each user was rate limited on and when the cooldown
+    /// ends
+    pub cooldowns: HashMap<i64, (String, i32)>,
+}
+
+impl RateLimits {
+    pub fn record_cooldown(&mut self, user_id: i64, command: String, time: i32, cooldown: i32) {
+        self.cooldowns.retain(|_, (_, ends_at)| *ends_at > time);
+        self.cooldowns.insert(user_id, (command, time + cooldown));
+    }
+
+    /// returns the command and the remaining seconds if the user is on a
+    /// cooldown
+    pub fn active_cooldown(&self, user_id: i64, time: i32) -> Option<(&str, i32)> {
+        self.cooldowns
+            .get(&user_id)
+            .filter(|(_, ends_at)| *ends_at > time)
+            .map(|(command, ends_at)| (command.as_str(), ends_at - time))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::{rate_limit_from_vars, RateLimiter};
+
+    #[test]
+    fn test_rate_limit_strict() {
+        let mut rate_limit = RateLimiter::new(4, 60);
+        for time in 0..3 {
+            assert_eq!(rate_limit.update_rate_limit(0, time), None);
+        }
+
+        // only 2 uses are allowed in groups, but that doesn't forget the others
+        assert_eq!(rate_limit.update_rate_limit_strict(0, 3), Some(58));
+        assert_eq!(rate_limit.update_rate_limit(0, 4), None);
+        assert_eq!(rate_limit.update_rate_limit(0, 5), Some(55));
+    }
+
+    #[test]
+    fn test_rate_limit_from_vars() {
+        let vars = HashMap::from([
+            ("RATE_LIMIT_TEST_COUNT", "10"),
+            ("RATE_LIMIT_TEST_WINDOW_SECS", "invalid"),
+            ("RATE_LIMIT_TEST_ZERO_COUNT", "0"),
+        ]);
+        let var = |name: &str| vars.get(name).map(ToString::to_string);
+
+        let rate_limit = rate_limit_from_vars("test_default", 3, 300, var);
+        assert_eq!((rate_limit.limit, rate_limit.duration), (3, 300));
+
+        let rate_limit = rate_limit_from_vars("test", 3, 300, var);
+        assert_eq!((rate_limit.limit, rate_limit.duration), (10, 300));
+
+        let rate_limit = rate_limit_from_vars("test_zero", 3, 300, var);
+        assert_eq!(rate_limit.limit, 3);
+    }
 }
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::bot::{TdError, TdResult};
+
+/// retrying more times would only make Telegram extend the wait
+const MAX_ATTEMPTS: usize = 3;
+/// longer flood waits fail the call instead of stalling the chat's messages
+const MAX_FLOOD_WAIT_SECS: u64 = 60;
+
+/// delays outgoing messages to chats for which Telegram requested a flood
+/// wait
+#[derive(Default)]
+pub struct TelegramRateLimiter {
+    cooldowns: Mutex<HashMap<i64, Instant>>,
+}
+
+impl TelegramRateLimiter {
+    /// runs the call, waiting out and retrying on flood wait errors
+    pub async fn run<T, F, Fut>(&self, chat_id: i64, mut call: F) -> TdResult<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = TdResult<T>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            let cooldown = self.cooldowns.lock().unwrap().get(&chat_id).copied();
+            if let Some(cooldown) = cooldown {
+                tokio::time::sleep_until(cooldown.into()).await;
+            }
+
+            match call().await {
+                Err(err) if attempt < MAX_ATTEMPTS => {
+                    let Some(seconds) = flood_wait_seconds(&err) else {
+                        return Err(err);
+                    };
+
+                    if seconds > MAX_FLOOD_WAIT_SECS {
+                        log::warn!("not waiting out a flood wait of {seconds}s in {chat_id}");
+                        return Err(err);
+                    }
+
+                    log::warn!("flood wait of {seconds}s in {chat_id}");
+                    attempt += 1;
+
+                    let now = Instant::now();
+                    let mut cooldowns = self.cooldowns.lock().unwrap();
+                    cooldowns.retain(|_, cooldown| *cooldown > now);
+                    cooldowns.insert(chat_id, now + Duration::from_secs(seconds + 1));
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// parses `FLOOD_WAIT_X` and `Too Many Requests: retry after X` errors
+fn flood_wait_seconds(err: &TdError) -> Option<u64> {
+    if let Some(seconds) = err.message.strip_prefix("FLOOD_WAIT_") {
+        return seconds.parse().ok();
+    }
+
+    if err.code == 429 {
+        return err.message.rsplit_once("retry after ")?.1.trim().parse().ok();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(code: i32, message: &str) -> TdError {
+        TdError { code, message: message.into() }
+    }
+
+    #[test]
+    fn test_flood_wait_seconds() {
+        assert_eq!(flood_wait_seconds(&error(420, "FLOOD_WAIT_17")), Some(17));
+        assert_eq!(flood_wait_seconds(&error(429, "Too Many Requests: retry after 5")), Some(5));
+        assert_eq!(flood_wait_seconds(&error(400, "Bad Request: message is too long")), None);
+        assert_eq!(flood_wait_seconds(&error(429, "Too Many Requests")), None);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_flood_wait() {
+        let rate_limiter = TelegramRateLimiter::default();
+        let mut calls = 0;
+
+        let result = rate_limiter
+            .run(1, || {
+                calls += 1;
+                let result = if calls == 1 { Err(error(420, "FLOOD_WAIT_0")) } else { Ok(calls) };
+                async move { result }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_long_flood_wait() {
+        let rate_limiter = TelegramRateLimiter::default();
+        let mut calls = 0;
+
+        let result = rate_limiter
+            .run(1, || {
+                calls += 1;
+                async { Err::<(), _>(error(420, "FLOOD_WAIT_3600")) }
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().message, "FLOOD_WAIT_3600");
+        assert_eq!(calls, 1);
+    }
+}
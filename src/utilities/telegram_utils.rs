@@ -1,16 +1,23 @@
 use std::borrow::Cow;
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use tdlib::enums::{
     self, ChatMemberStatus, ChatType, InlineKeyboardButtonType, MessageContent, MessageReplyTo,
-    ReplyMarkup, StickerFormat,
+    ReplyMarkup, StickerFormat, TextEntityType,
 };
 use tdlib::functions;
 use tdlib::types::{
-    File, FormattedText, InlineKeyboardButton, InlineKeyboardButtonTypeUrl, Message, Photo,
-    ReplyMarkupInlineKeyboard, Sticker, UpdateChatMember, User, WebPage,
+    File, FormattedText, InlineKeyboardButton, InlineKeyboardButtonTypeCallback, Message, Photo,
+    Sticker, TextEntity, UpdateChatMember, User, WebPage,
 };
 
 use super::cache::CompactChat;
+use super::keyboard::KeyboardBuilder;
+use super::text_utils::EscapeMarkdown;
+
+/// maximum length of a text message
+pub const MAX_MESSAGE_LENGTH: usize = 4096;
 
 pub trait MainUsername {
     fn main_username(&self) -> Option<&String>;
@@ -28,12 +35,17 @@ pub struct MessageImage {
 }
 
 pub fn donate_markup(name: &str, url: impl Into<String>) -> ReplyMarkup {
-    ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard {
-        rows: vec![vec![InlineKeyboardButton {
-            text: format!("donate to {name}"),
-            r#type: InlineKeyboardButtonType::Url(InlineKeyboardButtonTypeUrl { url: url.into() }),
-        }]],
-    })
+    KeyboardBuilder::new().url_button(format!("donate to {name}"), url).build()
+}
+
+/// creates a button sending `data` to the callback dispatcher when pressed
+pub fn callback_button(text: impl Into<String>, data: &str) -> InlineKeyboardButton {
+    InlineKeyboardButton {
+        text: text.into(),
+        r#type: InlineKeyboardButtonType::Callback(InlineKeyboardButtonTypeCallback {
+            data: STANDARD.encode(data),
+        }),
+    }
 }
 
 pub const fn get_message_text(content: &MessageContent) -> Option<&FormattedText> {
@@ -146,3 +158,143 @@ pub fn log_status_update(update: &UpdateChatMember, chat: &CompactChat) {
 
     log::info!("{} {}", status, chat);
 }
+
+struct Marker {
+    offset: usize,
+    opening: bool,
+    /// end of an opening marker's entity or start of a closing marker's entity
+    other_end: usize,
+    index: usize,
+    symbol: &'static str,
+}
+
+/// converts text with bold, italic and code entities to `MarkdownV2`, other
+/// entities are dropped
+pub fn entities_to_markdown(text: &str, entities: &[TextEntity]) -> String {
+    let mut markers = entities
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entity)| {
+            let symbol = match entity.r#type {
+                TextEntityType::Bold => "*",
+                TextEntityType::Italic => "_",
+                TextEntityType::Code => "`",
+                _ => return None,
+            };
+            let start = usize::try_from(entity.offset).unwrap();
+            let end = start + usize::try_from(entity.length).unwrap();
+
+            Some([
+                Marker { offset: start, opening: true, other_end: end, index, symbol },
+                Marker { offset: end, opening: false, other_end: start, index, symbol },
+            ])
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    // closing markers go first, inner entities are closed before and opened after
+    // outer ones
+    markers.sort_by(|a, b| {
+        a.offset.cmp(&b.offset).then(a.opening.cmp(&b.opening)).then_with(|| {
+            let order = b.other_end.cmp(&a.other_end);
+            if a.opening {
+                order.then(a.index.cmp(&b.index))
+            } else {
+                order.then(b.index.cmp(&a.index))
+            }
+        })
+    });
+
+    let mut markdown = String::with_capacity(text.len());
+    let mut markers = markers.into_iter().peekable();
+    let mut span = String::new();
+    let mut code_depth = 0_usize;
+    let mut offset = 0;
+
+    for char in text.chars().map(Some).chain([None]) {
+        while let Some(marker) = markers.next_if(|marker| marker.offset <= offset) {
+            push_escaped(&mut markdown, &span, code_depth > 0);
+            span.clear();
+            markdown.push_str(marker.symbol);
+
+            if marker.symbol == "`" {
+                if marker.opening {
+                    code_depth += 1;
+                } else {
+                    code_depth -= 1;
+                }
+            }
+        }
+
+        if let Some(char) = char {
+            span.push(char);
+            offset += char.len_utf16();
+        }
+    }
+
+    push_escaped(&mut markdown, &span, code_depth > 0);
+
+    markdown
+}
+
+fn push_escaped(markdown: &mut String, text: &str, in_code: bool) {
+    if in_code {
+        markdown.push_str(&text.replace('\\', "\\\\").replace('`', "\\`"));
+    } else {
+        markdown.push_str(&text.escape_markdown());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tdlib::enums::TextEntityType;
+    use tdlib::types::TextEntity;
+
+    use super::entities_to_markdown;
+
+    fn entity(offset: i32, length: i32, r#type: TextEntityType) -> TextEntity {
+        TextEntity { offset, length, r#type }
+    }
+
+    #[test]
+    fn test_entities_to_markdown() {
+        assert_eq!(entities_to_markdown("no entities.", &[]), "no entities\\.");
+        assert_eq!(
+            entities_to_markdown("hello bold world", &[entity(6, 4, TextEntityType::Bold)]),
+            "hello *bold* world"
+        );
+        assert_eq!(
+            entities_to_markdown(
+                "run a_b() now",
+                &[entity(4, 5, TextEntityType::Code), entity(0, 3, TextEntityType::Italic)]
+            ),
+            "_run_ `a_b()` now"
+        );
+        assert_eq!(
+            entities_to_markdown(
+                "bold and italic",
+                &[entity(0, 15, TextEntityType::Bold), entity(9, 6, TextEntityType::Italic)]
+            ),
+            "*bold and _italic_*"
+        );
+        assert_eq!(
+            entities_to_markdown(
+                "both",
+                &[entity(0, 4, TextEntityType::Bold), entity(0, 4, TextEntityType::Italic)]
+            ),
+            "*_both_*"
+        );
+        assert_eq!(
+            entities_to_markdown("👍 nice!", &[entity(3, 4, TextEntityType::Bold)]),
+            "👍 *nice*\\!"
+        );
+        assert_eq!(
+            entities_to_markdown("`tick`", &[entity(0, 6, TextEntityType::Code)]),
+            "`\\`tick\\``"
+        );
+        assert_eq!(
+            entities_to_markdown("link", &[entity(0, 4, TextEntityType::Underline)]),
+            "link"
+        );
+    }
+}
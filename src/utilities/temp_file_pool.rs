@@ -0,0 +1,156 @@
+use std::io::{self, Seek};
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+use tempfile::NamedTempFile;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// reuses temporary files for images sent to Telegram instead of creating and
+/// deleting one for every message.
+///
+/// reusing a path is safe as long as the file is held until the message is
+/// sent, which is when the upload has finished. the next upload from the same
+/// path has a different modification time, so it isn't mistaken for the
+/// previous file
+pub struct TempFilePool {
+    files: Mutex<Vec<NamedTempFile>>,
+    semaphore: Semaphore,
+}
+
+impl TempFilePool {
+    pub fn new(capacity: usize) -> Self {
+        // an empty pool would make every acquire wait forever
+        let capacity = capacity.max(1);
+
+        Self {
+            files: Mutex::new((0..capacity).map(|_| NamedTempFile::new().unwrap()).collect()),
+            semaphore: Semaphore::new(capacity),
+        }
+    }
+
+    /// waits until one of the files is free
+    pub async fn acquire(&self) -> PooledTempFile<'_> {
+        let permit = self.semaphore.acquire().await.unwrap();
+        let file = self.files.lock().unwrap().pop().unwrap();
+
+        PooledTempFile { file: Some(file), pool: self, permit: Some(permit) }
+    }
+}
+
+/// a temporary file which is emptied and returned to the pool when dropped
+pub struct PooledTempFile<'a> {
+    file: Option<NamedTempFile>,
+    pool: &'a TempFilePool,
+    permit: Option<SemaphorePermit<'a>>,
+}
+
+impl Deref for PooledTempFile<'_> {
+    type Target = NamedTempFile;
+
+    fn deref(&self) -> &Self::Target {
+        self.file.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledTempFile<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.file.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledTempFile<'_> {
+    fn drop(&mut self) {
+        let mut file = self.file.take().unwrap();
+
+        if let Err(err) = truncate(&mut file) {
+            log::warn!("failed to reuse a temporary file: {err}");
+            file = match NamedTempFile::new() {
+                Ok(file) => file,
+                Err(err) => {
+                    // without a file to hand out the pool has one less slot
+                    log::error!("failed to replace a temporary file, shrinking the pool: {err}");
+                    self.permit.take().unwrap().forget();
+                    return;
+                }
+            };
+        }
+
+        self.pool.files.lock().unwrap().push(file);
+    }
+}
+
+fn truncate(file: &mut NamedTempFile) -> io::Result<()> {
+    file.as_file().set_len(0)?;
+    file.rewind()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::io::Write;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use futures_util::future;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_temp_file_pool() {
+        let pool = TempFilePool::new(1);
+
+        let mut file = pool.acquire().await;
+        let path = file.path().to_owned();
+        file.write_all(b"image").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"image");
+        drop(file);
+
+        let mut file = pool.acquire().await;
+        assert_eq!(file.path(), path);
+        assert_eq!(fs::read(&path).unwrap(), b"");
+        file.write_all(b"png").unwrap();
+        assert_eq!(fs::read(file.path()).unwrap(), b"png");
+        drop(file);
+    }
+
+    #[tokio::test]
+    async fn test_empty_temp_file_pool() {
+        let pool = TempFilePool::new(0);
+        drop(pool.acquire().await);
+    }
+
+    /// compares the pool with creating a file for every image, run with
+    /// `cargo test --release bench_temp_file_pool -- --ignored --nocapture`
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "benchmark"]
+    async fn bench_temp_file_pool() {
+        const TASKS: usize = 64;
+        const ITERATIONS: usize = 200;
+
+        let pool = Arc::new(TempFilePool::new(16));
+        let start = Instant::now();
+        future::join_all((0..TASKS).map(|_| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                for _ in 0..ITERATIONS {
+                    pool.acquire().await.write_all(b"image").unwrap();
+                }
+            })
+        }))
+        .await;
+        let pooled = start.elapsed();
+
+        let start = Instant::now();
+        future::join_all((0..TASKS).map(|_| {
+            tokio::spawn(async {
+                for _ in 0..ITERATIONS {
+                    NamedTempFile::new().unwrap().write_all(b"image").unwrap();
+                }
+            })
+        }))
+        .await;
+        let unpooled = start.elapsed();
+
+        println!("pooled: {pooled:?}, NamedTempFile::new: {unpooled:?}");
+    }
+}
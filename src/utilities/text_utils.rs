@@ -1,30 +1,173 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::sync::LazyLock;
+
+use super::prompt_policy::PromptPolicy;
+
+/// how far before the limit a space is looked for when truncating at a word
+/// boundary
+const WORD_BOUNDARY_LOOKBACK: usize = 20;
+
 pub trait TruncateWithEllipsis {
     fn truncate_with_ellipsis(self, max_len: usize) -> Self;
+    fn truncate_at_word_boundary(self, max_len: usize) -> Self;
 }
 
 impl TruncateWithEllipsis for String {
     fn truncate_with_ellipsis(mut self, max_len: usize) -> Self {
         if self.chars().count() > max_len {
-            self.truncate(max_len - 1);
+            let end = self.char_indices().nth(max_len - 1).map_or(self.len(), |(i, _)| i);
+            self.truncate(end);
             self.push('…');
         }
 
         self
     }
+
+    fn truncate_at_word_boundary(self, max_len: usize) -> Self {
+        if self.chars().count() > max_len {
+            truncate_at_word_boundary(&self, max_len)
+        } else {
+            self
+        }
+    }
 }
 
-pub fn format_duration(duration: u64) -> String {
-    let hours = (duration / 3600) % 60;
-    let minutes = (duration / 60) % 60;
-    let seconds = duration % 60;
+/// truncates like `truncate_with_ellipsis`, but cuts at the last space before
+/// the limit unless the text has no space near it
+pub fn truncate_at_word_boundary(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.into();
+    }
 
-    if hours > 0 {
-        format!("{hours}h {minutes}m")
-    } else if minutes > 0 {
-        format!("{minutes}m {seconds}s")
-    } else {
-        format!("{seconds}s")
+    let kept_chars = max_chars.saturating_sub(1);
+    let end = s.char_indices().nth(kept_chars).map_or(s.len(), |(i, _)| i);
+    let head = &s[..end];
+
+    let head = head
+        .char_indices()
+        .rev()
+        .take(WORD_BOUNDARY_LOOKBACK + 1)
+        .find(|(_, char)| *char == ' ')
+        .map_or(head, |(i, _)| head[..i].trim_end());
+
+    format!("{head}…")
+}
+
+/// characters that have to be escaped in `MarkdownV2`, all of them are ASCII
+const MARKDOWN_CHARS: &[u8] = b"_*[]()~`>#+-=|{}.!\\";
+
+const IS_MARKDOWN_CHAR: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < MARKDOWN_CHARS.len() {
+        table[MARKDOWN_CHARS[i] as usize] = true;
+        i += 1;
+    }
+    table
+};
+
+pub trait EscapeMarkdown {
+    fn escape_markdown(&self) -> String;
+}
+
+impl EscapeMarkdown for str {
+    fn escape_markdown(&self) -> String {
+        let mut escaped = String::with_capacity(self.len() + self.len() / 8);
+        let mut start = 0;
+
+        // ASCII bytes never occur inside multi-byte characters, so slicing at them is
+        // safe
+        for (i, byte) in self.bytes().enumerate() {
+            if IS_MARKDOWN_CHAR[usize::from(byte)] {
+                escaped.push_str(&self[start..i]);
+                escaped.push('\\');
+                start = i;
+            }
+        }
+
+        escaped.push_str(&self[start..]);
+
+        escaped
+    }
+}
+
+pub struct FormattedDuration {
+    days: u64,
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+}
+
+impl FormattedDuration {
+    pub const fn new(duration: u64) -> Self {
+        Self {
+            days: duration / 86_400,
+            hours: (duration / 3600) % 24,
+            minutes: (duration / 60) % 60,
+            seconds: duration % 60,
+        }
+    }
+}
+
+impl Display for FormattedDuration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.days > 0 {
+            write!(f, "{}d {}h", self.days, self.hours)
+        } else if self.hours > 0 {
+            write!(f, "{}h {}m", self.hours, self.minutes)
+        } else if self.minutes > 0 {
+            write!(f, "{}m {}s", self.minutes, self.seconds)
+        } else {
+            write!(f, "{}s", self.seconds)
+        }
+    }
+}
+
+pub const fn format_duration(duration: u64) -> FormattedDuration {
+    FormattedDuration::new(duration)
+}
+
+pub fn format_duration_string(duration: u64) -> String {
+    format_duration(duration).to_string()
+}
+
+#[derive(Clone, Copy)]
+pub enum NumberLocale {
+    /// `1,234,567`
+    EnUs,
+    /// `1.234.567`
+    DeDe,
+}
+
+impl NumberLocale {
+    const fn thousands_separator(self) -> char {
+        match self {
+            Self::EnUs => ',',
+            Self::DeDe => '.',
+        }
+    }
+}
+
+/// set with `NUMBER_FORMAT_LOCALE=de_DE`, defaults to `en_US`
+pub static NUMBER_LOCALE: LazyLock<NumberLocale> =
+    LazyLock::new(|| match env::var("NUMBER_FORMAT_LOCALE").as_deref() {
+        Ok("de_DE" | "de-DE") => NumberLocale::DeDe,
+        _ => NumberLocale::EnUs,
+    });
+
+pub fn format_number(n: u64, locale: NumberLocale) -> String {
+    let digits = n.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(locale.thousands_separator());
+        }
+        formatted.push(digit);
     }
+
+    formatted
 }
 
 pub fn progress_bar(current: u32, max: u32) -> String {
@@ -50,20 +193,189 @@ pub fn progress_bar(current: u32, max: u32) -> String {
     progress
 }
 
-pub fn check_prompt(prompt: &str) -> Option<&'static str> {
-    if prompt.chars().count() > 1024 {
-        Some("this prompt is too long (>1024).")
-    } else if prompt.lines().count() > 8 {
-        Some("this prompt has too many lines (>8).")
+/// one square per image, finished ones first, at most 10 squares
+pub fn progress_bar_emoji(waiting: usize, processing: usize, finished: usize) -> String {
+    ["🟩"; 1]
+        .repeat(finished)
+        .into_iter()
+        .chain(["🟨"; 1].repeat(processing))
+        .chain(["🟥"; 1].repeat(waiting))
+        .take(10)
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PromptRejection {
+    pub message: String,
+    /// the policy field which rejected the prompt, `None` for built-in checks
+    pub policy_reason: Option<&'static str>,
+}
+
+impl PromptRejection {
+    fn new(message: impl Into<String>, policy_reason: Option<&'static str>) -> Self {
+        Self { message: message.into(), policy_reason }
+    }
+}
+
+pub fn check_prompt(prompt: &str, policy: &PromptPolicy) -> Option<PromptRejection> {
+    let chars = prompt.chars().count();
+
+    if chars > policy.max_chars {
+        Some(PromptRejection::new(
+            format!("this prompt is too long (>{}).", policy.max_chars),
+            Some("max_chars"),
+        ))
+    } else if chars < policy.min_chars {
+        Some(PromptRejection::new(
+            format!("this prompt is too short (<{}).", policy.min_chars),
+            Some("min_chars"),
+        ))
+    } else if prompt.lines().count() > policy.max_lines {
+        Some(PromptRejection::new(
+            format!("this prompt has too many lines (>{}).", policy.max_lines),
+            Some("max_lines"),
+        ))
+    } else if policy.banned_patterns.iter().any(|pattern| pattern.is_match(prompt)) {
+        Some(PromptRejection::new("this prompt isn't allowed.", Some("banned_patterns")))
+    } else if prompt.chars().any(is_bidi_control) {
+        // these can make log lines look different from what they contain
+        Some(PromptRejection::new("prompt contains bidirectional control characters.", None))
     } else {
         None
     }
 }
 
+const fn is_bidi_control(char: char) -> bool {
+    matches!(char, '\u{200e}' | '\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}')
+}
+
+/// splits text into chunks of at most `max_len` characters, preferring
+/// paragraph and sentence boundaries outside of code blocks
+pub fn split_for_telegram(text: &str, max_len: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+    let mut in_code_block = false;
+
+    while rest.chars().count() > max_len {
+        let limit = rest.char_indices().nth(max_len).map_or(rest.len(), |(i, _)| i);
+        let window = &rest[..limit];
+        let split_at = find_split(window, "\n\n", in_code_block)
+            .or_else(|| find_split(window, ".\n", in_code_block))
+            .unwrap_or(limit);
+
+        if window[..split_at].matches("```").count() % 2 == 1 {
+            in_code_block = !in_code_block;
+        }
+
+        let chunk = rest[..split_at].trim_end();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_owned());
+        }
+        rest = rest[split_at..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        chunks.push(rest.to_owned());
+    }
+
+    chunks
+}
+
+/// returns the end of the last separator in the window that isn't inside a code
+/// block
+fn find_split(window: &str, separator: &str, mut in_code_block: bool) -> Option<usize> {
+    let mut split_at = None;
+    let mut chars = window.char_indices();
+
+    while let Some((i, _)) = chars.next() {
+        if window[i..].starts_with("```") {
+            in_code_block = !in_code_block;
+            chars.nth(1);
+        } else if !in_code_block && window[i..].starts_with(separator) {
+            split_at = Some(i + separator.len());
+        }
+    }
+
+    split_at
+}
+
+/// formats `key: value` pairs as an italic `MarkdownV2` line, skipping empty
+/// values
+pub fn format_footer(items: &[(&str, impl Display)]) -> String {
+    let items = items
+        .iter()
+        .map(|(key, value)| (key, value.to_string()))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{}: {}", key.escape_markdown(), value.escape_markdown()))
+        .collect::<Vec<_>>();
+
+    if items.is_empty() {
+        return String::new();
+    }
+
+    format!("_{}_", items.join(" · "))
+}
+
+/// converts a two-letter country code to its flag emoji
+pub fn flag_emoji(country_code: &str) -> Option<String> {
+    if country_code.len() != 2 || !country_code.bytes().all(|byte| byte.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    country_code
+        .bytes()
+        .map(|byte| char::from_u32(0x1f1e6 + u32::from(byte.to_ascii_uppercase() - b'A')))
+        .collect()
+}
+
+/// number of single character insertions, deletions and substitutions needed
+/// to turn one string into the other
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, char_a) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, &char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(char_a != char_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
 #[cfg(test)]
 mod test {
+    use std::time::Instant;
+
+    use regex::Regex;
+
     use super::*;
 
+    #[test]
+    fn test_truncate_with_ellipsis() {
+        assert_eq!("short text".to_owned().truncate_with_ellipsis(20), "short text");
+        assert_eq!("hello world".to_owned().truncate_with_ellipsis(6), "hello…");
+        assert_eq!("привет мир".to_owned().truncate_with_ellipsis(5), "прив…");
+        assert_eq!("🦆🦆🦆".to_owned().truncate_with_ellipsis(2), "🦆…");
+    }
+
+    #[test]
+    fn test_truncate_at_word_boundary() {
+        assert_eq!(truncate_at_word_boundary("short text", 20), "short text");
+        assert_eq!(truncate_at_word_boundary("hello wonderful world", 18), "hello wonderful…");
+        assert_eq!(truncate_at_word_boundary("hello  world", 9), "hello…");
+        assert_eq!(truncate_at_word_boundary(&"a".repeat(30), 10), format!("{}…", "a".repeat(9)));
+        assert_eq!(
+            truncate_at_word_boundary(&format!("a {}", "b".repeat(40)), 30),
+            format!("a {}…", "b".repeat(27))
+        );
+        assert_eq!("zażółć gęślą jaźń".to_owned().truncate_at_word_boundary(14), "zażółć gęślą…");
+    }
+
     #[test]
     fn test_progress_bar() {
         assert_eq!(progress_bar(0, 10), "[--------------------]");
@@ -74,4 +386,173 @@ mod test {
         assert_eq!(progress_bar(0, 0), "[--------------------]");
         assert_eq!(progress_bar(1, 0), "[====================]");
     }
+
+    #[test]
+    fn test_progress_bar_emoji() {
+        assert_eq!(progress_bar_emoji(0, 0, 0), "");
+        assert_eq!(progress_bar_emoji(2, 1, 1), "🟩🟨🟥🟥");
+        assert_eq!(progress_bar_emoji(0, 0, 4), "🟩🟩🟩🟩");
+        assert_eq!(progress_bar_emoji(10, 2, 3).chars().count(), 10);
+        assert_eq!(progress_bar_emoji(10, 2, 3), "🟩🟩🟩🟨🟨🟥🟥🟥🟥🟥");
+    }
+
+    #[test]
+    fn test_check_prompt() {
+        let policy = PromptPolicy::default();
+        assert_eq!(check_prompt("a cat in a hat", &policy), None);
+        assert_eq!(check_prompt("مرحبا cat", &policy), None);
+        assert_eq!(
+            check_prompt(&"a".repeat(1025), &policy).unwrap().policy_reason,
+            Some("max_chars")
+        );
+        assert_eq!(
+            check_prompt("1\n2\n3\n4\n5\n6\n7\n8\n9", &policy).unwrap().policy_reason,
+            Some("max_lines")
+        );
+
+        for char in ['\u{200e}', '\u{200f}', '\u{202a}', '\u{202e}', '\u{2066}', '\u{2069}'] {
+            assert_eq!(
+                check_prompt(&format!("a cat{char}tah a ni"), &policy),
+                Some(PromptRejection::new(
+                    "prompt contains bidirectional control characters.",
+                    None
+                ))
+            );
+        }
+
+        let policy = PromptPolicy {
+            max_chars: 20,
+            min_chars: 3,
+            banned_patterns: vec![Regex::new(r"^dogs?\b").unwrap()],
+            ..PromptPolicy::default()
+        };
+        assert_eq!(check_prompt("a cat in a hat", &policy), None);
+        assert_eq!(
+            check_prompt("a cat in a very tall hat", &policy),
+            Some(PromptRejection::new("this prompt is too long (>20).", Some("max_chars")))
+        );
+        assert_eq!(check_prompt("hi", &policy).unwrap().policy_reason, Some("min_chars"));
+        assert_eq!(
+            check_prompt("dog in a hat", &policy).unwrap().policy_reason,
+            Some("banned_patterns")
+        );
+    }
+
+    #[test]
+    fn test_split_for_telegram() {
+        assert_eq!(split_for_telegram("short text", 20), vec!["short text"]);
+        assert_eq!(
+            split_for_telegram("first paragraph.\nstill first.\n\nsecond paragraph.", 40),
+            vec!["first paragraph.\nstill first.", "second paragraph."]
+        );
+        assert_eq!(
+            split_for_telegram("first sentence.\nsecond sentence.\nthird.", 35),
+            vec!["first sentence.\nsecond sentence.", "third."]
+        );
+        assert_eq!(split_for_telegram("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(
+            split_for_telegram("intro\n\n```\ncode\n\nmore code\n```\nend", 30),
+            vec!["intro", "```\ncode\n\nmore code\n```\nend"]
+        );
+        assert_eq!(split_for_telegram("żółć gęś", 4), vec!["żółć", "gęś"]);
+
+        let chunks = split_for_telegram(&"lorem ipsum.\n\n".repeat(1000), 4096);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 4096));
+        assert_eq!(chunks.join("\n\n"), "lorem ipsum.\n\n".repeat(1000).trim());
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(0, NumberLocale::EnUs), "0");
+        assert_eq!(format_number(999, NumberLocale::EnUs), "999");
+        assert_eq!(format_number(1000, NumberLocale::EnUs), "1,000");
+        assert_eq!(format_number(999_999, NumberLocale::EnUs), "999,999");
+        assert_eq!(format_number(1_000_000, NumberLocale::EnUs), "1,000,000");
+        assert_eq!(format_number(1_000_000, NumberLocale::DeDe), "1.000.000");
+        assert_eq!(format_number(u64::MAX, NumberLocale::EnUs), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!("plain text".escape_markdown(), "plain text");
+        assert_eq!("1.5s (fast)!".escape_markdown(), "1\\.5s \\(fast\\)\\!");
+        assert_eq!("snake_case *bold*".escape_markdown(), "snake\\_case \\*bold\\*");
+        assert_eq!("a\\b".escape_markdown(), "a\\\\b");
+        assert_eq!("zażółć".escape_markdown(), "zażółć");
+    }
+
+    /// compares the lookup table with checking every character against the
+    /// list, run with
+    /// `cargo test --release bench_escape_markdown -- --ignored --nocapture`
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_escape_markdown() {
+        const ITERATIONS: usize = 100_000;
+
+        let caption = "a *fluffy* cat (in space), 4k_hdr! ".repeat(30);
+        assert!(caption.len() >= 1024);
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            std::hint::black_box(std::hint::black_box(caption.as_str()).escape_markdown());
+        }
+        let table = start.elapsed();
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            let mut escaped = String::new();
+            for char in std::hint::black_box(caption.as_str()).chars() {
+                if char.is_ascii() && MARKDOWN_CHARS.contains(&(char as u8)) {
+                    escaped.push('\\');
+                }
+                escaped.push(char);
+            }
+            std::hint::black_box(escaped);
+        }
+        let per_char = start.elapsed();
+
+        println!("lookup table: {table:?}, per character: {per_char:?}");
+    }
+
+    #[test]
+    fn test_format_footer() {
+        let no_items: &[(&str, &str)] = &[];
+        assert_eq!(format_footer(no_items), "");
+        assert_eq!(format_footer(&[("model", "text-bison-001")]), "_model: text\\-bison\\-001_");
+        assert_eq!(
+            format_footer(&[("model", "llama3"), ("tokens", ""), ("latency", "1.2s")]),
+            "_model: llama3 · latency: 1\\.2s_"
+        );
+        assert_eq!(format_footer(&[("tokens", "")]), "");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration_string(0), "0s");
+        assert_eq!(format_duration_string(59), "59s");
+        assert_eq!(format_duration_string(90), "1m 30s");
+        assert_eq!(format_duration_string(3600), "1h 0m");
+        assert_eq!(format_duration_string(86_399), "23h 59m");
+        assert_eq!(format_duration_string(90_000), "1d 1h");
+        assert_eq!(format_duration_string(7 * 86_400), "7d 0h");
+    }
+
+    #[test]
+    fn test_flag_emoji() {
+        assert_eq!(flag_emoji("PL").as_deref(), Some("🇵🇱"));
+        assert_eq!(flag_emoji("us").as_deref(), Some("🇺🇸"));
+        assert_eq!(flag_emoji("USA"), None);
+        assert_eq!(flag_emoji("1A"), None);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("sd", ""), 2);
+        assert_eq!(levenshtein("", "sd"), 2);
+        assert_eq!(levenshtein("craiyon", "craiyon"), 0);
+        assert_eq!(levenshtein("criayon", "craiyon"), 2);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("żółw", "zółw"), 1);
+    }
 }
@@ -0,0 +1,65 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+const MAX_ENTRIES: usize = 20;
+
+/// recent generation durations per model
+#[derive(Default)]
+pub struct TimingHistory {
+    data: HashMap<String, VecDeque<Duration>>,
+}
+
+impl TimingHistory {
+    pub fn push(&mut self, model: &str, duration: Duration) {
+        let durations = self.data.entry(model.into()).or_default();
+
+        if durations.len() >= MAX_ENTRIES {
+            durations.pop_front();
+        }
+
+        durations.push_back(duration);
+    }
+
+    pub fn mean(&self, model: &str) -> Option<Duration> {
+        let durations = self.data.get(model).filter(|durations| !durations.is_empty())?;
+
+        Some(durations.iter().sum::<Duration>() / u32::try_from(durations.len()).unwrap())
+    }
+}
+
+/// weighs the API estimate against the historical mean
+pub fn blend_estimate(api_estimate: Duration, historical_mean: Duration) -> Duration {
+    api_estimate.mul_f64(0.6) + historical_mean.mul_f64(0.4)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_timing_history() {
+        let mut history = TimingHistory::default();
+        assert_eq!(history.mean("model"), None);
+
+        history.push("model", Duration::from_secs(10));
+        history.push("model", Duration::from_secs(20));
+        assert_eq!(history.mean("model"), Some(Duration::from_secs(15)));
+        assert_eq!(history.mean("other model"), None);
+
+        for _ in 0..MAX_ENTRIES {
+            history.push("model", Duration::from_secs(30));
+        }
+        assert_eq!(history.mean("model"), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_blend_estimate() {
+        assert_eq!(
+            blend_estimate(Duration::from_secs(10), Duration::from_secs(20)),
+            Duration::from_secs(14)
+        );
+        assert_eq!(blend_estimate(Duration::ZERO, Duration::ZERO), Duration::ZERO);
+    }
+}
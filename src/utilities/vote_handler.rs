@@ -0,0 +1,168 @@
+use image::DynamicImage;
+use tdlib::enums::{self, ReplyMarkup};
+use tdlib::functions;
+use tdlib::types::{InlineKeyboardButton, ReplyMarkupInlineKeyboard};
+
+use super::bot_state::BotState;
+use super::callback_dispatcher::CallbackContext;
+use super::database::{Vote, VoteKind};
+use super::keyboard::KeyboardBuilder;
+use super::telegram_utils;
+use crate::commands::CommandResult;
+
+/// identifies a generated image by its decoded pixels, so the same cached
+/// result shares votes
+pub fn image_hash(image: &DynamicImage) -> String {
+    format!("{:x}", md5::compute(image.as_bytes()))
+}
+
+/// adds a row with 👍 and 👎 buttons showing the current vote counts
+pub fn with_vote_buttons(
+    keyboard: KeyboardBuilder,
+    bot_state: &BotState,
+    image_hash: &str,
+    author_id: i64,
+) -> KeyboardBuilder {
+    let counts = count_votes(&bot_state.database.lock().unwrap().votes, image_hash);
+    keyboard.buttons(vote_row(image_hash, author_id, counts))
+}
+
+pub async fn callback(ctx: &CallbackContext, arguments: &str) -> CommandResult {
+    let mut arguments = arguments.split(':');
+    let (Some(kind), Some(image_hash), Some(Ok(author_id))) =
+        (arguments.next(), arguments.next(), arguments.next().map(str::parse::<i64>))
+    else {
+        ctx.answer("", false).await?;
+        return Ok(());
+    };
+
+    let kind = match kind {
+        "up" => VoteKind::Up,
+        "down" => VoteKind::Down,
+        _ => {
+            ctx.answer("", false).await?;
+            return Ok(());
+        }
+    };
+
+    let (added, counts) = {
+        let mut database = ctx.bot_state.database.lock().unwrap();
+        let added = toggle_vote(
+            &mut database.votes,
+            Vote {
+                user_id: ctx.query.sender_user_id,
+                author_id,
+                image_hash: image_hash.into(),
+                kind,
+            },
+        );
+        (added, count_votes(&database.votes, image_hash))
+    };
+
+    // a disliked result shouldn't be served again from the prompt cache
+    if added && kind == VoteKind::Down {
+        let cache_key = ctx.bot_state.prompt_cache_keys.lock().unwrap().remove(&image_hash.into());
+        if let Some(cache_key) = cache_key {
+            ctx.bot_state.prompt_cache.lock().unwrap().remove(&cache_key);
+        }
+    }
+
+    let enums::Message::Message(message) =
+        functions::get_message(ctx.query.chat_id, ctx.query.message_id, ctx.client_id).await?;
+
+    // the vote buttons are always in the first row, the others are kept as they
+    // were
+    let mut rows = match message.reply_markup {
+        Some(ReplyMarkup::InlineKeyboard(keyboard)) => keyboard.rows,
+        _ => Vec::new(),
+    };
+    let vote_row = vote_row(image_hash, author_id, counts);
+    if rows.is_empty() {
+        rows.push(vote_row);
+    } else {
+        rows[0] = vote_row;
+    }
+
+    ctx.edit_reply_markup(ReplyMarkup::InlineKeyboard(ReplyMarkupInlineKeyboard { rows })).await?;
+    ctx.answer(if added { "vote recorded." } else { "vote removed." }, false).await?;
+
+    Ok(())
+}
+
+fn vote_row(
+    image_hash: &str,
+    author_id: i64,
+    (up, down): (usize, usize),
+) -> Vec<InlineKeyboardButton> {
+    vec![
+        telegram_utils::callback_button(
+            format!("👍 {up}"),
+            &format!("vote:up:{image_hash}:{author_id}"),
+        ),
+        telegram_utils::callback_button(
+            format!("👎 {down}"),
+            &format!("vote:down:{image_hash}:{author_id}"),
+        ),
+    ]
+}
+
+/// records the vote, replacing the user's previous vote on the image.
+/// clicking the same vote again removes it. returns whether the vote was added
+fn toggle_vote(votes: &mut Vec<Vote>, vote: Vote) -> bool {
+    let existing = votes.iter().position(|existing| {
+        existing.user_id == vote.user_id && existing.image_hash == vote.image_hash
+    });
+
+    match existing {
+        Some(i) if votes[i].kind == vote.kind => {
+            votes.swap_remove(i);
+            false
+        }
+        Some(i) => {
+            votes[i] = vote;
+            true
+        }
+        None => {
+            votes.push(vote);
+            true
+        }
+    }
+}
+
+fn count_votes(votes: &[Vote], image_hash: &str) -> (usize, usize) {
+    votes.iter().filter(|vote| vote.image_hash == image_hash).fold((0, 0), |(up, down), vote| {
+        match vote.kind {
+            VoteKind::Up => (up + 1, down),
+            VoteKind::Down => (up, down + 1),
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vote(user_id: i64, image_hash: &str, kind: VoteKind) -> Vote {
+        Vote { user_id, author_id: 0, image_hash: image_hash.into(), kind }
+    }
+
+    #[test]
+    fn test_toggle_vote() {
+        let mut votes = Vec::new();
+
+        assert!(toggle_vote(&mut votes, vote(1, "a", VoteKind::Up)));
+        assert!(toggle_vote(&mut votes, vote(2, "a", VoteKind::Down)));
+        assert!(toggle_vote(&mut votes, vote(1, "b", VoteKind::Down)));
+        assert_eq!(count_votes(&votes, "a"), (1, 1));
+        assert_eq!(count_votes(&votes, "b"), (0, 1));
+
+        // changing the vote replaces it
+        assert!(toggle_vote(&mut votes, vote(2, "a", VoteKind::Up)));
+        assert_eq!(count_votes(&votes, "a"), (2, 0));
+
+        // clicking the same vote again removes it
+        assert!(!toggle_vote(&mut votes, vote(1, "a", VoteKind::Up)));
+        assert_eq!(count_votes(&votes, "a"), (1, 0));
+        assert_eq!(count_votes(&votes, "c"), (0, 0));
+    }
+}
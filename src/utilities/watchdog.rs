@@ -0,0 +1,35 @@
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::bot_state::{BotState, BotStatus};
+
+const DEFAULT_TIMEOUT: u64 = 120;
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// warns when Telegram stops delivering updates for `WATCHDOG_TIMEOUT_SECS`,
+/// which usually means the bot looks online but doesn't respond anymore
+pub async fn watch(bot_state: Arc<BotState>) {
+    let timeout = env::var("WATCHDOG_TIMEOUT_SECS")
+        .ok()
+        .and_then(|timeout| timeout.parse().ok())
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let mut stalled = false;
+
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        if !matches!(*bot_state.status.lock().unwrap(), BotStatus::Running) {
+            break;
+        }
+
+        let age = bot_state.last_update_age();
+        if age >= timeout && !stalled {
+            log::warn!("no updates received from TDLib for {age}s, the update loop might be stuck");
+            stalled = true;
+        } else if age < timeout && stalled {
+            log::info!("receiving updates from TDLib again");
+            stalled = false;
+        }
+    }
+}
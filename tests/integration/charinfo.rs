@@ -0,0 +1,17 @@
+use craiyon_bot::testing::charinfo;
+
+#[test]
+fn test_charinfo() {
+    assert_eq!(charinfo("a").text, "a U+0061 – LATIN SMALL LETTER A");
+    assert_eq!(
+        charinfo("a b").text,
+        "a U+0061 – LATIN SMALL LETTER A\n\nb U+0062 – LATIN SMALL LETTER B"
+    );
+    assert_eq!(
+        charinfo("e\u{301}").text,
+        "e\u{301}\n    e U+0065 – LATIN SMALL LETTER E\n    \u{25CC}\u{301} U+0301 – COMBINING ACUTE \
+         ACCENT"
+    );
+    assert_eq!(charinfo("🦀").text, "🦀 U+1F980 – CRAB");
+    assert!(charinfo(&"a".repeat(11)).text.ends_with('…'));
+}
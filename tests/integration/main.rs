@@ -0,0 +1,3 @@
+mod charinfo;
+mod ping;
+mod stablehorde;
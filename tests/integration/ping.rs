@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+use craiyon_bot::testing::ping_text;
+
+#[test]
+fn test_ping_text() {
+    assert_eq!(ping_text(Duration::from_micros(42_900)), "ping: 42ms");
+    assert_eq!(ping_text(Duration::ZERO), "ping: 0ms");
+}
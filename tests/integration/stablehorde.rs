@@ -0,0 +1,82 @@
+use std::io::Cursor;
+
+use craiyon_bot::testing::{CommandContextBuilder, HordeConfig, StableHorde};
+use image::{DynamicImage, ImageFormat};
+use serde_json::json;
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// only images from Cloudflare storage are accepted, so this host is resolved
+/// to the mock server
+const IMAGE_HOST: &str = "test.r2.cloudflarestorage.com";
+
+async fn mock(server: &MockServer, method_name: &str, url_path: &str, response: ResponseTemplate) {
+    Mock::given(method(method_name))
+        .and(path(url_path))
+        .respond_with(response)
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_generate_webp() {
+    let mut image = Vec::new();
+    DynamicImage::new_rgb8(1, 1).write_to(&mut Cursor::new(&mut image), ImageFormat::WebP).unwrap();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/generate/async"))
+        .and(header("apikey", "0000000000"))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({ "id": "test" })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    mock(
+        &server,
+        "GET",
+        "/generate/check/test",
+        ResponseTemplate::new(200).set_body_json(json!({
+            "done": true,
+            "faulted": false,
+            "is_possible": true,
+            "waiting": 0,
+            "processing": 0,
+            "finished": 1,
+            "queue_position": 0,
+            "wait_time": 0,
+        })),
+    )
+    .await;
+    mock(
+        &server,
+        "GET",
+        "/generate/status/test",
+        ResponseTemplate::new(200).set_body_json(json!({
+            "generations": [{
+                "img": format!("http://{IMAGE_HOST}:{}/image.webp", server.address().port()),
+                "worker_id": "worker_id",
+                "worker_name": "worker_name",
+            }],
+        })),
+    )
+    .await;
+    mock(&server, "GET", "/image.webp", ResponseTemplate::new(200).set_body_bytes(image)).await;
+
+    let ctx = CommandContextBuilder::default()
+        .http_client(
+            reqwest::Client::builder().resolve(IMAGE_HOST, *server.address()).build().unwrap(),
+        )
+        .stablehorde(HordeConfig {
+            base_url: server.uri(),
+            token: "0000000000".into(),
+            client_agent: "craiyon-bot:test:none".into(),
+        })
+        .build();
+
+    let collage = Box::pin(StableHorde::stable_diffusion().generate_collage(&ctx, "a cat".into()))
+        .await
+        .unwrap();
+    assert_eq!(collage.workers, ["worker_name"]);
+    assert!(collage.image.width() > 0 && collage.image.height() > 0);
+}